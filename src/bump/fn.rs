@@ -9,7 +9,7 @@ use crate::*;
 /// # Returns
 ///
 /// - `Option<Version>`: Parsed version if successful, None otherwise
-fn parse_version(version_str: &str) -> Option<Version> {
+pub(crate) fn parse_version(version_str: &str) -> Option<Version> {
     let parts: Vec<&str> = version_str.split('-').collect();
     let version_part: &str = parts.first()?;
     let prerelease: Option<String> = parts.get(1).map(|s: &&str| s.to_string());
@@ -60,10 +60,11 @@ fn parse_prerelease(prerelease: &str) -> Option<(&str, u64)> {
 fn get_next_prerelease(current: Option<&String>, target_type: &str) -> String {
     match current {
         Some(pre) => {
-            if let Some((pre_type, number)) = parse_prerelease(pre) {
-                if pre_type == target_type && number > 0 {
-                    return format!("{}.{}", target_type, number + 1);
-                }
+            if let Some((pre_type, number)) = parse_prerelease(pre)
+                && pre_type == target_type
+                && number > 0
+            {
+                return format!("{}.{}", target_type, number + 1);
             }
             format!("{target_type}.1")
         }
@@ -227,3 +228,279 @@ pub(crate) fn execute_bump(
         None => Err("failed to bump version".into()),
     }
 }
+
+/// Bump the `version` field of a specific manifest section
+///
+/// # Arguments
+///
+/// - `&str`: Manifest content
+/// - `&str`: Section header such as `[package]` or `[workspace.package]`
+/// - `&BumpVersionType`: Type of version bump to apply
+///
+/// # Returns
+///
+/// - `Option<(String, String, String)>`: Updated content, old version and new version
+fn bump_section_version(
+    content: &str,
+    section: &str,
+    bump_type: &BumpVersionType,
+) -> Option<(String, String, String)> {
+    let mut current_section: &str = "";
+    let mut updated_lines: Vec<String> = Vec::new();
+    let mut versions: Option<(String, String)> = None;
+    for line in content.lines() {
+        let trimmed: &str = line.trim();
+        if trimmed.starts_with('[') {
+            current_section = trimmed;
+        }
+        if versions.is_none()
+            && current_section == section
+            && let Some((version_start, version_end)) = find_version_position(line)
+            && let Some(version) = parse_version(&line[version_start..version_end])
+        {
+            let new_version: String = version_to_string(&bump_version(&version, bump_type));
+            updated_lines.push(format!(
+                "{}{}{}",
+                &line[..version_start],
+                new_version,
+                &line[version_end..]
+            ));
+            versions = Some((line[version_start..version_end].to_string(), new_version));
+            continue;
+        }
+        updated_lines.push(line.to_string());
+    }
+    let (old_version, new_version): (String, String) = versions?;
+    let mut updated_content: String = updated_lines.join("\n");
+    if content.ends_with('\n') {
+        updated_content.push('\n');
+    }
+    Some((updated_content, old_version, new_version))
+}
+
+/// Update version requirements of bumped local dependencies in a manifest
+///
+/// Only single-line dependency entries carrying both a `path` and a
+/// `version` key are updated; the requirement operator is preserved.
+///
+/// # Arguments
+///
+/// - `&str`: Manifest content
+/// - `&HashMap<String, String>`: New version per bumped package name
+///
+/// # Returns
+///
+/// - `String`: Updated manifest content
+pub(crate) fn update_dependency_requirements(
+    content: &str,
+    bumped: &HashMap<String, String>,
+) -> String {
+    let mut updated_lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let updated_line: Option<String> =
+            DEPENDENCY_REQUIREMENT_REGEX
+                .captures(line)
+                .and_then(|captures: Captures<'_>| {
+                    let key: &str = captures.get(1)?.as_str();
+                    let package: &str = PACKAGE_RENAME_REGEX
+                        .captures(line)
+                        .and_then(|rename: Captures<'_>| rename.get(1))
+                        .map_or(key, |m: regex::Match<'_>| m.as_str());
+                    let new_version: &String = bumped.get(package)?;
+                    if !line.contains("path") {
+                        return None;
+                    }
+                    let requirement: regex::Match<'_> = captures.get(3)?;
+                    let operator: &str = captures.get(2)?.as_str();
+                    Some(format!(
+                        "{}{operator}{new_version}{}",
+                        &line[..requirement.start() - operator.len()],
+                        &line[requirement.end()..]
+                    ))
+                });
+        updated_lines.push(updated_line.unwrap_or_else(|| line.to_string()));
+    }
+    let mut updated_content: String = updated_lines.join("\n");
+    if content.ends_with('\n') {
+        updated_content.push('\n');
+    }
+    updated_content
+}
+
+/// Find bumped local dependencies whose version requirement was not updated
+///
+/// Catches the entries `update_dependency_requirements` cannot rewrite, such
+/// as multi-line inline tables and `[dependencies.name]` sub-tables. Covers
+/// the dependency sections, their `[target.'cfg(..)'.*]` variants and
+/// `[workspace.dependencies]`.
+///
+/// # Arguments
+///
+/// - `&toml::Value`: Parsed manifest after the update
+/// - `&HashMap<String, String>`: New version per bumped package name
+///
+/// # Returns
+///
+/// - `Vec<String>`: Keys of the dependencies with a stale requirement
+pub(crate) fn stale_dependency_requirements(
+    doc: &toml::Value,
+    bumped: &HashMap<String, String>,
+) -> Vec<String> {
+    let mut scopes: Vec<&toml::Value> = vec![doc];
+    if let Some(targets) = doc.get("target").and_then(|t: &toml::Value| t.as_table()) {
+        scopes.extend(targets.values());
+    }
+    let mut tables: Vec<&toml::Table> = Vec::new();
+    for scope in scopes {
+        for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+            if let Some(table) = scope.get(section).and_then(|s: &toml::Value| s.as_table()) {
+                tables.push(table);
+            }
+        }
+    }
+    if let Some(table) = doc
+        .get("workspace")
+        .and_then(|w: &toml::Value| w.get("dependencies"))
+        .and_then(|d: &toml::Value| d.as_table())
+    {
+        tables.push(table);
+    }
+    let mut stale: Vec<String> = Vec::new();
+    for table in tables {
+        for (dep_key, dep_value) in table {
+            if dep_value.get("path").is_none() {
+                continue;
+            }
+            let package: &str = dep_value
+                .get("package")
+                .and_then(|p: &toml::Value| p.as_str())
+                .unwrap_or(dep_key);
+            let (Some(new_version), Some(requirement)) = (
+                bumped.get(package),
+                dep_value
+                    .get("version")
+                    .and_then(|v: &toml::Value| v.as_str()),
+            ) else {
+                continue;
+            };
+            let version: &str = requirement.trim_start_matches(['=', '^', '~', '<', '>', ' ']);
+            if version != new_version {
+                stale.push(dep_key.clone());
+            }
+        }
+    }
+    stale
+}
+
+/// Bump the version of every workspace member
+///
+/// Members with their own `version` are bumped individually, members using
+/// `version.workspace = true` follow `[workspace.package]`. Version
+/// requirements of local path dependencies are updated in every manifest;
+/// a requirement that cannot be updated fails the bump.
+///
+/// # Arguments
+///
+/// - `&str`: Path to workspace root Cargo.toml
+/// - `&BumpVersionType`: Type of version bump to apply
+///
+/// # Returns
+///
+/// - `Result<Vec<(String, String)>, Box<dyn std::error::Error>>`: Package names with their new versions
+pub(crate) fn execute_bump_workspace(
+    manifest_path: &str,
+    bump_type: &BumpVersionType,
+) -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+    let root_manifest: &Path = Path::new(manifest_path);
+    let mut root_content: String = read_to_string(root_manifest)?;
+    let workspace_version: Option<String> =
+        match bump_section_version(&root_content, "[workspace.package]", bump_type) {
+            Some((updated_content, _, new_version)) => {
+                root_content = updated_content;
+                Some(new_version)
+            }
+            None => None,
+        };
+    let mut bumped: HashMap<String, String> = HashMap::new();
+    let mut order: Vec<String> = Vec::new();
+    let mut manifests: Vec<(PathBuf, String)> = vec![(root_manifest.to_path_buf(), root_content)];
+    for dir in discover_member_dirs(root_manifest)? {
+        let member_manifest: PathBuf = dir.join("Cargo.toml");
+        let doc: toml::Value = read_manifest(&member_manifest)?;
+        let Some(package) = doc.get("package") else {
+            continue;
+        };
+        let name: String = package
+            .get("name")
+            .and_then(|n: &toml::Value| n.as_str())
+            .ok_or("package name not found")?
+            .to_string();
+        let staged: Option<usize> = manifests
+            .iter()
+            .position(|(path, _): &(PathBuf, String)| *path == member_manifest);
+        let mut content: String = match staged {
+            Some(index) => manifests.remove(index).1,
+            None => read_to_string(&member_manifest)?,
+        };
+        let new_version: Option<String> = if package.get("version").is_some_and(|v| v.is_table()) {
+            workspace_version.clone()
+        } else if let Some((updated_content, _, new_version)) =
+            bump_section_version(&content, "[package]", bump_type)
+        {
+            content = updated_content;
+            Some(new_version)
+        } else {
+            None
+        };
+        if let Some(new_version) = new_version {
+            bumped.insert(name.clone(), new_version);
+            order.push(name);
+        }
+        manifests.push((member_manifest, content));
+    }
+    if bumped.is_empty() && workspace_version.is_none() {
+        return Err("version field not found in workspace".into());
+    }
+    for (manifest, content) in &mut manifests {
+        *content = update_dependency_requirements(content, &bumped);
+        let stale: Vec<String> = stale_dependency_requirements(&toml::from_str(content)?, &bumped);
+        if !stale.is_empty() {
+            return Err(format!(
+                "could not update the version requirement of {} in {}",
+                stale.join(", "),
+                manifest.display()
+            )
+            .into());
+        }
+    }
+    for (manifest, content) in &manifests {
+        if *content != read_to_string(manifest)? {
+            write(manifest, content)?;
+        }
+    }
+    Ok(order
+        .into_iter()
+        .map(|name: String| {
+            let version: String = bumped.get(&name).cloned().unwrap_or_default();
+            (name, version)
+        })
+        .collect())
+}
+
+/// Add the bumped versions to the context of the post bump hooks
+///
+/// The package is set when a single package was bumped, the version when
+/// every package got the same one.
+///
+/// # Arguments
+///
+/// - `&[(String, String)]`: Bumped package names and their new versions
+/// - `&mut HookContext`: Context of the post hooks
+pub(crate) fn add_bumped_versions(bumped: &[(String, String)], context: &mut HookContext) {
+    let new_version: Option<String> = bumped.first().map(|(_, v): &(String, String)| v.clone());
+    let uniform: bool = bumped
+        .iter()
+        .all(|(_, v): &(String, String)| Some(v) == new_version.as_ref());
+    context.package = (bumped.len() == 1).then(|| bumped[0].0.clone());
+    context.new_version = new_version.filter(|_| uniform);
+}
@@ -0,0 +1,19 @@
+use crate::*;
+
+/// Regex pattern to match a dependency entry with a version requirement
+///
+/// This pattern matches `name = { ..., version = "^0.1.0", ... }` lines and
+/// captures the key, the requirement operator and the version.
+pub(crate) static DEPENDENCY_REQUIREMENT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    regex::Regex::new(
+        r#"^\s*"?([A-Za-z0-9_-]+)"?\s*=\s*\{.*\bversion\s*=\s*"([=^~<>]*\s*)([^"]+)""#,
+    )
+    .expect("Invalid regex pattern")
+});
+
+/// Regex pattern to match the `package` rename key of a dependency entry
+///
+/// This pattern matches `package = "real-name"` inside an inline table.
+pub(crate) static PACKAGE_RENAME_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    regex::Regex::new(r#"\bpackage\s*=\s*"([^"]+)""#).expect("Invalid regex pattern")
+});
@@ -191,3 +191,91 @@ edition = "2024"
         execute_bump(manifest_path.to_str().unwrap(), &BumpVersionType::Patch);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_execute_bump_workspace() {
+    use std::fs::write;
+    use std::path::PathBuf;
+    let tmp_dir: PathBuf = PathBuf::from("./tmp/test_bump_workspace");
+    let _ = std::fs::remove_dir_all(&tmp_dir);
+    std::fs::create_dir_all(tmp_dir.join("crates/core")).unwrap();
+    std::fs::create_dir_all(tmp_dir.join("crates/app")).unwrap();
+    write(
+        tmp_dir.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"0.3.0\"\n",
+    )
+    .unwrap();
+    write(
+        tmp_dir.join("crates/core/Cargo.toml"),
+        "[package]\nname = \"core\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    write(
+        tmp_dir.join("crates/app/Cargo.toml"),
+        "[package]\nname = \"app\"\nversion.workspace = true\n\n[dependencies]\ncore = { path = \"../core\", version = \"^0.1.0\" }\nserde = { version = \"0.1.0\" }\n",
+    )
+    .unwrap();
+    let manifest_path: PathBuf = tmp_dir.join("Cargo.toml");
+    let mut bumped: Vec<(String, String)> =
+        execute_bump_workspace(manifest_path.to_str().unwrap(), &BumpVersionType::Minor).unwrap();
+    bumped.sort();
+    assert_eq!(
+        bumped,
+        vec![
+            ("app".to_string(), "0.4.0".to_string()),
+            ("core".to_string(), "0.2.0".to_string()),
+        ]
+    );
+    let root_content: String = std::fs::read_to_string(&manifest_path).unwrap();
+    assert!(root_content.contains("version = \"0.4.0\""));
+    let app_content: String =
+        std::fs::read_to_string(tmp_dir.join("crates/app/Cargo.toml")).unwrap();
+    assert!(app_content.contains("core = { path = \"../core\", version = \"^0.2.0\" }"));
+    assert!(app_content.contains("serde = { version = \"0.1.0\" }"));
+}
+
+#[test]
+fn test_execute_bump_workspace_stale_requirement() {
+    use std::fs::write;
+    use std::path::PathBuf;
+    let tmp_dir: PathBuf = PathBuf::from("./tmp/test_bump_workspace_stale");
+    let _ = std::fs::remove_dir_all(&tmp_dir);
+    std::fs::create_dir_all(tmp_dir.join("crates/core")).unwrap();
+    std::fs::create_dir_all(tmp_dir.join("crates/app")).unwrap();
+    write(
+        tmp_dir.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    )
+    .unwrap();
+    let core_content: &str = "[package]\nname = \"core\"\nversion = \"0.1.0\"\n";
+    let app_content: &str = "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies.core]\npath = \"../core\"\nversion = \"0.1.0\"\n";
+    write(tmp_dir.join("crates/core/Cargo.toml"), core_content).unwrap();
+    write(tmp_dir.join("crates/app/Cargo.toml"), app_content).unwrap();
+    let manifest_path: PathBuf = tmp_dir.join("Cargo.toml");
+    let result: Result<Vec<(String, String)>, Box<dyn std::error::Error>> =
+        execute_bump_workspace(manifest_path.to_str().unwrap(), &BumpVersionType::Patch);
+    assert!(result.unwrap_err().to_string().contains("of core in"));
+    assert_eq!(
+        std::fs::read_to_string(tmp_dir.join("crates/core/Cargo.toml")).unwrap(),
+        core_content
+    );
+    assert_eq!(
+        std::fs::read_to_string(tmp_dir.join("crates/app/Cargo.toml")).unwrap(),
+        app_content
+    );
+}
+
+#[test]
+fn test_stale_dependency_requirements() {
+    let bumped: HashMap<String, String> = HashMap::from([
+        ("core".to_string(), "0.2.0".to_string()),
+        ("util".to_string(), "1.1.0".to_string()),
+    ]);
+    let doc: toml::Value = toml::from_str(
+        "[dependencies]\ncore = { path = \"../core\", version = \"^0.2.0\" }\nserde = { version = \"1.0.0\" }\nlocal = { path = \"../util\", package = \"util\", version = \"1.0.0\" }\n\n[target.'cfg(unix)'.dev-dependencies]\ncore = { path = \"../core\", version = \"=0.1.0\" }\n\n[workspace.dependencies]\nutil = { path = \"util\" }\n",
+    )
+    .unwrap();
+    let mut stale: Vec<String> = stale_dependency_requirements(&doc, &bumped);
+    stale.sort();
+    assert_eq!(stale, vec!["core".to_string(), "local".to_string()]);
+}
@@ -9,6 +9,16 @@ pub(crate) enum CommandType {
     Bump,
     /// Publish packages in monorepo
     Publish,
+    /// Bump, tag and publish a release
+    Release,
+    /// Run a command in every workspace package
+    Exec,
+    /// Show workspace packages and their metadata
+    Info,
+    /// Export the workspace dependency graph
+    Graph,
+    /// Report clippy diagnostics grouped by lint
+    Lint,
     /// Create a new project from template
     New,
     /// Generate template components
@@ -9,11 +9,57 @@ use crate::*;
 /// - `Args`: Parsed arguments
 pub(crate) fn parse_args() -> Args {
     let raw_args: Vec<String> = args().collect();
+    parse_args_from(&raw_args)
+}
+
+/// Parse a list of command line arguments
+///
+/// # Arguments
+///
+/// - `&[String]`: Arguments including the program name
+///
+/// # Returns
+///
+/// - `Args`: Parsed arguments
+pub(crate) fn parse_args_from(raw_args: &[String]) -> Args {
     let mut command: CommandType = CommandType::Help;
     let mut check: bool = false;
     let mut manifest_path: Option<String> = None;
     let mut bump_type: Option<BumpVersionType> = None;
+    let mut workspace: bool = false;
     let mut max_retries: u32 = 3;
+    let mut jobs: usize = 1;
+    let mut index_url: Option<String> = None;
+    let mut index_timeout: u64 = 300;
+    let mut retry_max_delay: u64 = 60;
+    let mut retry_jitter: u64 = 1000;
+    let mut packages: Vec<String> = Vec::new();
+    let mut excludes: Vec<String> = Vec::new();
+    let mut from: Option<String> = None;
+    let mut changed: bool = false;
+    let mut changed_since: Option<String> = None;
+    let mut with_dependents: bool = false;
+    let mut registry: Option<String> = None;
+    let mut index: Option<String> = None;
+    let mut features: Vec<String> = Vec::new();
+    let mut no_verify: bool = false;
+    let mut target: Option<String> = None;
+    let mut report: Option<String> = None;
+    let mut report_format: Option<String> = None;
+    let mut keep_going: bool = false;
+    let mut paths: Vec<String> = Vec::new();
+    let mut diff: bool = false;
+    let mut clippy: Option<bool> = None;
+    let mut clippy_only: bool = false;
+    let mut lint_type: Option<LintType> = None;
+    let mut fix: bool = false;
+    let mut json: bool = false;
+    let mut graph_format: Option<String> = None;
+    let mut edges: Option<String> = None;
+    let mut levels: bool = false;
+    let mut invert: Option<String> = None;
+    let mut yes: bool = false;
+    let mut extra_args: Vec<String> = Vec::new();
     let mut project_name: Option<String> = None;
     let mut template_type: Option<TemplateType> = None;
     let mut model_sub_type: Option<ModelSubType> = None;
@@ -28,69 +74,108 @@ pub(crate) fn parse_args() -> Args {
             "-v" | "--version" => {
                 command = CommandType::Version;
             }
-            "fmt" => {
-                if command == CommandType::Help || command == CommandType::Version {
-                    command = CommandType::Fmt;
+            "fmt" if command == CommandType::Help || command == CommandType::Version => {
+                command = CommandType::Fmt;
+            }
+            "watch" if command == CommandType::Help || command == CommandType::Version => {
+                command = CommandType::Watch;
+            }
+            "bump" if command == CommandType::Help || command == CommandType::Version => {
+                command = CommandType::Bump;
+            }
+            "publish" if command == CommandType::Help || command == CommandType::Version => {
+                command = CommandType::Publish;
+            }
+            "release" if command == CommandType::Help || command == CommandType::Version => {
+                command = CommandType::Release;
+            }
+            "exec" | "foreach"
+                if command == CommandType::Help || command == CommandType::Version =>
+            {
+                command = CommandType::Exec;
+            }
+            "--keep-going" => {
+                keep_going = true;
+            }
+            "info" if command == CommandType::Help || command == CommandType::Version => {
+                command = CommandType::Info;
+            }
+            "--json" => {
+                json = true;
+            }
+            "graph" if command == CommandType::Help || command == CommandType::Version => {
+                command = CommandType::Graph;
+            }
+            "--format" => {
+                i += 1;
+                if i < raw_args.len() {
+                    graph_format = Some(raw_args[i].clone());
                 }
             }
-            "watch" => {
-                if command == CommandType::Help || command == CommandType::Version {
-                    command = CommandType::Watch;
+            "-e" | "--edges" => {
+                i += 1;
+                if i < raw_args.len() {
+                    edges = Some(raw_args[i].clone());
+                }
+            }
+            "--levels" => {
+                levels = true;
+            }
+            "-i" | "--invert" => {
+                i += 1;
+                if i < raw_args.len() {
+                    invert = Some(raw_args[i].clone());
                 }
             }
-            "bump" => {
-                if command == CommandType::Help || command == CommandType::Version {
-                    command = CommandType::Bump;
+            "-y" | "--yes" => {
+                yes = true;
+            }
+            "lint" if command == CommandType::Help || command == CommandType::Version => {
+                command = CommandType::Lint;
+                if i + 1 < raw_args.len() && !raw_args[i + 1].starts_with('-') {
+                    i += 1;
+                    lint_type = LintType::from_str(&raw_args[i]).ok();
                 }
             }
-            "publish" => {
-                if command == CommandType::Help || command == CommandType::Version {
-                    command = CommandType::Publish;
+            "new" if command == CommandType::Help || command == CommandType::Version => {
+                command = CommandType::New;
+                i += 1;
+                if i < raw_args.len()
+                    && !raw_args[i].starts_with("--")
+                    && !raw_args[i].starts_with("-")
+                {
+                    project_name = Some(raw_args[i].clone());
+                } else {
+                    i -= 1;
                 }
             }
-            "new" => {
-                if command == CommandType::Help || command == CommandType::Version {
-                    command = CommandType::New;
+            "template" if command == CommandType::Help || command == CommandType::Version => {
+                command = CommandType::Template;
+                i += 1;
+                if i < raw_args.len()
+                    && !raw_args[i].starts_with("--")
+                    && !raw_args[i].starts_with("-")
+                {
+                    let type_str: &str = &raw_args[i];
+                    template_type = TemplateType::from_str(type_str).ok();
                     i += 1;
-                    if i < raw_args.len()
+                    if template_type == Some(TemplateType::Model)
+                        && i < raw_args.len()
                         && !raw_args[i].starts_with("--")
                         && !raw_args[i].starts_with("-")
                     {
-                        project_name = Some(raw_args[i].clone());
-                    } else {
-                        i -= 1;
+                        let sub_type_str: &str = &raw_args[i];
+                        model_sub_type = ModelSubType::from_str(sub_type_str).ok();
+                        i += 1;
                     }
-                }
-            }
-            "template" => {
-                if command == CommandType::Help || command == CommandType::Version {
-                    command = CommandType::Template;
-                    i += 1;
                     if i < raw_args.len()
                         && !raw_args[i].starts_with("--")
                         && !raw_args[i].starts_with("-")
                     {
-                        let type_str: &str = &raw_args[i];
-                        template_type = TemplateType::from_str(type_str).ok();
+                        component_name = Some(raw_args[i].clone());
                         i += 1;
-                        if template_type == Some(TemplateType::Model)
-                            && i < raw_args.len()
-                            && !raw_args[i].starts_with("--")
-                            && !raw_args[i].starts_with("-")
-                        {
-                            let sub_type_str: &str = &raw_args[i];
-                            model_sub_type = ModelSubType::from_str(sub_type_str).ok();
-                            i += 1;
-                        }
-                        if i < raw_args.len()
-                            && !raw_args[i].starts_with("--")
-                            && !raw_args[i].starts_with("-")
-                        {
-                            component_name = Some(raw_args[i].clone());
-                            i += 1;
-                        }
-                        i -= 1;
                     }
+                    i -= 1;
                 }
             }
             "--patch" => {
@@ -117,6 +202,24 @@ pub(crate) fn parse_args() -> Args {
             "--check" => {
                 check = true;
             }
+            "--diff" => {
+                diff = true;
+            }
+            "--clippy" => {
+                clippy = Some(true);
+            }
+            "--no-clippy" => {
+                clippy = Some(false);
+            }
+            "--clippy-only" => {
+                clippy_only = true;
+            }
+            "--fix" => {
+                fix = true;
+            }
+            "--workspace" => {
+                workspace = true;
+            }
             "--manifest-path" => {
                 i += 1;
                 if i < raw_args.len() {
@@ -124,13 +227,132 @@ pub(crate) fn parse_args() -> Args {
                 }
             }
             "--max-retries" => {
+                i += 1;
+                if i < raw_args.len()
+                    && let Ok(n) = raw_args[i].parse::<u32>()
+                {
+                    max_retries = n;
+                }
+            }
+            "-j" | "--jobs" => {
+                i += 1;
+                if i < raw_args.len()
+                    && let Ok(n) = raw_args[i].parse::<usize>()
+                {
+                    jobs = n.max(1);
+                }
+            }
+            "--index-url" => {
                 i += 1;
                 if i < raw_args.len() {
-                    if let Ok(n) = raw_args[i].parse::<u32>() {
-                        max_retries = n;
-                    }
+                    index_url = Some(raw_args[i].clone());
+                }
+            }
+            "--index-timeout" => {
+                i += 1;
+                if i < raw_args.len()
+                    && let Ok(n) = raw_args[i].parse::<u64>()
+                {
+                    index_timeout = n;
                 }
             }
+            "--retry-max-delay" => {
+                i += 1;
+                if i < raw_args.len()
+                    && let Ok(n) = raw_args[i].parse::<u64>()
+                {
+                    retry_max_delay = n;
+                }
+            }
+            "--retry-jitter" => {
+                i += 1;
+                if i < raw_args.len()
+                    && let Ok(n) = raw_args[i].parse::<u64>()
+                {
+                    retry_jitter = n;
+                }
+            }
+            "-p" | "--package" => {
+                i += 1;
+                if i < raw_args.len() {
+                    packages.push(raw_args[i].clone());
+                }
+            }
+            "--exclude" => {
+                i += 1;
+                if i < raw_args.len() {
+                    excludes.push(raw_args[i].clone());
+                }
+            }
+            "--from" => {
+                i += 1;
+                if i < raw_args.len() {
+                    from = Some(raw_args[i].clone());
+                }
+            }
+            "--changed" => {
+                changed = true;
+            }
+            "--changed-since" => {
+                i += 1;
+                if i < raw_args.len() {
+                    changed_since = Some(raw_args[i].clone());
+                }
+            }
+            "--with-dependents" => {
+                with_dependents = true;
+            }
+            "--registry" => {
+                i += 1;
+                if i < raw_args.len() {
+                    registry = Some(raw_args[i].clone());
+                }
+            }
+            "--index" => {
+                i += 1;
+                if i < raw_args.len() {
+                    index = Some(raw_args[i].clone());
+                }
+            }
+            "-F" | "--features" => {
+                i += 1;
+                if i < raw_args.len() {
+                    features.extend(
+                        raw_args[i]
+                            .split([',', ' '])
+                            .filter(|s: &&str| !s.is_empty())
+                            .map(|s: &str| s.to_string()),
+                    );
+                }
+            }
+            "--no-verify" => {
+                no_verify = true;
+            }
+            "--target" => {
+                i += 1;
+                if i < raw_args.len() {
+                    target = Some(raw_args[i].clone());
+                }
+            }
+            "--report" => {
+                i += 1;
+                if i < raw_args.len() {
+                    report = Some(raw_args[i].clone());
+                }
+            }
+            "--report-format" => {
+                i += 1;
+                if i < raw_args.len() {
+                    report_format = Some(raw_args[i].clone());
+                }
+            }
+            path if command == CommandType::Fmt && !path.starts_with('-') => {
+                paths.push(path.to_string());
+            }
+            "--" => {
+                extra_args = raw_args[i + 1..].to_vec();
+                break;
+            }
             _ => {}
         }
         i += 1;
@@ -140,7 +362,40 @@ pub(crate) fn parse_args() -> Args {
         check,
         manifest_path,
         bump_type,
+        workspace,
         max_retries,
+        jobs,
+        index_url,
+        index_timeout,
+        retry_max_delay,
+        retry_jitter,
+        packages,
+        excludes,
+        from,
+        changed,
+        changed_since,
+        with_dependents,
+        registry,
+        index,
+        features,
+        no_verify,
+        target,
+        report,
+        report_format,
+        keep_going,
+        paths,
+        diff,
+        clippy,
+        clippy_only,
+        lint_type,
+        fix,
+        json,
+        graph_format,
+        edges,
+        levels,
+        invert,
+        yes,
+        extra_args,
         project_name,
         template_type,
         model_sub_type,
@@ -11,8 +11,74 @@ pub struct Args {
     pub manifest_path: Option<String>,
     /// Bump type for bump command
     pub bump_type: Option<BumpVersionType>,
+    /// Bump every workspace member for bump command
+    pub workspace: bool,
     /// Maximum retry attempts for publish command
     pub max_retries: u32,
+    /// Maximum concurrent publishes per dependency level for publish command
+    pub jobs: usize,
+    /// Registry index polled after each publish for publish command
+    pub index_url: Option<String>,
+    /// Seconds to wait for index propagation for publish command
+    pub index_timeout: u64,
+    /// Maximum retry backoff in seconds for publish command
+    pub retry_max_delay: u64,
+    /// Maximum retry jitter in milliseconds for publish command
+    pub retry_jitter: u64,
+    /// Selected packages for publish command
+    pub packages: Vec<String>,
+    /// Excluded packages for publish command
+    pub excludes: Vec<String>,
+    /// Package to resume from for publish command
+    pub from: Option<String>,
+    /// Only handle packages changed since their last release tag
+    pub changed: bool,
+    /// Only handle packages changed since this git reference
+    pub changed_since: Option<String>,
+    /// Include dependents of changed packages
+    pub with_dependents: bool,
+    /// Registry name for publish command
+    pub registry: Option<String>,
+    /// Registry index URL for publish command
+    pub index: Option<String>,
+    /// Features to activate for publish command
+    pub features: Vec<String>,
+    /// Skip verification for publish command
+    pub no_verify: bool,
+    /// Target triple for publish command
+    pub target: Option<String>,
+    /// Report file path for publish command
+    pub report: Option<String>,
+    /// Report format for publish command, validated when building the publish options
+    pub report_format: Option<String>,
+    /// Continue exec after a package failed
+    pub keep_going: bool,
+    /// Files, directories or packages to restrict fmt to
+    pub paths: Vec<String>,
+    /// Print a diff of the changes made by fmt
+    pub diff: bool,
+    /// Force the clippy stage of fmt on or off
+    pub clippy: Option<bool>,
+    /// Only run the clippy stage of fmt
+    pub clippy_only: bool,
+    /// Check run by lint command
+    pub lint_type: Option<LintType>,
+    /// Fix the issues found by lint command
+    pub fix: bool,
+    /// Print JSON output
+    pub json: bool,
+    /// Output format for graph command
+    pub graph_format: Option<String>,
+    /// Dependency kinds drawn by graph command
+    pub edges: Option<String>,
+    /// Group graph nodes by publish level
+    pub levels: bool,
+    /// Package whose reverse dependencies are graphed
+    pub invert: Option<String>,
+    /// Answer yes at every release checkpoint
+    pub yes: bool,
+    /// Arguments after `--`, passed through to the underlying command
+    pub extra_args: Vec<String>,
     /// Project name for new command
     pub project_name: Option<String>,
     /// Template type for template command
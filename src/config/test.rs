@@ -7,7 +7,40 @@ fn test_args_default_values() {
         check: false,
         manifest_path: None,
         bump_type: None,
+        workspace: false,
         max_retries: 3,
+        jobs: 1,
+        index_url: None,
+        index_timeout: 300,
+        retry_max_delay: 60,
+        retry_jitter: 1000,
+        packages: Vec::new(),
+        excludes: Vec::new(),
+        from: None,
+        changed: false,
+        changed_since: None,
+        with_dependents: false,
+        registry: None,
+        index: None,
+        features: Vec::new(),
+        no_verify: false,
+        target: None,
+        report: None,
+        report_format: None,
+        keep_going: false,
+        paths: Vec::new(),
+        diff: false,
+        clippy: None,
+        clippy_only: false,
+        lint_type: None,
+        fix: false,
+        json: false,
+        graph_format: None,
+        edges: None,
+        levels: false,
+        invert: None,
+        yes: false,
+        extra_args: Vec::new(),
         project_name: None,
         template_type: None,
         model_sub_type: None,
@@ -30,7 +63,40 @@ fn test_args_with_values() {
         check: true,
         manifest_path: Some("./test/Cargo.toml".to_string()),
         bump_type: Some(BumpVersionType::Minor),
+        workspace: false,
         max_retries: 5,
+        jobs: 1,
+        index_url: None,
+        index_timeout: 300,
+        retry_max_delay: 60,
+        retry_jitter: 1000,
+        packages: Vec::new(),
+        excludes: Vec::new(),
+        from: None,
+        changed: false,
+        changed_since: None,
+        with_dependents: false,
+        registry: None,
+        index: None,
+        features: Vec::new(),
+        no_verify: false,
+        target: None,
+        report: None,
+        report_format: None,
+        keep_going: false,
+        paths: Vec::new(),
+        diff: false,
+        clippy: None,
+        clippy_only: false,
+        lint_type: None,
+        fix: false,
+        json: false,
+        graph_format: None,
+        edges: None,
+        levels: false,
+        invert: None,
+        yes: false,
+        extra_args: Vec::new(),
         project_name: Some("test-project".to_string()),
         template_type: Some(TemplateType::Controller),
         model_sub_type: None,
@@ -52,7 +118,40 @@ fn test_args_with_model_subtype() {
         check: false,
         manifest_path: None,
         bump_type: None,
+        workspace: false,
         max_retries: 3,
+        jobs: 1,
+        index_url: None,
+        index_timeout: 300,
+        retry_max_delay: 60,
+        retry_jitter: 1000,
+        packages: Vec::new(),
+        excludes: Vec::new(),
+        from: None,
+        changed: false,
+        changed_since: None,
+        with_dependents: false,
+        registry: None,
+        index: None,
+        features: Vec::new(),
+        no_verify: false,
+        target: None,
+        report: None,
+        report_format: None,
+        keep_going: false,
+        paths: Vec::new(),
+        diff: false,
+        clippy: None,
+        clippy_only: false,
+        lint_type: None,
+        fix: false,
+        json: false,
+        graph_format: None,
+        edges: None,
+        levels: false,
+        invert: None,
+        yes: false,
+        extra_args: Vec::new(),
         project_name: None,
         template_type: Some(TemplateType::Model),
         model_sub_type: Some(ModelSubType::Request),
@@ -69,6 +168,11 @@ fn test_command_type_enum_values() {
     let _: CommandType = CommandType::Watch;
     let _: CommandType = CommandType::Bump;
     let _: CommandType = CommandType::Publish;
+    let _: CommandType = CommandType::Release;
+    let _: CommandType = CommandType::Graph;
+    let _: CommandType = CommandType::Lint;
+    let _: CommandType = CommandType::Exec;
+    let _: CommandType = CommandType::Info;
     let _: CommandType = CommandType::New;
     let _: CommandType = CommandType::Template;
     let _: CommandType = CommandType::Help;
@@ -82,7 +186,40 @@ fn test_args_clone() {
         check: true,
         manifest_path: Some("./test/Cargo.toml".to_string()),
         bump_type: Some(BumpVersionType::Minor),
+        workspace: false,
         max_retries: 5,
+        jobs: 1,
+        index_url: None,
+        index_timeout: 300,
+        retry_max_delay: 60,
+        retry_jitter: 1000,
+        packages: Vec::new(),
+        excludes: Vec::new(),
+        from: None,
+        changed: false,
+        changed_since: None,
+        with_dependents: false,
+        registry: None,
+        index: None,
+        features: Vec::new(),
+        no_verify: false,
+        target: None,
+        report: None,
+        report_format: None,
+        keep_going: false,
+        paths: Vec::new(),
+        diff: false,
+        clippy: None,
+        clippy_only: false,
+        lint_type: None,
+        fix: false,
+        json: false,
+        graph_format: None,
+        edges: None,
+        levels: false,
+        invert: None,
+        yes: false,
+        extra_args: Vec::new(),
         project_name: Some("test-project".to_string()),
         template_type: Some(TemplateType::Controller),
         model_sub_type: None,
@@ -0,0 +1,26 @@
+use crate::*;
+
+/// Error types for exec operation
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ExecError {
+    /// No command was given after `--`
+    #[error("No command given. Usage: hyperlane-cli exec -- <COMMAND> [ARGS]...")]
+    MissingCommand,
+    /// Package selection failed
+    #[error(transparent)]
+    PublishError(#[from] PublishError),
+    /// IO error
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+/// Outcome of running the command in a package
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ExecStatus {
+    /// The command exited successfully
+    Success,
+    /// The command failed or could not be started
+    Failed,
+    /// The command was not run because an earlier package failed
+    Skipped,
+}
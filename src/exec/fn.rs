@@ -0,0 +1,211 @@
+use crate::*;
+
+/// Print every line of a child output stream with a package prefix
+///
+/// # Arguments
+///
+/// - `R`: Output stream of the child process
+/// - `&str`: Prefix printed before each line
+/// - `bool`: Print to stderr instead of stdout
+async fn print_prefixed_lines<R>(stream: R, prefix: &str, to_stderr: bool)
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    let mut lines: tokio::io::Lines<tokio::io::BufReader<R>> =
+        tokio::io::AsyncBufReadExt::lines(tokio::io::BufReader::new(stream));
+    while let Ok(Some(line)) = lines.next_line().await {
+        if to_stderr {
+            eprintln!("{prefix} {line}");
+        } else {
+            println!("{prefix} {line}");
+        }
+    }
+}
+
+/// Run the command in a package directory
+///
+/// # Arguments
+///
+/// - `&Package`: Package to run the command in
+/// - `&[String]`: Program and arguments
+/// - `&str`: Prefix printed before each output line
+///
+/// # Returns
+///
+/// - `ExecResult`: Outcome of the command
+async fn run_in_package(package: &Package, command: &[String], prefix: &str) -> ExecResult {
+    let start: Instant = Instant::now();
+    let failed = |exit_code: Option<i32>| -> ExecResult {
+        ExecResult {
+            package_name: package.name.clone(),
+            status: ExecStatus::Failed,
+            exit_code,
+            duration: start.elapsed(),
+        }
+    };
+    let spawned: Result<tokio::process::Child, std::io::Error> = Command::new(&command[0])
+        .args(&command[1..])
+        .current_dir(&package.path)
+        .env(HOOK_ENV_PACKAGE, &package.name)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child: tokio::process::Child = match spawned {
+        Ok(child) => child,
+        Err(error) => {
+            eprintln!("{prefix} failed to run {}: {error}", command[0]);
+            return failed(None);
+        }
+    };
+    let stdout: Option<tokio::process::ChildStdout> = child.stdout.take();
+    let stderr: Option<tokio::process::ChildStderr> = child.stderr.take();
+    tokio::join!(
+        async {
+            if let Some(stdout) = stdout {
+                print_prefixed_lines(stdout, prefix, false).await;
+            }
+        },
+        async {
+            if let Some(stderr) = stderr {
+                print_prefixed_lines(stderr, prefix, true).await;
+            }
+        }
+    );
+    match child.wait().await {
+        Ok(status) if status.success() => ExecResult {
+            package_name: package.name.clone(),
+            status: ExecStatus::Success,
+            exit_code: status.code(),
+            duration: start.elapsed(),
+        },
+        Ok(status) => failed(status.code()),
+        Err(_) => failed(None),
+    }
+}
+
+/// Build a result for a package that was not run
+///
+/// # Arguments
+///
+/// - `&Package`: Skipped package
+///
+/// # Returns
+///
+/// - `ExecResult`: Skipped result
+fn skipped_result(package: &Package) -> ExecResult {
+    ExecResult {
+        package_name: package.name.clone(),
+        status: ExecStatus::Skipped,
+        exit_code: None,
+        duration: Duration::ZERO,
+    }
+}
+
+/// Format exec results as an aligned summary table
+///
+/// # Arguments
+///
+/// - `&[ExecResult]`: Results in execution order
+///
+/// # Returns
+///
+/// - `String`: Table with package, status, exit code and duration columns
+pub(crate) fn format_exec_summary(results: &[ExecResult]) -> String {
+    let package_width: usize = results
+        .iter()
+        .map(|result: &ExecResult| result.package_name.len())
+        .chain(std::iter::once("PACKAGE".len()))
+        .max()
+        .unwrap_or_default();
+    let mut table: String = format!(
+        "{:<package_width$}  {:<7}  {:<4}  DURATION\n",
+        "PACKAGE", "STATUS", "CODE"
+    );
+    for result in results {
+        let code: String = result
+            .exit_code
+            .map(|code: i32| code.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        table.push_str(&format!(
+            "{:<package_width$}  {:<7}  {:<4}  {:.1}s\n",
+            result.package_name,
+            result.status.as_str(),
+            code,
+            result.duration.as_secs_f64()
+        ));
+    }
+    table
+}
+
+/// Execute exec command
+///
+/// Runs the command in every selected package directory in topological
+/// order. With more than one job the packages of a dependency level run
+/// concurrently. After a failure the remaining packages are skipped unless
+/// `keep_going` is set.
+///
+/// # Arguments
+///
+/// - `&str`: Path to workspace root Cargo.toml
+/// - `&ExecOptions`: Exec options
+///
+/// # Returns
+///
+/// - `Result<Vec<ExecResult>, ExecError>`: Results in execution order
+pub(crate) async fn execute_exec(
+    manifest_path: &str,
+    options: &ExecOptions,
+) -> Result<Vec<ExecResult>, ExecError> {
+    if options.command.is_empty() {
+        return Err(ExecError::MissingCommand);
+    }
+    let path: &Path = Path::new(manifest_path);
+    let packages: Vec<Package> = discover_packages(path)?;
+    let default_members: Option<Vec<String>> = discover_default_members(path)?;
+    let selected: Vec<Package> =
+        select_packages_by_name(&packages, default_members, &options.selection)?;
+    let selected: Vec<Package> =
+        select_from(&packages, selected, options.selection.from.as_deref())?;
+    let selected: Vec<Package> = filter_changed_packages(selected, &options.selection).await?;
+    let width: usize = selected
+        .iter()
+        .map(|package: &Package| package.name.len())
+        .max()
+        .unwrap_or_default();
+    let prefix = |package: &Package| -> String { format!("[{:<width$}]", package.name) };
+    let levels: Vec<Vec<Package>> = if options.jobs > 1 {
+        topological_levels(&selected)?
+    } else {
+        topological_sort(&selected)?
+            .into_iter()
+            .map(|package: Package| vec![package])
+            .collect()
+    };
+    let mut results: Vec<ExecResult> = Vec::new();
+    let mut failed: bool = false;
+    for level in levels {
+        if failed && !options.keep_going {
+            results.extend(level.iter().map(skipped_result));
+            continue;
+        }
+        let semaphore: Arc<Semaphore> = Arc::new(Semaphore::new(options.jobs.max(1)));
+        let mut join_set: JoinSet<(usize, ExecResult)> = JoinSet::new();
+        for (index, package) in level.into_iter().enumerate() {
+            let semaphore: Arc<Semaphore> = Arc::clone(&semaphore);
+            let command: Vec<String> = options.command.clone();
+            let prefix: String = prefix(&package);
+            join_set.spawn(async move {
+                let _permit: Result<tokio::sync::OwnedSemaphorePermit, tokio::sync::AcquireError> =
+                    semaphore.acquire_owned().await;
+                (index, run_in_package(&package, &command, &prefix).await)
+            });
+        }
+        let mut indexed_results: Vec<(usize, ExecResult)> = join_set.join_all().await;
+        indexed_results.sort_by_key(|(index, _): &(usize, ExecResult)| *index);
+        for (_, result) in indexed_results {
+            failed |= result.status == ExecStatus::Failed;
+            results.push(result);
+        }
+    }
+    Ok(results)
+}
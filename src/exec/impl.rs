@@ -0,0 +1,27 @@
+use crate::*;
+
+impl From<&Args> for ExecOptions {
+    fn from(args: &Args) -> Self {
+        Self {
+            command: args.extra_args.clone(),
+            jobs: args.jobs,
+            keep_going: args.keep_going,
+            selection: PackageSelection::from(args),
+        }
+    }
+}
+
+impl ExecStatus {
+    /// Get the lowercase name used in the summary
+    ///
+    /// # Returns
+    ///
+    /// - `&'static str`: Status name
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "ok",
+            Self::Failed => "failed",
+            Self::Skipped => "skipped",
+        }
+    }
+}
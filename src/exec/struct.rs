@@ -0,0 +1,27 @@
+use crate::*;
+
+/// Options for the exec command
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ExecOptions {
+    /// Program and arguments run in every package
+    pub command: Vec<String>,
+    /// Maximum number of packages run concurrently within a level
+    pub jobs: usize,
+    /// Continue with the remaining packages after a failure
+    pub keep_going: bool,
+    /// Packages to run the command in
+    pub selection: PackageSelection,
+}
+
+/// Result of running the command in a single package
+#[derive(Clone, Debug)]
+pub(crate) struct ExecResult {
+    /// Package name
+    pub package_name: String,
+    /// Outcome of the command
+    pub status: ExecStatus,
+    /// Exit code if the command exited normally
+    pub exit_code: Option<i32>,
+    /// Time the command ran
+    pub duration: std::time::Duration,
+}
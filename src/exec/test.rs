@@ -0,0 +1,124 @@
+use crate::*;
+
+#[cfg(unix)]
+#[test]
+fn test_execute_exec_order_and_fail_fast() {
+    let root: PathBuf = PathBuf::from("./tmp/test_exec");
+    let _ = std::fs::remove_dir_all(&root);
+    create_dir_all(root.join("app")).unwrap();
+    create_dir_all(root.join("core")).unwrap();
+    write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"app\", \"core\"]\n",
+    )
+    .unwrap();
+    write(
+        root.join("app/Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\ncore = { path = \"../core\" }\n",
+    )
+    .unwrap();
+    write(
+        root.join("core/Cargo.toml"),
+        "[package]\nname = \"core\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    let args: Args = Args {
+        extra_args: vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "echo $HYPERLANE_PACKAGE >> ../order.txt; test $HYPERLANE_PACKAGE != core".to_string(),
+        ],
+        ..parse_args_from(&["hyperlane-cli".to_string(), "exec".to_string()])
+    };
+    let manifest_path: String = root.join("Cargo.toml").display().to_string();
+    let rt: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    let options: ExecOptions = ExecOptions::from(&args);
+    let results: Vec<ExecResult> = rt.block_on(execute_exec(&manifest_path, &options)).unwrap();
+    let statuses: Vec<(String, ExecStatus)> = results
+        .iter()
+        .map(|r: &ExecResult| (r.package_name.clone(), r.status))
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            ("core".to_string(), ExecStatus::Failed),
+            ("app".to_string(), ExecStatus::Skipped),
+        ]
+    );
+    assert_eq!(results[0].exit_code, Some(1));
+    let keep_going: ExecOptions = ExecOptions {
+        keep_going: true,
+        ..options
+    };
+    let results: Vec<ExecResult> = rt
+        .block_on(execute_exec(&manifest_path, &keep_going))
+        .unwrap();
+    assert_eq!(results[1].status, ExecStatus::Success);
+    assert_eq!(
+        read_to_string(root.join("order.txt")).unwrap(),
+        "core\ncore\napp\n"
+    );
+    let summary: String = format_exec_summary(&results);
+    assert!(summary.starts_with("PACKAGE  STATUS   CODE  DURATION\n"));
+    assert!(summary.contains("core     failed   1"));
+}
+
+#[test]
+fn test_execute_exec_requires_command() {
+    let args: Args = parse_args_from(&["hyperlane-cli".to_string(), "foreach".to_string()]);
+    assert_eq!(args.command, CommandType::Exec);
+    let rt: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    let result: Result<Vec<ExecResult>, ExecError> =
+        rt.block_on(execute_exec("Cargo.toml", &ExecOptions::from(&args)));
+    assert!(matches!(result, Err(ExecError::MissingCommand)));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_execute_exec_resumes_from_package() {
+    let root: PathBuf = PathBuf::from("./tmp/test_exec_from");
+    let _ = std::fs::remove_dir_all(&root);
+    create_dir_all(root.join("app")).unwrap();
+    create_dir_all(root.join("core")).unwrap();
+    write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"app\", \"core\"]\n",
+    )
+    .unwrap();
+    write(
+        root.join("app/Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\ncore = { path = \"../core\" }\n",
+    )
+    .unwrap();
+    write(
+        root.join("core/Cargo.toml"),
+        "[package]\nname = \"core\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    let raw_args: Vec<String> = [
+        "hyperlane-cli",
+        "exec",
+        "--from",
+        "app",
+        "--report-format",
+        "bogus",
+        "--",
+        "true",
+    ]
+    .iter()
+    .map(|arg: &&str| arg.to_string())
+    .collect();
+    let manifest_path: String = root.join("Cargo.toml").display().to_string();
+    let rt: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    let results: Vec<ExecResult> = rt
+        .block_on(execute_exec(
+            &manifest_path,
+            &ExecOptions::from(&parse_args_from(&raw_args)),
+        ))
+        .unwrap();
+    let names: Vec<&str> = results
+        .iter()
+        .map(|r: &ExecResult| r.package_name.as_str())
+        .collect();
+    assert_eq!(names, vec!["app"]);
+}
@@ -0,0 +1,89 @@
+/// Table inside the tool metadata that holds the fmt settings
+pub(crate) const FMT_METADATA_KEY: &str = "fmt";
+
+/// Table inside the fmt settings that holds the clippy stage settings
+pub(crate) const CLIPPY_METADATA_KEY: &str = "clippy";
+
+/// Key of the fmt settings that lists excluded paths
+pub(crate) const FMT_EXCLUDE_KEY: &str = "exclude";
+
+/// Table inside the fmt settings that holds the derive ordering
+pub(crate) const DERIVE_METADATA_KEY: &str = "derive";
+
+/// Path of the fmt content-hash cache inside the target directory
+pub(crate) const FMT_CACHE_FILE: &str = "hyperlane-cli/fmt-cache";
+
+/// Edition passed to rustfmt when a package does not declare one
+pub(crate) const DEFAULT_RUST_EDITION: &str = "2015";
+
+/// Exit code of `fmt --check` when only derive ordering or manifest formatting fails
+pub(crate) const FMT_CHECK_EXIT_CODE: i32 = 2;
+
+/// Number of unchanged lines shown around each diff hunk
+pub(crate) const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Std traits in the order used by the `std` preset
+pub(crate) const STD_DERIVE_ORDER: &[&str] = &[
+    "Debug",
+    "Clone",
+    "Copy",
+    "PartialEq",
+    "Eq",
+    "PartialOrd",
+    "Ord",
+    "Hash",
+    "Default",
+];
+
+/// Serde traits in the order used by the `std` preset
+pub(crate) const SERDE_DERIVE_ORDER: &[&str] = &["Serialize", "Deserialize"];
+
+/// Canonical order of the keys of `[package]` and `[workspace.package]`
+pub(crate) const PACKAGE_KEY_ORDER: &[&str] = &[
+    "name",
+    "version",
+    "authors",
+    "edition",
+    "rust-version",
+    "description",
+    "documentation",
+    "readme",
+    "homepage",
+    "repository",
+    "license",
+    "license-file",
+    "keywords",
+    "categories",
+    "workspace",
+    "build",
+    "links",
+    "exclude",
+    "include",
+    "publish",
+    "metadata",
+    "default-run",
+    "autolib",
+    "autobins",
+    "autoexamples",
+    "autotests",
+    "autobenches",
+    "resolver",
+];
+
+/// Names of the manifest tables that list dependencies
+pub(crate) const DEPENDENCY_TABLE_NAMES: &[&str] = &[
+    "dependencies",
+    "dev-dependencies",
+    "dev_dependencies",
+    "build-dependencies",
+    "build_dependencies",
+];
+
+/// Dependency keys that can be combined with `workspace = true`, plus the dropped version
+pub(crate) const INHERITABLE_DEPENDENCY_KEYS: &[&str] = &[
+    "version",
+    "features",
+    "optional",
+    "default-features",
+    "default_features",
+];
@@ -0,0 +1,68 @@
+use crate::*;
+
+/// Error types for the fmt command
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum FmtError {
+    /// Derive ordering configuration is malformed
+    #[error("Invalid derive ordering configuration: {0}")]
+    InvalidDeriveConfig(String),
+    /// Check mode found manifests, derive lists or files that are not formatted
+    #[error("{}", .0.join(", "))]
+    CheckFailed(Vec<String>),
+    /// fmt configuration is malformed
+    #[error("Invalid fmt configuration: {0}")]
+    InvalidConfig(String),
+    /// A path given to fmt is neither a file, a directory nor a package
+    #[error("No such file, directory or package: {0}")]
+    UnknownPath(String),
+    /// rustfmt reported unformatted files or failed
+    #[error("rustfmt failed")]
+    RustfmtFailed,
+    /// Packages could not be discovered
+    #[error(transparent)]
+    PublishError(#[from] PublishError),
+    /// Changed files could not be listed
+    #[error(transparent)]
+    GitError(#[from] GitError),
+    /// Workspace manifest could not be read
+    #[error(transparent)]
+    WorkspaceError(#[from] WorkspaceError),
+    /// Hook configuration could not be loaded or a pre hook failed
+    #[error(transparent)]
+    HookError(#[from] HookError),
+    /// IO error
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+/// A line of a line-based diff
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum DiffLine<'a> {
+    /// Line present in both versions
+    Context(&'a str),
+    /// Line only present in the original
+    Removed(&'a str),
+    /// Line only present in the new version
+    Added(&'a str),
+}
+
+/// A line of a manifest table
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum ManifestItem {
+    /// Key/value entry with the comments above it
+    Entry(ManifestEntry),
+    /// Comment not followed by an entry
+    Comment(String),
+    /// Blank line separating groups of entries
+    Blank,
+}
+
+/// Named derive ordering presets
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum DerivePreset {
+    /// Case-insensitive alphabetical order
+    #[default]
+    Alphabetical,
+    /// Std traits in a fixed order, then serde traits, then the rest alphabetically
+    Std,
+}
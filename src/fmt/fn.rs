@@ -1,166 +1,2267 @@
 use crate::*;
 
-/// Sort derive traits in a single line
+/// Check whether a byte can be part of an identifier
 ///
 /// # Arguments
 ///
-/// - `&str`: The line containing derive attribute
+/// - `u8`: Byte to check
 ///
 /// # Returns
 ///
-/// - `Option<String>`: Sorted line if derive found, None otherwise
-fn sort_derive_in_line(line: &str) -> Option<String> {
-    let captures: Captures<'_> = DERIVE_REGEX.captures(line)?;
-    let derive_content: &str = captures.get(1)?.as_str();
-    let mut traits: Vec<String> = derive_content
-        .split(',')
-        .map(|s: &str| s.trim().to_string())
-        .filter(|s: &String| !s.is_empty())
+/// - `bool`: True for ASCII alphanumerics, `_` and non-ASCII bytes
+pub(crate) fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte >= 0x80
+}
+
+/// Skip whitespace starting at an offset
+///
+/// # Arguments
+///
+/// - `&[u8]`: Source bytes
+/// - `usize`: Start offset
+///
+/// # Returns
+///
+/// - `usize`: Offset of the next non-whitespace byte
+pub(crate) fn skip_whitespace(bytes: &[u8], mut index: usize) -> usize {
+    while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+        index += 1;
+    }
+    index
+}
+
+/// Skip a comment, string literal or character literal
+///
+/// Handles line comments, nested block comments, string and byte string
+/// literals with escapes, raw strings with any number of `#` and character
+/// literals. Lifetimes are left alone.
+///
+/// # Arguments
+///
+/// - `&[u8]`: Source bytes
+/// - `usize`: Offset to check
+///
+/// # Returns
+///
+/// - `Option<usize>`: Offset after the skipped token, None if no such token starts here
+pub(crate) fn skip_non_code(bytes: &[u8], index: usize) -> Option<usize> {
+    let at = |offset: usize| -> u8 { bytes.get(offset).copied().unwrap_or_default() };
+    match at(index) {
+        b'/' if at(index + 1) == b'/' => {
+            let mut end: usize = index;
+            while end < bytes.len() && bytes[end] != b'\n' {
+                end += 1;
+            }
+            Some(end)
+        }
+        b'/' if at(index + 1) == b'*' => {
+            let mut depth: usize = 0;
+            let mut end: usize = index;
+            while end < bytes.len() {
+                if at(end) == b'/' && at(end + 1) == b'*' {
+                    depth += 1;
+                    end += 2;
+                } else if at(end) == b'*' && at(end + 1) == b'/' {
+                    depth -= 1;
+                    end += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    end += 1;
+                }
+            }
+            Some(end.min(bytes.len()))
+        }
+        b'"' => {
+            let mut end: usize = index + 1;
+            while end < bytes.len() && bytes[end] != b'"' {
+                end += if bytes[end] == b'\\' { 2 } else { 1 };
+            }
+            Some((end + 1).min(bytes.len()))
+        }
+        b'\'' => {
+            if at(index + 1) == b'\\' {
+                let mut end: usize = index + 3;
+                while end < bytes.len() && bytes[end] != b'\'' {
+                    end += 1;
+                }
+                return Some((end + 1).min(bytes.len()));
+            }
+            let char_len: usize = match at(index + 1) {
+                0x00..=0x7f => 1,
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                _ => 4,
+            };
+            if at(index + 1 + char_len) == b'\'' {
+                Some(index + 2 + char_len)
+            } else {
+                None
+            }
+        }
+        b'r' | b'b' | b'c' if index == 0 || !is_ident_byte(bytes[index - 1]) => {
+            let mut start: usize = index;
+            if at(start) != b'r' {
+                start += 1;
+            }
+            if at(start) != b'r' {
+                return None;
+            }
+            let mut quote: usize = start + 1;
+            while at(quote) == b'#' {
+                quote += 1;
+            }
+            if at(quote) != b'"' {
+                return None;
+            }
+            let hashes: usize = quote - start - 1;
+            let mut end: usize = quote + 1;
+            while end < bytes.len() {
+                if bytes[end] == b'"'
+                    && bytes.len() >= end + 1 + hashes
+                    && bytes[end + 1..end + 1 + hashes]
+                        .iter()
+                        .all(|b: &u8| *b == b'#')
+                {
+                    return Some(end + 1 + hashes);
+                }
+                end += 1;
+            }
+            Some(bytes.len())
+        }
+        _ => None,
+    }
+}
+
+/// Find the offset of the bracket that closes a group
+///
+/// # Arguments
+///
+/// - `&[u8]`: Source bytes
+/// - `usize`: Offset of the first byte after the opening bracket
+///
+/// # Returns
+///
+/// - `Option<usize>`: Offset of the closing bracket, None if the group is unterminated
+pub(crate) fn find_group_end(bytes: &[u8], mut index: usize) -> Option<usize> {
+    let mut depth: usize = 0;
+    while index < bytes.len() {
+        if let Some(next) = skip_non_code(bytes, index) {
+            index = next;
+            continue;
+        }
+        match bytes[index] {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' if depth == 0 => return Some(index),
+            b')' | b']' | b'}' => depth -= 1,
+            _ => {}
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Find the derive lists of every attribute in Rust source
+///
+/// Attributes are located with a lightweight tokenizer, so derives inside
+/// comments and string literals are ignored while multi-line derives and
+/// derives nested in `cfg_attr` are found.
+///
+/// # Arguments
+///
+/// - `&str`: Rust source code
+///
+/// # Returns
+///
+/// - `Vec<DeriveSpan>`: Trait list ranges in source order
+pub(crate) fn find_derive_spans(source: &str) -> Vec<DeriveSpan> {
+    let bytes: &[u8] = source.as_bytes();
+    let mut spans: Vec<DeriveSpan> = Vec::new();
+    let mut index: usize = 0;
+    while index < bytes.len() {
+        if let Some(next) = skip_non_code(bytes, index) {
+            index = next;
+            continue;
+        }
+        if bytes[index] != b'#' {
+            index += 1;
+            continue;
+        }
+        let mut open: usize = skip_whitespace(bytes, index + 1);
+        if bytes.get(open) == Some(&b'!') {
+            open = skip_whitespace(bytes, open + 1);
+        }
+        if bytes.get(open) != Some(&b'[') {
+            index += 1;
+            continue;
+        }
+        let Some(attribute_end) = find_group_end(bytes, open + 1) else {
+            break;
+        };
+        let mut cursor: usize = open + 1;
+        while cursor < attribute_end {
+            if let Some(next) = skip_non_code(bytes, cursor) {
+                cursor = next;
+                continue;
+            }
+            let ident_start: bool =
+                is_ident_byte(bytes[cursor]) && (cursor == 0 || !is_ident_byte(bytes[cursor - 1]));
+            if !ident_start {
+                cursor += 1;
+                continue;
+            }
+            let mut ident_end: usize = cursor;
+            while ident_end < attribute_end && is_ident_byte(bytes[ident_end]) {
+                ident_end += 1;
+            }
+            let paren: usize = skip_whitespace(bytes, ident_end);
+            if &bytes[cursor..ident_end] == b"derive"
+                && bytes.get(paren) == Some(&b'(')
+                && let Some(end) = find_group_end(bytes, paren + 1)
+            {
+                spans.push(DeriveSpan {
+                    start: paren + 1,
+                    end,
+                });
+                cursor = end + 1;
+            } else {
+                cursor = ident_end;
+            }
+        }
+        index = attribute_end + 1;
+    }
+    spans
+}
+
+/// Split a derive list into traits and their comments
+///
+/// Repeated traits are merged into their first occurrence.
+///
+/// # Arguments
+///
+/// - `&str`: Text between the parentheses of `derive(...)`
+///
+/// # Returns
+///
+/// - `Option<Vec<DeriveEntry>>`: Parsed traits, None if the list contains anything but paths and comments
+pub(crate) fn parse_derive_list(list: &str) -> Option<Vec<DeriveEntry>> {
+    let bytes: &[u8] = list.as_bytes();
+    let mut entries: Vec<DeriveEntry> = Vec::new();
+    let mut current: DeriveEntry = DeriveEntry::default();
+    let mut pending_comments: Vec<String> = Vec::new();
+    let mut after_comma: bool = false;
+    let mut index: usize = 0;
+    while index < bytes.len() {
+        if let Some(next) = skip_non_code(bytes, index) {
+            let text: &str = &list[index..next];
+            if !text.starts_with("//") && !text.starts_with("/*") {
+                return None;
+            }
+            let comment: String = text.trim_end().to_string();
+            if !current.path.is_empty() {
+                current.trailing_comments.push(comment);
+            } else if after_comma && let Some(previous) = entries.last_mut() {
+                previous.trailing_comments.push(comment);
+            } else {
+                pending_comments.push(comment);
+            }
+            index = next;
+            continue;
+        }
+        match bytes[index] {
+            b'\n' => after_comma = false,
+            b',' => {
+                if !current.path.is_empty() {
+                    entries.push(std::mem::take(&mut current));
+                }
+                after_comma = true;
+            }
+            byte if byte.is_ascii_whitespace() => {}
+            byte if is_ident_byte(byte) || byte == b':' => {
+                if current.path.is_empty() {
+                    current.leading_comments = std::mem::take(&mut pending_comments);
+                }
+                let char_len: usize = list[index..].chars().next().map_or(1, char::len_utf8);
+                current.path.push_str(&list[index..index + char_len]);
+                index += char_len;
+                continue;
+            }
+            _ => return None,
+        }
+        index += 1;
+    }
+    if !current.path.is_empty() {
+        entries.push(current);
+    }
+    if let Some(last) = entries.last_mut() {
+        last.trailing_comments.append(&mut pending_comments);
+    } else if !pending_comments.is_empty() {
+        return None;
+    }
+    let mut unique: Vec<DeriveEntry> = Vec::new();
+    for entry in entries {
+        match unique
+            .iter_mut()
+            .find(|existing: &&mut DeriveEntry| existing.path == entry.path)
+        {
+            Some(existing) => {
+                existing.leading_comments.extend(entry.leading_comments);
+                existing.trailing_comments.extend(entry.trailing_comments);
+            }
+            None => unique.push(entry),
+        }
+    }
+    Some(unique)
+}
+
+/// Read a list of strings from a derive ordering setting
+///
+/// # Arguments
+///
+/// - `&toml::Value`: Setting value
+/// - `&str`: Setting name used in errors
+///
+/// # Returns
+///
+/// - `Result<Vec<String>, FmtError>`: Strings or error if the value is not a string list
+fn derive_string_list(value: &toml::Value, name: &str) -> Result<Vec<String>, FmtError> {
+    value
+        .as_array()
+        .and_then(|items: &Vec<toml::Value>| {
+            items
+                .iter()
+                .map(|item: &toml::Value| item.as_str().map(|s: &str| s.to_string()))
+                .collect::<Option<Vec<String>>>()
+        })
+        .ok_or_else(|| FmtError::InvalidDeriveConfig(format!("`{name}` must be a list of strings")))
+}
+
+/// Build a derive ordering from a settings table
+///
+/// `preset` selects the base groups, `groups` replaces them and `order`
+/// adds a group placed before all others.
+///
+/// # Arguments
+///
+/// - `&toml::Value`: `[*.metadata.hyperlane-cli.fmt.derive]` table
+///
+/// # Returns
+///
+/// - `Result<DeriveOrder, FmtError>`: Derive ordering or error for malformed settings
+pub(crate) fn parse_derive_order(table: &toml::Value) -> Result<DeriveOrder, FmtError> {
+    let preset: DerivePreset = match table.get("preset") {
+        Some(value) => value
+            .as_str()
+            .ok_or_else(|| FmtError::InvalidDeriveConfig("`preset` must be a string".to_string()))?
+            .parse()?,
+        None => DerivePreset::default(),
+    };
+    let mut order: DeriveOrder = DeriveOrder::from(preset);
+    if let Some(groups) = table.get("groups") {
+        order.groups = groups
+            .as_array()
+            .ok_or_else(|| {
+                FmtError::InvalidDeriveConfig("`groups` must be a list of lists".to_string())
+            })?
+            .iter()
+            .map(|group: &toml::Value| derive_string_list(group, "groups"))
+            .collect::<Result<Vec<Vec<String>>, FmtError>>()?;
+    }
+    if let Some(first) = table.get("order") {
+        order.groups.insert(0, derive_string_list(first, "order")?);
+    }
+    Ok(order)
+}
+
+/// Load the derive ordering configured in a manifest
+///
+/// The ordering lives in `[workspace.metadata.hyperlane-cli.fmt.derive]` or
+/// `[package.metadata.hyperlane-cli.fmt.derive]`, the package table wins. A
+/// missing manifest or table yields alphabetical ordering.
+///
+/// # Arguments
+///
+/// - `&Path`: Path to Cargo.toml
+///
+/// # Returns
+///
+/// - `Result<DeriveOrder, FmtError>`: Configured derive ordering
+pub(crate) fn load_derive_order(manifest_path: &Path) -> Result<DeriveOrder, FmtError> {
+    if !manifest_path.is_file() {
+        return Ok(DeriveOrder::default());
+    }
+    let doc: toml::Value = read_manifest(manifest_path)?;
+    let mut order: DeriveOrder = DeriveOrder::default();
+    for section in ["workspace", "package"] {
+        if let Some(table) = doc
+            .get(section)
+            .and_then(|s: &toml::Value| s.get("metadata"))
+            .and_then(|m: &toml::Value| m.get(CONFIG_METADATA_KEY))
+            .and_then(|c: &toml::Value| c.get(FMT_METADATA_KEY))
+            .and_then(|f: &toml::Value| f.get(DERIVE_METADATA_KEY))
+        {
+            order = parse_derive_order(table)?;
+        }
+    }
+    Ok(order)
+}
+
+/// Render sorted derive entries back into a list
+///
+/// Single-line lists are joined with `, `. Multi-line lists get one trait
+/// per line with the indentation of the original items and a trailing comma.
+///
+/// # Arguments
+///
+/// - `&str`: Original list text
+/// - `&[DeriveEntry]`: Sorted entries
+///
+/// # Returns
+///
+/// - `String`: New list text
+fn render_derive_list(original: &str, entries: &[DeriveEntry]) -> String {
+    let with_comments = |entry: &DeriveEntry, separator: &str| -> String {
+        let mut text: String = entry.path.clone();
+        if !entry.trailing_comments.is_empty() {
+            text = format!("{text}{separator} {}", entry.trailing_comments.join(" "));
+        }
+        text
+    };
+    if !original.contains('\n') {
+        return entries
+            .iter()
+            .map(|entry: &DeriveEntry| {
+                let mut text: String = entry.leading_comments.join(" ");
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(&with_comments(entry, ""));
+                text
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+    }
+    let indent: &str = original
+        .split('\n')
+        .skip(1)
+        .find(|line: &&str| !line.trim().is_empty())
+        .map(|line: &str| &line[..line.len() - line.trim_start().len()])
+        .unwrap_or("    ");
+    let closing: &str = original
+        .rsplit('\n')
+        .next()
+        .filter(|line: &&str| line.trim().is_empty())
+        .unwrap_or_else(|| indent.strip_suffix("    ").unwrap_or_default());
+    let mut output: String = String::new();
+    for entry in entries {
+        for comment in &entry.leading_comments {
+            output.push_str(&format!("\n{indent}{comment}"));
+        }
+        output.push_str(&format!("\n{indent}{}", with_comments(entry, ",")));
+        if entry.trailing_comments.is_empty() {
+            output.push(',');
+        }
+    }
+    output.push('\n');
+    output.push_str(closing);
+    output
+}
+
+/// Compute the derive lists that change when sorted
+///
+/// # Arguments
+///
+/// - `&str`: Rust source code
+/// - `&DeriveOrder`: Ordering applied to the traits
+///
+/// # Returns
+///
+/// - `Vec<DeriveEdit>`: Replacements for unsorted lists in source order
+pub(crate) fn derive_edits(source: &str, order: &DeriveOrder) -> Vec<DeriveEdit> {
+    let mut edits: Vec<DeriveEdit> = Vec::new();
+    for span in find_derive_spans(source) {
+        let original: &str = &source[span.start..span.end];
+        let Some(mut entries) = parse_derive_list(original) else {
+            continue;
+        };
+        if entries.is_empty() {
+            continue;
+        }
+        entries.sort_by_cached_key(|entry: &DeriveEntry| order.sort_key(&entry.path));
+        let replacement: String = render_derive_list(original, &entries);
+        if replacement != original {
+            edits.push(DeriveEdit { span, replacement });
+        }
+    }
+    edits
+}
+
+/// Sort and deduplicate the traits of every derive attribute in Rust source
+///
+/// # Arguments
+///
+/// - `&str`: Rust source code
+/// - `&DeriveOrder`: Ordering applied to the traits
+///
+/// # Returns
+///
+/// - `String`: Source with sorted derive lists
+pub(crate) fn sort_derives_in_source(source: &str, order: &DeriveOrder) -> String {
+    let mut output: String = String::with_capacity(source.len());
+    let mut copied: usize = 0;
+    for edit in derive_edits(source, order) {
+        output.push_str(&source[copied..edit.span.start]);
+        output.push_str(&edit.replacement);
+        copied = edit.span.end;
+    }
+    output.push_str(&source[copied..]);
+    output
+}
+
+/// Compute a line diff with Myers' algorithm
+///
+/// # Arguments
+///
+/// - `&[&str]`: Original lines
+/// - `&[&str]`: New lines
+///
+/// # Returns
+///
+/// - `Vec<DiffLine>`: Shortest edit script from the original to the new lines
+pub(crate) fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffLine<'a>> {
+    let old_len: isize = old.len() as isize;
+    let new_len: isize = new.len() as isize;
+    let offset: isize = old_len + new_len + 1;
+    let mut frontier: Vec<isize> = vec![0; (2 * offset + 1) as usize];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+    let follows_down = |frontier: &[isize], k: isize, d: isize| -> bool {
+        k == -d
+            || (k != d && frontier[(k - 1 + offset) as usize] < frontier[(k + 1 + offset) as usize])
+    };
+    'search: for d in 0..offset {
+        trace.push(frontier.clone());
+        for k in (-d..=d).step_by(2) {
+            let mut x: isize = if follows_down(&frontier, k, d) {
+                frontier[(k + 1 + offset) as usize]
+            } else {
+                frontier[(k - 1 + offset) as usize] + 1
+            };
+            let mut y: isize = x - k;
+            while x < old_len && y < new_len && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            frontier[(k + offset) as usize] = x;
+            if x >= old_len && y >= new_len {
+                break 'search;
+            }
+        }
+    }
+    let mut result: Vec<DiffLine<'a>> = Vec::new();
+    let (mut x, mut y): (isize, isize) = (old_len, new_len);
+    for (d, frontier) in trace.iter().enumerate().rev() {
+        let d: isize = d as isize;
+        let k: isize = x - y;
+        let previous_k: isize = if follows_down(frontier, k, d) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let previous_x: isize = frontier[(previous_k + offset) as usize];
+        let previous_y: isize = previous_x - previous_k;
+        while x > previous_x && y > previous_y {
+            result.push(DiffLine::Context(old[(x - 1) as usize]));
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            if x == previous_x {
+                result.push(DiffLine::Added(new[(y - 1) as usize]));
+            } else {
+                result.push(DiffLine::Removed(old[(x - 1) as usize]));
+            }
+            x = previous_x;
+            y = previous_y;
+        }
+    }
+    result.reverse();
+    result
+}
+
+/// Format the difference between two versions of a file as a unified diff
+///
+/// # Arguments
+///
+/// - `&str`: Path shown in the diff header
+/// - `&str`: Original content
+/// - `&str`: New content
+///
+/// # Returns
+///
+/// - `String`: Unified diff, empty if the contents have the same lines
+pub(crate) fn format_unified_diff(path: &str, old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let diff: Vec<DiffLine> = diff_lines(&old_lines, &new_lines);
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (index, line) in diff.iter().enumerate() {
+        if matches!(line, DiffLine::Context(_)) {
+            continue;
+        }
+        let start: usize = index.saturating_sub(DIFF_CONTEXT_LINES);
+        let end: usize = (index + 1 + DIFF_CONTEXT_LINES).min(diff.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+    if hunks.is_empty() {
+        return String::new();
+    }
+    let mut old_line: Vec<usize> = Vec::with_capacity(diff.len() + 1);
+    let mut new_line: Vec<usize> = Vec::with_capacity(diff.len() + 1);
+    let (mut old_count, mut new_count): (usize, usize) = (0, 0);
+    for line in &diff {
+        old_line.push(old_count);
+        new_line.push(new_count);
+        match line {
+            DiffLine::Context(_) => {
+                old_count += 1;
+                new_count += 1;
+            }
+            DiffLine::Removed(_) => old_count += 1,
+            DiffLine::Added(_) => new_count += 1,
+        }
+    }
+    old_line.push(old_count);
+    new_line.push(new_count);
+    let mut output: String = format!("--- a/{path}\n+++ b/{path}\n");
+    for (start, end) in hunks {
+        let old_len: usize = old_line[end] - old_line[start];
+        let new_len: usize = new_line[end] - new_line[start];
+        output.push_str(&format!(
+            "@@ -{},{old_len} +{},{new_len} @@\n",
+            old_line[start] + usize::from(old_len > 0),
+            new_line[start] + usize::from(new_len > 0)
+        ));
+        for line in &diff[start..end] {
+            let (marker, text): (char, &str) = match line {
+                DiffLine::Context(text) => (' ', text),
+                DiffLine::Removed(text) => ('-', text),
+                DiffLine::Added(text) => ('+', text),
+            };
+            output.push(marker);
+            output.push_str(text);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// Compute the derive changes of a file
+///
+/// # Arguments
+///
+/// - `&Path`: Path to the Rust file
+/// - `&DeriveOrder`: Ordering applied to the traits
+///
+/// # Returns
+///
+/// - `Result<Option<DeriveFileChange>, std::io::Error>`: Change if any derive list is unsorted
+fn derive_change_for_file(
+    file_path: &Path,
+    order: &DeriveOrder,
+) -> Result<Option<DeriveFileChange>, std::io::Error> {
+    let content: String = read_to_string(file_path)?;
+    let edits: Vec<DeriveEdit> = derive_edits(&content, order);
+    if edits.is_empty() {
+        return Ok(None);
+    }
+    let lines: Vec<usize> = edits
+        .iter()
+        .map(|edit: &DeriveEdit| content[..edit.span.start].matches('\n').count() + 1)
+        .collect();
+    let formatted: String = sort_derives_in_source(&content, order);
+    Ok(Some(DeriveFileChange {
+        path: file_path.to_path_buf(),
+        lines,
+        original: content,
+        formatted,
+    }))
+}
+
+/// Format derive attributes in a file
+///
+/// # Arguments
+///
+/// - `&Path`: Path to the Rust file
+/// - `&DeriveOrder`: Ordering applied to the traits
+///
+/// # Returns
+///
+/// - `Result<bool, std::io::Error>`: True if file was modified, false otherwise
+async fn format_derive_in_file(
+    file_path: &Path,
+    order: &DeriveOrder,
+) -> Result<bool, std::io::Error> {
+    match derive_change_for_file(file_path, order)? {
+        Some(change) => {
+            write(file_path, change.formatted)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Remove `.` components from a path
+///
+/// # Arguments
+///
+/// - `&Path`: Path to normalize
+///
+/// # Returns
+///
+/// - `PathBuf`: Path without current-directory components
+fn normalize_path(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component: &std::path::Component| {
+            !matches!(component, std::path::Component::CurDir)
+        })
+        .collect()
+}
+
+/// Parse a line of a `.gitignore` file or an exclude pattern
+///
+/// # Arguments
+///
+/// - `&Path`: Directory the pattern is relative to
+/// - `&str`: Pattern line
+///
+/// # Returns
+///
+/// - `Option<IgnoreRule>`: Parsed rule, None for blank lines and comments
+pub(crate) fn parse_ignore_rule(base: &Path, line: &str) -> Option<IgnoreRule> {
+    let line: &str = line.trim_end();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let (negated, line): (bool, &str) = match line.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+    let (dir_only, line): (bool, &str) = match line.strip_suffix('/') {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+    let anchored: bool = line.contains('/');
+    let pattern: &str = line.trim_start_matches('/');
+    if pattern.is_empty() {
+        return None;
+    }
+    Some(IgnoreRule {
+        base: normalize_path(base),
+        pattern: pattern.to_string(),
+        negated,
+        dir_only,
+        anchored,
+    })
+}
+
+/// Read the rules of the `.gitignore` file in a directory
+///
+/// # Arguments
+///
+/// - `&Path`: Directory that may contain a `.gitignore`
+///
+/// # Returns
+///
+/// - `Vec<IgnoreRule>`: Rules of the file, empty if there is none
+fn read_gitignore(dir: &Path) -> Vec<IgnoreRule> {
+    read_to_string(dir.join(".gitignore"))
+        .map(|content: String| {
+            content
+                .lines()
+                .filter_map(|line: &str| parse_ignore_rule(dir, line))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Match path segments against pattern segments where `**` spans any depth
+///
+/// # Arguments
+///
+/// - `&[&str]`: Pattern segments
+/// - `&[&str]`: Path segments
+///
+/// # Returns
+///
+/// - `bool`: True if the whole path matches
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => {
+            (0..=path.len()).any(|skip: usize| segments_match(rest, &path[skip..]))
+        }
+        Some((first, rest)) => {
+            !path.is_empty() && glob_match(first, path[0]) && segments_match(rest, &path[1..])
+        }
+    }
+}
+
+/// Check whether a path is ignored
+///
+/// Rules are applied in order and the last matching rule wins, so negated
+/// rules re-include earlier matches.
+///
+/// # Arguments
+///
+/// - `&[IgnoreRule]`: Rules in precedence order
+/// - `&Path`: File or directory path
+/// - `bool`: Whether the path is a directory
+///
+/// # Returns
+///
+/// - `bool`: True if the path is ignored
+pub(crate) fn is_ignored(rules: &[IgnoreRule], path: &Path, is_dir: bool) -> bool {
+    let path: PathBuf = normalize_path(path);
+    let mut ignored: bool = false;
+    for rule in rules {
+        if rule.dir_only && !is_dir {
+            continue;
+        }
+        let Ok(relative) = path.strip_prefix(&rule.base) else {
+            continue;
+        };
+        let segments: Vec<String> = relative
+            .components()
+            .map(|component: std::path::Component| {
+                component.as_os_str().to_string_lossy().to_string()
+            })
+            .collect();
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let matched: bool = if rule.anchored {
+            let pattern: Vec<&str> = rule.pattern.split('/').collect();
+            segments_match(&pattern, &segments)
+        } else {
+            segments
+                .last()
+                .is_some_and(|name: &&str| glob_match(&rule.pattern, name))
+        };
+        if matched {
+            ignored = !rule.negated;
+        }
+    }
+    ignored
+}
+
+/// Load the fmt exclude patterns configured in a manifest
+///
+/// Patterns live in `exclude` of `[workspace.metadata.hyperlane-cli.fmt]`
+/// or `[package.metadata.hyperlane-cli.fmt]` and use `.gitignore` syntax
+/// relative to the manifest directory.
+///
+/// # Arguments
+///
+/// - `&Path`: Path to Cargo.toml
+///
+/// # Returns
+///
+/// - `Result<Vec<IgnoreRule>, FmtError>`: Exclude rules
+pub(crate) fn load_fmt_excludes(manifest_path: &Path) -> Result<Vec<IgnoreRule>, FmtError> {
+    if !manifest_path.is_file() {
+        return Ok(Vec::new());
+    }
+    let doc: toml::Value = read_manifest(manifest_path)?;
+    let base: PathBuf = manifest_dir(manifest_path);
+    let mut rules: Vec<IgnoreRule> = Vec::new();
+    for section in ["workspace", "package"] {
+        if let Some(excludes) = doc
+            .get(section)
+            .and_then(|s: &toml::Value| s.get("metadata"))
+            .and_then(|m: &toml::Value| m.get(CONFIG_METADATA_KEY))
+            .and_then(|c: &toml::Value| c.get(FMT_METADATA_KEY))
+            .and_then(|f: &toml::Value| f.get(FMT_EXCLUDE_KEY))
+        {
+            let patterns: Vec<String> = excludes
+                .as_array()
+                .and_then(|items: &Vec<toml::Value>| {
+                    items
+                        .iter()
+                        .map(|item: &toml::Value| item.as_str().map(|s: &str| s.to_string()))
+                        .collect::<Option<Vec<String>>>()
+                })
+                .ok_or_else(|| {
+                    FmtError::InvalidConfig(format!(
+                        "`{FMT_EXCLUDE_KEY}` must be a list of strings"
+                    ))
+                })?;
+            rules.extend(
+                patterns
+                    .iter()
+                    .filter_map(|pattern: &String| parse_ignore_rule(&base, pattern)),
+            );
+        }
+    }
+    Ok(rules)
+}
+
+/// List the source roots of every cargo target of a package
+///
+/// Covers `src`, `tests`, `examples`, `benches`, the build script and the
+/// paths of `[lib]`, `[[bin]]`, `[[test]]`, `[[example]]` and `[[bench]]`
+/// targets. A target file outside the standard directories contributes its
+/// directory, or only itself when it lives in the package root.
+///
+/// # Arguments
+///
+/// - `&Path`: Package directory
+///
+/// # Returns
+///
+/// - `Vec<PathBuf>`: Existing directories and files, without roots nested in other roots
+pub(crate) fn package_source_roots(package_dir: &Path) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = ["src", "tests", "examples", "benches"]
+        .iter()
+        .map(|name: &&str| package_dir.join(name))
+        .filter(|dir: &PathBuf| dir.is_dir())
+        .collect();
+    let doc: Option<toml::Value> = read_manifest(&package_dir.join("Cargo.toml")).ok();
+    let mut target_files: Vec<PathBuf> = Vec::new();
+    match doc
+        .as_ref()
+        .and_then(|d: &toml::Value| d.get("package"))
+        .and_then(|p: &toml::Value| p.get("build"))
+    {
+        Some(toml::Value::String(build)) => target_files.push(package_dir.join(build)),
+        Some(toml::Value::Boolean(false)) => {}
+        _ => target_files.push(package_dir.join("build.rs")),
+    }
+    if let Some(doc) = &doc {
+        if let Some(path) = doc
+            .get("lib")
+            .and_then(|lib: &toml::Value| lib.get("path"))
+            .and_then(|p: &toml::Value| p.as_str())
+        {
+            target_files.push(package_dir.join(path));
+        }
+        for section in ["bin", "test", "example", "bench"] {
+            let targets: &[toml::Value] = doc
+                .get(section)
+                .and_then(|t: &toml::Value| t.as_array())
+                .map_or(&[], Vec::as_slice);
+            for target in targets {
+                if let Some(path) = target.get("path").and_then(|p: &toml::Value| p.as_str()) {
+                    target_files.push(package_dir.join(path));
+                }
+            }
+        }
+    }
+    let package_root: PathBuf = normalize_path(package_dir);
+    for file in target_files
+        .into_iter()
+        .filter(|file: &PathBuf| file.is_file())
+    {
+        let root: PathBuf = match file.parent() {
+            Some(parent) if normalize_path(parent) != package_root => parent.to_path_buf(),
+            _ => file,
+        };
+        if !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+    let normalized: Vec<PathBuf> = roots
+        .iter()
+        .map(|root: &PathBuf| normalize_path(root))
+        .collect();
+    roots
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _): &(usize, PathBuf)| {
+            !normalized
+                .iter()
+                .enumerate()
+                .any(|(other, dir): (usize, &PathBuf)| {
+                    other != *index
+                        && normalized[*index] != *dir
+                        && normalized[*index].starts_with(dir)
+                })
+        })
+        .map(|(_, root): (usize, PathBuf)| root)
+        .collect()
+}
+
+/// Find the Rust files of a single package
+///
+/// Walks every target source root while honouring `.gitignore` files from
+/// the workspace root down and the configured excludes.
+///
+/// # Arguments
+///
+/// - `&Path`: Workspace root directory
+/// - `&Path`: Package directory
+/// - `&[IgnoreRule]`: Configured exclude rules
+/// - `&mut Vec<PathBuf>`: Vector to collect file paths
+///
+/// # Returns
+///
+/// - `Result<(), std::io::Error>`: Success or error
+pub(crate) async fn find_package_rust_files(
+    workspace_root: &Path,
+    package_dir: &Path,
+    excludes: &[IgnoreRule],
+    files: &mut Vec<PathBuf>,
+) -> Result<(), std::io::Error> {
+    let mut rules: Vec<IgnoreRule> = excludes.to_vec();
+    let root: PathBuf = normalize_path(workspace_root);
+    let package: PathBuf = normalize_path(package_dir);
+    let mut ancestors: Vec<&Path> = package
+        .ancestors()
+        .take_while(|dir: &&Path| dir.starts_with(&root))
+        .collect();
+    ancestors.reverse();
+    for dir in ancestors {
+        rules.extend(read_gitignore(
+            &workspace_root.join(dir.strip_prefix(&root).unwrap_or(dir)),
+        ));
+    }
+    for source_root in package_source_roots(package_dir) {
+        if source_root.is_dir() {
+            if !is_ignored(&rules, &source_root, true) {
+                find_rust_files_in_dir(&source_root, &mut rules, files).await?;
+            }
+        } else if !is_ignored(&rules, &source_root, false) {
+            files.push(source_root);
+        }
+    }
+    Ok(())
+}
+
+/// Find all Rust files in workspace
+///
+/// # Arguments
+///
+/// - `&Path`: Path to Cargo.toml
+/// - `&[IgnoreRule]`: Configured exclude rules
+///
+/// # Returns
+///
+/// - `Result<Vec<PathBuf>, std::io::Error>`: List of Rust file paths
+async fn find_rust_files(
+    manifest_path: &Path,
+    excludes: &[IgnoreRule],
+) -> Result<Vec<PathBuf>, std::io::Error> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    let workspace_root: PathBuf = manifest_dir(manifest_path);
+    let member_dirs: Vec<PathBuf> =
+        discover_member_dirs(manifest_path).map_err(std::io::Error::other)?;
+    if !member_dirs.contains(&workspace_root) {
+        find_package_rust_files(&workspace_root, &workspace_root, excludes, &mut files).await?;
+    }
+    for member_dir in member_dirs {
+        find_package_rust_files(&workspace_root, &member_dir, excludes, &mut files).await?;
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Recursively find Rust files in directory
+///
+/// The `.gitignore` of every visited directory is added to the rules while
+/// its entries are walked.
+///
+/// # Arguments
+///
+/// - `&Path`: Directory to search
+/// - `&mut Vec<IgnoreRule>`: Rules in effect for the directory
+/// - `&mut Vec<PathBuf>`: Vector to collect file paths
+///
+/// # Returns
+///
+/// - `Result<(), std::io::Error>`: Success or error
+async fn find_rust_files_in_dir(
+    dir: &Path,
+    rules: &mut Vec<IgnoreRule>,
+    files: &mut Vec<PathBuf>,
+) -> Result<(), std::io::Error> {
+    let inherited: usize = rules.len();
+    rules.extend(read_gitignore(dir));
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path: PathBuf = entry.path();
+        if path.is_file()
+            && path
+                .extension()
+                .is_some_and(|ext: &std::ffi::OsStr| ext == "rs")
+            && !is_ignored(rules, &path, false)
+        {
+            files.push(path);
+        } else if path.is_dir() && !is_ignored(rules, &path, true) {
+            Box::pin(find_rust_files_in_dir(&path, rules, files)).await?;
+        }
+    }
+    rules.truncate(inherited);
+    Ok(())
+}
+
+/// Hash file content with 64-bit FNV-1a
+///
+/// # Arguments
+///
+/// - `&[u8]`: Content to hash
+///
+/// # Returns
+///
+/// - `u64`: Content hash, stable across runs and toolchains
+pub(crate) fn content_hash(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325, |hash: u64, byte: &u8| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+/// Get the path of the fmt cache file
+///
+/// # Arguments
+///
+/// - `&Path`: Path to Cargo.toml
+///
+/// # Returns
+///
+/// - `PathBuf`: Cache file inside `CARGO_TARGET_DIR` or the workspace `target` directory
+fn fmt_cache_path(manifest_path: &Path) -> PathBuf {
+    let target_dir: PathBuf = std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| manifest_dir(manifest_path).join("target"));
+    target_dir.join(FMT_CACHE_FILE)
+}
+
+/// Hash the settings that affect formatting
+///
+/// Covers the tool version, the derive ordering, the clippy settings when
+/// clippy runs and the rustfmt configuration next to the manifest, so
+/// changing any of them or enabling clippy invalidates the cache.
+///
+/// # Arguments
+///
+/// - `&Path`: Path to Cargo.toml
+/// - `&DeriveOrder`: Derive ordering in use
+/// - `Option<&ClippySettings>`: Clippy settings, None when clippy does not run
+///
+/// # Returns
+///
+/// - `u64`: Cache key
+fn fmt_cache_key(
+    manifest_path: &Path,
+    order: &DeriveOrder,
+    clippy: Option<&ClippySettings>,
+) -> u64 {
+    let dir: PathBuf = manifest_dir(manifest_path);
+    let mut settings: String = format!("{}\n{order:?}\n{clippy:?}\n", env!("CARGO_PKG_VERSION"));
+    for name in ["rustfmt.toml", ".rustfmt.toml"] {
+        settings.push_str(&read_to_string(dir.join(name)).unwrap_or_default());
+        settings.push('\n');
+    }
+    content_hash(settings.as_bytes())
+}
+
+/// Get the key of a file in the fmt cache
+///
+/// # Arguments
+///
+/// - `&Path`: File path
+///
+/// # Returns
+///
+/// - `PathBuf`: Canonical path, or the path itself if it cannot be resolved
+fn cache_file_key(file: &Path) -> PathBuf {
+    std::fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf())
+}
+
+/// Resolve the files selected by `fmt <paths...>` or `--changed`
+///
+/// Paths may be Rust files, directories or package names; Cargo.toml paths
+/// are handled by `select_manifest_files`. `--changed` keeps the Rust files
+/// changed compared to `HEAD`, `--changed-since` compares against the given
+/// reference instead.
+///
+/// # Arguments
+///
+/// - `&Path`: Path to Cargo.toml
+/// - `&[Package]`: Workspace packages
+/// - `&[IgnoreRule]`: Configured exclude rules
+/// - `&Args`: The parsed arguments
+///
+/// # Returns
+///
+/// - `Result<Option<Vec<PathBuf>>, FmtError>`: Selected files, None to format the whole workspace
+async fn select_fmt_files(
+    manifest_path: &Path,
+    packages: &[Package],
+    excludes: &[IgnoreRule],
+    args: &Args,
+) -> Result<Option<Vec<PathBuf>>, FmtError> {
+    let reference: Option<&str> = match &args.changed_since {
+        Some(reference) => Some(reference.as_str()),
+        None if args.changed => Some("HEAD"),
+        None => None,
+    };
+    if args.paths.is_empty() && reference.is_none() {
+        return Ok(None);
+    }
+    let is_rust_file = |path: &Path| -> bool {
+        path.is_file()
+            && path
+                .extension()
+                .is_some_and(|ext: &std::ffi::OsStr| ext == "rs")
+    };
+    let mut files: Vec<PathBuf> = Vec::new();
+    for path_arg in &args.paths {
+        let path: &Path = Path::new(path_arg);
+        if is_rust_file(path) {
+            files.push(path.to_path_buf());
+        } else if path.is_dir() {
+            find_rust_files_in_dir(path, &mut excludes.to_vec(), &mut files).await?;
+        } else if path.is_file()
+            && path
+                .file_name()
+                .is_some_and(|name: &std::ffi::OsStr| name == "Cargo.toml")
+        {
+            continue;
+        } else if let Some(package) = packages.iter().find(|p: &&Package| &p.name == path_arg) {
+            find_package_rust_files(
+                &manifest_dir(manifest_path),
+                &package.path,
+                excludes,
+                &mut files,
+            )
+            .await?;
+        } else {
+            return Err(FmtError::UnknownPath(path_arg.clone()));
+        }
+    }
+    if let Some(reference) = reference {
+        let changed: Vec<PathBuf> = changed_files(&manifest_dir(manifest_path), reference).await?;
+        let changed: Vec<PathBuf> = changed
+            .into_iter()
+            .filter(|path: &PathBuf| is_rust_file(path) && !is_ignored(excludes, path, false))
+            .collect();
+        if args.paths.is_empty() {
+            files = changed;
+        } else {
+            let changed_keys: Vec<PathBuf> = changed
+                .iter()
+                .map(|p: &PathBuf| cache_file_key(p))
+                .collect();
+            files.retain(|file: &PathBuf| changed_keys.contains(&cache_file_key(file)));
+        }
+    }
+    let mut seen: Vec<PathBuf> = Vec::new();
+    files.retain(|file: &PathBuf| {
+        let key: PathBuf = cache_file_key(file);
+        let is_new: bool = !seen.contains(&key);
+        seen.push(key);
+        is_new
+    });
+    Ok(Some(files))
+}
+
+/// Find the package a file belongs to
+///
+/// # Arguments
+///
+/// - `&'a [Package]`: Workspace packages
+/// - `&Path`: File path
+///
+/// # Returns
+///
+/// - `Option<&'a Package>`: Package with the longest directory prefix of the file
+fn package_for_file<'a>(packages: &'a [Package], file: &Path) -> Option<&'a Package> {
+    let file: PathBuf = cache_file_key(file);
+    packages
+        .iter()
+        .filter_map(|package: &Package| {
+            let dir: PathBuf = cache_file_key(&package.path);
+            file.starts_with(&dir)
+                .then_some((dir.components().count(), package))
+        })
+        .max_by_key(|(depth, _): &(usize, &Package)| *depth)
+        .map(|(_, package): (usize, &Package)| package)
+}
+
+/// Read the edition of a package
+///
+/// Resolves `edition.workspace = true` through the workspace manifest.
+///
+/// # Arguments
+///
+/// - `&Path`: Package directory
+/// - `&Path`: Path to the workspace Cargo.toml
+///
+/// # Returns
+///
+/// - `String`: Declared edition or `DEFAULT_RUST_EDITION`
+fn package_edition(package_dir: &Path, manifest_path: &Path) -> String {
+    let edition = |doc: &toml::Value, section: &[&str]| -> Option<toml::Value> {
+        section
+            .iter()
+            .try_fold(doc, |value: &toml::Value, key: &&str| value.get(*key))
+            .and_then(|value: &toml::Value| value.get("edition"))
+            .cloned()
+    };
+    let Ok(doc) = read_manifest(&package_dir.join("Cargo.toml")) else {
+        return DEFAULT_RUST_EDITION.to_string();
+    };
+    match edition(&doc, &["package"]) {
+        Some(toml::Value::String(edition)) => edition,
+        Some(toml::Value::Table(_)) => read_manifest(manifest_path)
+            .ok()
+            .and_then(|root: toml::Value| edition(&root, &["workspace", "package"]))
+            .and_then(|value: toml::Value| value.as_str().map(|s: &str| s.to_string()))
+            .unwrap_or_else(|| DEFAULT_RUST_EDITION.to_string()),
+        _ => DEFAULT_RUST_EDITION.to_string(),
+    }
+}
+
+/// Run rustfmt on a set of files
+///
+/// Files are grouped by the edition of their package.
+///
+/// # Arguments
+///
+/// - `&Path`: Path to Cargo.toml
+/// - `&[Package]`: Workspace packages
+/// - `&[PathBuf]`: Files to format
+/// - `bool`: Only check, do not write
+///
+/// # Returns
+///
+/// - `Result<bool, FmtError>`: Whether every file is formatted, or an error if rustfmt failed
+async fn run_rustfmt(
+    manifest_path: &Path,
+    packages: &[Package],
+    files: &[PathBuf],
+    check: bool,
+) -> Result<bool, FmtError> {
+    let mut by_edition: Vec<(String, Vec<&PathBuf>)> = Vec::new();
+    for file in files {
+        let edition: String = match package_for_file(packages, file) {
+            Some(package) => package_edition(&package.path, manifest_path),
+            None => package_edition(&manifest_dir(manifest_path), manifest_path),
+        };
+        match by_edition
+            .iter_mut()
+            .find(|(existing, _): &&mut (String, Vec<&PathBuf>)| *existing == edition)
+        {
+            Some((_, group)) => group.push(file),
+            None => by_edition.push((edition, vec![file])),
+        }
+    }
+    let mut success: bool = true;
+    let mut formatted: bool = true;
+    for (edition, group) in by_edition {
+        let mut cmd: Command = Command::new("rustfmt");
+        cmd.arg("--edition").arg(&edition);
+        if check {
+            cmd.arg("--check");
+        }
+        cmd.args(group);
+        cmd.stdout(Stdio::piped()).stderr(Stdio::inherit());
+        let output: std::process::Output = cmd.output().await?;
+        print!("{}", String::from_utf8_lossy(&output.stdout));
+        if output.status.success() {
+            continue;
+        }
+        if check && !output.stdout.is_empty() {
+            formatted = false;
+        } else {
+            success = false;
+        }
+    }
+    if !success {
+        return Err(FmtError::RustfmtFailed);
+    }
+    Ok(formatted)
+}
+
+/// List the packages that own a set of files
+///
+/// # Arguments
+///
+/// - `&[Package]`: Workspace packages
+/// - `&[PathBuf]`: Files
+///
+/// # Returns
+///
+/// - `Vec<String>`: Sorted package names without duplicates
+fn touched_packages(packages: &[Package], files: &[PathBuf]) -> Vec<String> {
+    let mut touched: Vec<String> = files
+        .iter()
+        .filter_map(|file: &PathBuf| package_for_file(packages, file))
+        .map(|package: &Package| package.name.clone())
+        .collect();
+    touched.sort();
+    touched.dedup();
+    touched
+}
+
+/// Sort derive attributes in a set of files
+///
+/// # Arguments
+///
+/// - `&[PathBuf]`: Files to process
+/// - `&DeriveOrder`: Ordering applied to the traits
+/// - `bool`: Write the sorted files, false only reports the changes
+///
+/// # Returns
+///
+/// - `Result<Vec<DeriveFileChange>, FmtError>`: Changed files sorted by path
+async fn format_derive_attributes(
+    files: &[PathBuf],
+    order: &DeriveOrder,
+    write_changes: bool,
+) -> Result<Vec<DeriveFileChange>, FmtError> {
+    let order: Arc<DeriveOrder> = Arc::new(order.clone());
+    let mut join_set: JoinSet<Result<Option<DeriveFileChange>, std::io::Error>> = JoinSet::new();
+    for file in files {
+        let order: Arc<DeriveOrder> = Arc::clone(&order);
+        let file: PathBuf = file.clone();
+        join_set.spawn(async move {
+            let change: Option<DeriveFileChange> = derive_change_for_file(&file, &order)?;
+            if write_changes && let Some(change) = &change {
+                write(&change.path, &change.formatted)?;
+            }
+            Ok(change)
+        });
+    }
+    let mut changes: Vec<DeriveFileChange> = Vec::new();
+    for result in join_set.join_all().await {
+        changes.extend(result?);
+    }
+    changes.sort_by(|a: &DeriveFileChange, b: &DeriveFileChange| a.path.cmp(&b.path));
+    Ok(changes)
+}
+
+/// Sort derive attributes in every Rust file below a directory
+///
+/// # Arguments
+///
+/// - `&Path`: Directory to search
+/// - `&DeriveOrder`: Ordering applied to the traits
+///
+/// # Returns
+///
+/// - `Result<usize, std::io::Error>`: Number of modified files
+pub(crate) async fn sort_derives_in_dir(
+    dir: &Path,
+    order: &DeriveOrder,
+) -> Result<usize, std::io::Error> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    find_rust_files_in_dir(dir, &mut Vec::new(), &mut files).await?;
+    let mut modified: usize = 0;
+    for file in files {
+        if format_derive_in_file(&file, order).await? {
+            modified += 1;
+        }
+    }
+    Ok(modified)
+}
+
+/// Scan a TOML line for brackets, strings and comments
+///
+/// # Arguments
+///
+/// - `&str`: Line to scan
+/// - `&mut TomlScanState`: State carried over from the previous lines, updated in place
+///
+/// # Returns
+///
+/// - `Option<usize>`: Byte offset of a comment starting on the line
+fn scan_toml_line(line: &str, state: &mut TomlScanState) -> Option<usize> {
+    let bytes: &[u8] = line.as_bytes();
+    let mut i: usize = 0;
+    while i < bytes.len() {
+        if let Some(quote) = state.multiline {
+            if quote == b'"' && bytes[i] == b'\\' {
+                i += 2;
+            } else if bytes[i..].starts_with(&[quote; 3]) {
+                state.multiline = None;
+                i += 3;
+            } else {
+                i += 1;
+            }
+            continue;
+        }
+        match bytes[i] {
+            b'#' => return Some(i),
+            quote @ (b'"' | b'\'') => {
+                if bytes[i..].starts_with(&[quote; 3]) {
+                    state.multiline = Some(quote);
+                    i += 3;
+                    continue;
+                }
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    if quote == b'"' && bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            b'[' | b'{' => state.depth += 1,
+            b']' | b'}' => state.depth = state.depth.saturating_sub(1),
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Find a byte outside of TOML strings
+///
+/// # Arguments
+///
+/// - `&str`: Text to search
+/// - `u8`: Byte to find
+///
+/// # Returns
+///
+/// - `Option<usize>`: Offset of the first unquoted occurrence
+fn find_toml_unquoted(text: &str, target: u8) -> Option<usize> {
+    let bytes: &[u8] = text.as_bytes();
+    let mut i: usize = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            byte if byte == target => return Some(i),
+            quote @ (b'"' | b'\'') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    if quote == b'"' && bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Split a dotted TOML key into its trimmed segments
+///
+/// # Arguments
+///
+/// - `&str`: Key or table name such as `target.'cfg(unix)'.dependencies`
+///
+/// # Returns
+///
+/// - `Vec<String>`: Segments with their quotes kept
+fn toml_key_segments(key: &str) -> Vec<String> {
+    let mut segments: Vec<String> = Vec::new();
+    let mut rest: &str = key;
+    while let Some(dot) = find_toml_unquoted(rest, b'.') {
+        segments.push(rest[..dot].trim().to_string());
+        rest = &rest[dot + 1..];
+    }
+    segments.push(rest.trim().to_string());
+    segments
+}
+
+/// Remove the quotes around a TOML key segment or string
+///
+/// # Arguments
+///
+/// - `&str`: Segment
+///
+/// # Returns
+///
+/// - `String`: Segment without surrounding quotes
+fn unquote_toml(segment: &str) -> String {
+    segment
+        .trim_matches(|c: char| c == '"' || c == '\'')
+        .to_string()
+}
+
+/// Split a single-line TOML value into tokens
+///
+/// # Arguments
+///
+/// - `&str`: Value text without a trailing comment
+///
+/// # Returns
+///
+/// - `Option<Vec<String>>`: Strings, punctuation and bare words, None for multi-line strings
+fn toml_value_tokens(value: &str) -> Option<Vec<String>> {
+    let bytes: &[u8] = value.as_bytes();
+    let mut tokens: Vec<String> = Vec::new();
+    let mut i: usize = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b' ' | b'\t' => i += 1,
+            b'{' | b'}' | b'[' | b']' | b',' | b'=' => {
+                tokens.push(value[i..i + 1].to_string());
+                i += 1;
+            }
+            quote @ (b'"' | b'\'') => {
+                if bytes[i..].starts_with(&[quote; 3]) {
+                    return None;
+                }
+                let start: usize = i;
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    if quote == b'"' && bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                if i >= bytes.len() {
+                    return None;
+                }
+                i += 1;
+                tokens.push(value[start..i].to_string());
+            }
+            b'#' => return None,
+            _ => {
+                let start: usize = i;
+                while i < bytes.len() && !b" \t{}[],=\"'#".contains(&bytes[i]) {
+                    i += 1;
+                }
+                tokens.push(value[start..i].to_string());
+            }
+        }
+    }
+    Some(tokens)
+}
+
+/// Render TOML value tokens with normalised spacing
+///
+/// Inline tables are padded as `{ key = value }`, array items are separated
+/// by `, ` and trailing commas of arrays are dropped.
+///
+/// # Arguments
+///
+/// - `&[String]`: Value tokens
+///
+/// # Returns
+///
+/// - `String`: Rendered value
+fn render_toml_tokens(tokens: &[String]) -> String {
+    let kept: Vec<&str> = tokens
+        .iter()
+        .enumerate()
+        .filter(|(index, token): &(usize, &String)| {
+            *token != ","
+                || tokens
+                    .get(index + 1)
+                    .is_none_or(|next: &String| next != "]")
+        })
+        .map(|(_, token): (usize, &String)| token.as_str())
         .collect();
-    traits.sort_by_key(|a| a.to_lowercase());
-    let sorted_traits: String = traits.join(", ");
-    let result: String = line.replace(derive_content, &sorted_traits);
-    Some(result)
+    let mut output: String = String::new();
+    for (index, token) in kept.iter().enumerate() {
+        let previous: Option<&str> = index.checked_sub(1).map(|p: usize| kept[p]);
+        let separator: &str = match (previous, *token) {
+            (None, _) | (Some("{"), "}") => "",
+            (Some("{"), _) | (_, "}") | (Some(","), _) | (Some("="), _) | (_, "=") => " ",
+            (Some("["), _) | (_, "]") | (_, ",") => "",
+            _ => " ",
+        };
+        output.push_str(separator);
+        output.push_str(token);
+    }
+    output
 }
 
-/// Format derive attributes in a file
+/// Split a manifest into tables, entries, comments and blank lines
 ///
 /// # Arguments
 ///
-/// - `&Path`: Path to the Rust file
+/// - `&str`: Manifest content
 ///
 /// # Returns
 ///
-/// - `Result<bool, std::io::Error>`: True if file was modified, false otherwise
-async fn format_derive_in_file(file_path: &Path) -> Result<bool, std::io::Error> {
-    let content: String = read_to_string(file_path)?;
-    let lines: std::str::Lines<'_> = content.lines();
-    let mut modified: bool = false;
-    let mut new_content: String = String::new();
-    for line in lines {
+/// - `Vec<ManifestSection>`: Sections in document order, the first one holds the lines before any table
+fn parse_manifest_sections(content: &str) -> Vec<ManifestSection> {
+    let mut sections: Vec<ManifestSection> = vec![ManifestSection::default()];
+    let mut comments: Vec<String> = Vec::new();
+    let mut lines: std::str::Lines<'_> = content.lines();
+    while let Some(line) = lines.next() {
         let trimmed: &str = line.trim();
-        let new_line: String = if trimmed.starts_with("#[derive(") {
-            if let Some(sorted) = sort_derive_in_line(line) {
-                if sorted != line {
-                    modified = true;
-                }
-                sorted
-            } else {
-                line.to_string()
-            }
+        if trimmed.starts_with('#') {
+            comments.push(line.to_string());
+            continue;
+        }
+        let section: &mut ManifestSection = sections.last_mut().expect("sections is never empty");
+        if trimmed.is_empty() {
+            section
+                .items
+                .extend(comments.drain(..).map(ManifestItem::Comment));
+            section.items.push(ManifestItem::Blank);
+        } else if trimmed.starts_with('[') {
+            section
+                .items
+                .extend(comments.drain(..).map(ManifestItem::Comment));
+            let header_end: usize = line
+                [..scan_toml_line(line, &mut TomlScanState::default()).unwrap_or(line.len())]
+                .trim_end()
+                .len();
+            let header: &str = line[..header_end].trim_start();
+            let brackets: usize = if header.starts_with("[[") { 2 } else { 1 };
+            let name: &str = header
+                .get(brackets..header.len().saturating_sub(brackets))
+                .unwrap_or_default();
+            let segments: Vec<String> = toml_key_segments(name);
+            let indent: &str = &line[..line.len() - line.trim_start().len()];
+            sections.push(ManifestSection {
+                header: Some(format!(
+                    "{indent}{}{}{}{}",
+                    &header[..brackets],
+                    segments.join("."),
+                    &header[header.len() - brackets..],
+                    &line[header_end..]
+                )),
+                name: segments.iter().map(|s: &String| unquote_toml(s)).collect(),
+                items: Vec::new(),
+            });
         } else {
-            line.to_string()
-        };
-        new_content.push_str(&new_line);
-        new_content.push('\n');
+            let mut entry_lines: Vec<String> = vec![line.to_string()];
+            let mut state: TomlScanState = TomlScanState::default();
+            scan_toml_line(line, &mut state);
+            while state.depth > 0 || state.multiline.is_some() {
+                let Some(next) = lines.next() else {
+                    break;
+                };
+                scan_toml_line(next, &mut state);
+                entry_lines.push(next.to_string());
+            }
+            let key: String = match find_toml_unquoted(line, b'=') {
+                Some(eq) => unquote_toml(&toml_key_segments(&line[..eq])[0]),
+                None => trimmed.to_string(),
+            };
+            section.items.push(ManifestItem::Entry(ManifestEntry {
+                key,
+                comments: std::mem::take(&mut comments),
+                lines: entry_lines,
+            }));
+        }
     }
-    if modified {
-        write(file_path, new_content)?;
+    if let Some(section) = sections.last_mut() {
+        section
+            .items
+            .extend(comments.drain(..).map(ManifestItem::Comment));
     }
-    Ok(modified)
+    sections
 }
 
-/// Find all Rust files in workspace
+/// Split the first line of an entry into its parts
 ///
 /// # Arguments
 ///
-/// - `&Path`: Path to Cargo.toml
+/// - `&str`: Entry line
 ///
 /// # Returns
 ///
-/// - `Result<Vec<PathBuf>, std::io::Error>`: List of Rust file paths
-async fn find_rust_files(manifest_path: &Path) -> Result<Vec<PathBuf>, std::io::Error> {
-    let mut files: Vec<PathBuf> = Vec::new();
-    let workspace_root: &Path = manifest_path.parent().unwrap_or(Path::new("."));
-    let src_dir: PathBuf = workspace_root.join("src");
-    if src_dir.exists() {
-        find_rust_files_in_dir(&src_dir, &mut files).await?;
-    }
-    let content: String = read_to_string(manifest_path)?;
-    if let Ok(doc) = toml::from_str::<toml::Value>(&content) {
-        if let Some(workspace) = doc.get("workspace") {
-            if let Some(members) = workspace
-                .get("members")
-                .and_then(|m: &toml::Value| m.as_array())
-            {
-                for member in members {
-                    if let Some(pattern) = member.as_str() {
-                        let member_src: PathBuf = workspace_root.join(pattern).join("src");
-                        if member_src.exists() {
-                            find_rust_files_in_dir(&member_src, &mut files).await?;
-                        }
+/// - `Option<(&str, Vec<String>, &str, &str)>`: Indentation, key segments, value and trailing comment with the space before it
+fn split_manifest_entry_line(line: &str) -> Option<(&str, Vec<String>, &str, &str)> {
+    let eq: usize = find_toml_unquoted(line, b'=')?;
+    let indent: &str = &line[..line.len() - line.trim_start().len()];
+    let rest: &str = &line[eq + 1..];
+    let comment: Option<usize> = scan_toml_line(rest, &mut TomlScanState::default());
+    let value: &str = rest[..comment.unwrap_or(rest.len())].trim_end();
+    let trailing: &str = if comment.is_some() {
+        &rest[value.len()..]
+    } else {
+        ""
+    };
+    Some((
+        indent,
+        toml_key_segments(&line[..eq]),
+        value.trim(),
+        trailing,
+    ))
+}
+
+/// Normalise the spacing of a manifest entry
+///
+/// Single-line values are re-rendered with `render_toml_tokens`, multi-line
+/// values only get a normalised `key = ` prefix.
+///
+/// # Arguments
+///
+/// - `&mut ManifestEntry`: Entry updated in place
+fn normalize_manifest_entry(entry: &mut ManifestEntry) {
+    let first: &str = &entry.lines[0];
+    let Some((indent, key, value, trailing)) = split_manifest_entry_line(first) else {
+        return;
+    };
+    let line: String = if entry.lines.len() == 1 {
+        let value: String = toml_value_tokens(value)
+            .map(|tokens: Vec<String>| render_toml_tokens(&tokens))
+            .unwrap_or_else(|| value.to_string());
+        format!("{indent}{} = {value}{trailing}", key.join("."))
+    } else {
+        let eq: usize = find_toml_unquoted(first, b'=').unwrap_or_default();
+        format!(
+            "{indent}{} = {}",
+            key.join("."),
+            first[eq + 1..].trim_start()
+        )
+    };
+    entry.lines[0] = line;
+}
+
+/// Compare two version requirements ignoring a leading caret
+///
+/// # Arguments
+///
+/// - `&str`: First requirement
+/// - `&str`: Second requirement
+///
+/// # Returns
+///
+/// - `bool`: True if both requirements are the same
+fn same_version_requirement(a: &str, b: &str) -> bool {
+    a.trim().trim_start_matches('^') == b.trim().trim_start_matches('^')
+}
+
+/// Replace a dependency that repeats `[workspace.dependencies]` with `workspace = true`
+///
+/// Only single-line entries that inherit to the same dependency are
+/// rewritten: the version requirement matches, `default-features` and the
+/// workspace features agree and every other key is `features` or
+/// `optional`. Those keys are kept, the version is dropped.
+///
+/// # Arguments
+///
+/// - `&mut ManifestEntry`: Dependency entry updated in place
+/// - `&HashMap<String, WorkspaceDependency>`: Workspace dependencies
+///
+/// # Returns
+///
+/// - `Option<String>`: Warning if the version differs from the workspace one
+fn align_workspace_dependency(
+    entry: &mut ManifestEntry,
+    workspace_dependencies: &HashMap<String, WorkspaceDependency>,
+) -> Option<String> {
+    if entry.lines.len() != 1 {
+        return None;
+    }
+    let (indent, key, value, trailing) = split_manifest_entry_line(&entry.lines[0])?;
+    if key.len() != 1 {
+        return None;
+    }
+    let workspace: &WorkspaceDependency = workspace_dependencies.get(&entry.key)?;
+    let tokens: Vec<String> = toml_value_tokens(value)?;
+    let mut fields: Vec<Vec<String>> = Vec::new();
+    let version: String = match tokens.as_slice() {
+        [version] if version.starts_with(['"', '\'']) => unquote_toml(version),
+        [open, inner @ .., close] if open == "{" && close == "}" => {
+            let mut depth: usize = 0;
+            let mut field: Vec<String> = Vec::new();
+            for token in inner {
+                match token.as_str() {
+                    "[" | "{" => depth += 1,
+                    "]" | "}" => depth = depth.saturating_sub(1),
+                    "," if depth == 0 => {
+                        fields.push(std::mem::take(&mut field));
+                        continue;
                     }
+                    _ => {}
                 }
+                field.push(token.clone());
             }
+            if !field.is_empty() {
+                fields.push(field);
+            }
+            if fields
+                .iter()
+                .any(|field: &Vec<String>| field.len() < 3 || field[1] != "=")
+            {
+                return None;
+            }
+            let field_key = |field: &Vec<String>| -> String { unquote_toml(&field[0]) };
+            if fields.iter().any(|field: &Vec<String>| {
+                !INHERITABLE_DEPENDENCY_KEYS.contains(&field_key(field).as_str())
+            }) {
+                return None;
+            }
+            let version: Option<String> = fields
+                .iter()
+                .find(|field: &&Vec<String>| field_key(field) == "version")
+                .map(|field: &Vec<String>| unquote_toml(&field[2]));
+            fields.retain(|field: &Vec<String>| field_key(field) != "version");
+            version?
         }
+        _ => return None,
+    };
+    match &workspace.version {
+        Some(workspace_version) if same_version_requirement(&version, workspace_version) => {}
+        Some(workspace_version) => {
+            return Some(format!(
+                "dependency `{}` requires \"{version}\" but [workspace.dependencies] has \"{workspace_version}\"",
+                entry.key
+            ));
+        }
+        None => return None,
     }
-    Ok(files)
+    let mut default_features: Option<bool> = None;
+    let mut features: Vec<String> = Vec::new();
+    for field in &fields {
+        match unquote_toml(&field[0]).as_str() {
+            "default-features" | "default_features" => match field[2..].join("").as_str() {
+                "true" => default_features = Some(true),
+                "false" => default_features = Some(false),
+                _ => return None,
+            },
+            "features" => features.extend(
+                field[2..]
+                    .iter()
+                    .filter(|token: &&String| token.starts_with(['"', '\'']))
+                    .map(|token: &String| unquote_toml(token)),
+            ),
+            _ => {}
+        }
+    }
+    if default_features.unwrap_or(true)
+        != (workspace.default_features || default_features == Some(true))
+        || workspace
+            .features
+            .iter()
+            .any(|feature: &String| !features.contains(feature))
+    {
+        return None;
+    }
+    let mut aligned: Vec<String> = ["{", "workspace", "=", "true"]
+        .iter()
+        .map(|token: &&str| token.to_string())
+        .collect();
+    for field in fields {
+        aligned.push(",".to_string());
+        aligned.extend(field);
+    }
+    aligned.push("}".to_string());
+    entry.lines[0] = format!(
+        "{indent}{} = {}{trailing}",
+        key.join("."),
+        render_toml_tokens(&aligned)
+    );
+    None
 }
 
-/// Recursively find Rust files in directory
+/// Check whether a table lists dependencies
 ///
 /// # Arguments
 ///
-/// - `&Path`: Directory to search
-/// - `&mut Vec<PathBuf>`: Vector to collect file paths
+/// - `&[String]`: Table name segments
 ///
 /// # Returns
 ///
-/// - `Result<(), std::io::Error>`: Success or error
-async fn find_rust_files_in_dir(
-    dir: &Path,
-    files: &mut Vec<PathBuf>,
-) -> Result<(), std::io::Error> {
-    let mut entries = tokio::fs::read_dir(dir).await?;
-    while let Some(entry) = entries.next_entry().await? {
-        let path: PathBuf = entry.path();
-        if path.is_file()
-            && path
-                .extension()
-                .is_some_and(|ext: &std::ffi::OsStr| ext == "rs")
-        {
-            files.push(path);
-        } else if path.is_dir() {
-            Box::pin(find_rust_files_in_dir(&path, files)).await?;
+/// - `bool`: True for `[dependencies]`, `[workspace.dependencies]` and their dev, build and target variants
+fn is_dependency_table(name: &[String]) -> bool {
+    match name {
+        [table] => DEPENDENCY_TABLE_NAMES.contains(&table.as_str()),
+        [workspace, table] => workspace == "workspace" && table == "dependencies",
+        [target, _, table] => {
+            target == "target" && DEPENDENCY_TABLE_NAMES.contains(&table.as_str())
+        }
+        _ => false,
+    }
+}
+
+/// Reorder the entries of a table within each run of non-blank lines
+///
+/// Comments above an entry move with it, blank lines keep separating groups.
+///
+/// # Arguments
+///
+/// - `&mut [ManifestItem]`: Table items updated in place
+/// - `fn(&ManifestEntry) -> (usize, String)`: Sort key, ties keep their order
+fn sort_manifest_entries(
+    items: &mut [ManifestItem],
+    sort_key: fn(&ManifestEntry) -> (usize, String),
+) {
+    for run in items.split_mut(|item: &ManifestItem| *item == ManifestItem::Blank) {
+        let mut entries: Vec<ManifestEntry> = run
+            .iter()
+            .filter_map(|item: &ManifestItem| match item {
+                ManifestItem::Entry(entry) => Some(entry.clone()),
+                _ => None,
+            })
+            .collect();
+        entries.sort_by_cached_key(sort_key);
+        let mut sorted: std::vec::IntoIter<ManifestEntry> = entries.into_iter();
+        for item in run.iter_mut() {
+            if let ManifestItem::Entry(entry) = item
+                && let Some(next) = sorted.next()
+            {
+                *entry = next;
+            }
         }
     }
-    Ok(())
 }
 
-/// Format derive attributes in all workspace files
+/// Format a Cargo.toml
+///
+/// Dependency tables are sorted alphabetically, `[package]` keys follow
+/// `PACKAGE_KEY_ORDER`, single-line values get normalised spacing and
+/// dependencies repeating `[workspace.dependencies]` use `workspace = true`.
+/// Comments and blank lines are kept; entries are only reordered between
+/// blank lines.
 ///
 /// # Arguments
 ///
-/// - `&str`: Path to Cargo.toml
+/// - `&str`: Manifest content
+/// - `&HashMap<String, WorkspaceDependency>`: Workspace dependencies
 ///
 /// # Returns
 ///
-/// - `Result<(), std::io::Error>`: Success or error
-async fn format_derive_attributes(manifest_path: &str) -> Result<(), std::io::Error> {
-    let path: &Path = Path::new(manifest_path);
-    let files: Vec<PathBuf> = find_rust_files(path).await?;
-    let modified_count: Arc<Mutex<usize>> = Arc::new(Mutex::new(0));
-    let mut handles: Vec<tokio::task::JoinHandle<Result<(), std::io::Error>>> = Vec::new();
-    for file in files {
-        let counter: Arc<Mutex<usize>> = Arc::clone(&modified_count);
-        let handle: tokio::task::JoinHandle<Result<(), std::io::Error>> =
-            tokio::spawn(async move {
-                if format_derive_in_file(&file).await? {
-                    let mut count: tokio::sync::MutexGuard<'_, usize> = counter.lock().await;
-                    *count += 1;
+/// - `(String, Vec<String>)`: Formatted content and warnings about dependencies that could not be aligned
+pub(crate) fn format_manifest(
+    content: &str,
+    workspace_dependencies: &HashMap<String, WorkspaceDependency>,
+) -> (String, Vec<String>) {
+    let mut sections: Vec<ManifestSection> = parse_manifest_sections(content);
+    let mut warnings: Vec<String> = Vec::new();
+    for section in &mut sections {
+        let is_dependencies: bool = is_dependency_table(&section.name);
+        let is_workspace_dependencies: bool = section.name == ["workspace", "dependencies"];
+        for item in &mut section.items {
+            if let ManifestItem::Entry(entry) = item {
+                normalize_manifest_entry(entry);
+                if is_dependencies
+                    && !is_workspace_dependencies
+                    && let Some(warning) = align_workspace_dependency(entry, workspace_dependencies)
+                {
+                    warnings.push(warning);
                 }
-                Ok(())
+            }
+        }
+        if is_dependencies {
+            sort_manifest_entries(&mut section.items, |entry: &ManifestEntry| {
+                (0, entry.key.to_lowercase())
+            });
+        } else if section.name == ["package"] || section.name == ["workspace", "package"] {
+            sort_manifest_entries(&mut section.items, |entry: &ManifestEntry| {
+                let rank: usize = PACKAGE_KEY_ORDER
+                    .iter()
+                    .position(|key: &&str| *key == entry.key)
+                    .unwrap_or(PACKAGE_KEY_ORDER.len());
+                (rank, String::new())
             });
-        handles.push(handle);
+        }
+    }
+    let mut lines: Vec<String> = Vec::new();
+    for section in sections {
+        lines.extend(section.header);
+        for item in section.items {
+            match item {
+                ManifestItem::Entry(entry) => {
+                    lines.extend(entry.comments);
+                    lines.extend(entry.lines);
+                }
+                ManifestItem::Comment(comment) => lines.push(comment),
+                ManifestItem::Blank => lines.push(String::new()),
+            }
+        }
+    }
+    let mut formatted: String = lines.join("\n");
+    if content.ends_with('\n') {
+        formatted.push('\n');
     }
-    for handle in handles {
-        handle.await??;
+    (formatted, warnings)
+}
+
+/// Load the entries of `[workspace.dependencies]`
+///
+/// # Arguments
+///
+/// - `&Path`: Path to the workspace Cargo.toml
+///
+/// # Returns
+///
+/// - `Result<HashMap<String, WorkspaceDependency>, FmtError>`: Entry per dependency
+pub(crate) fn load_workspace_dependencies(
+    manifest_path: &Path,
+) -> Result<HashMap<String, WorkspaceDependency>, FmtError> {
+    let mut dependencies: HashMap<String, WorkspaceDependency> = HashMap::new();
+    if !manifest_path.is_file() {
+        return Ok(dependencies);
     }
-    let count: usize = *modified_count.lock().await;
-    if count > 0 {
-        println!("Sorted derive attributes in {count} files");
+    let doc: toml::Value = read_manifest(manifest_path)?;
+    let Some(table) = doc
+        .get("workspace")
+        .and_then(|w: &toml::Value| w.get("dependencies"))
+        .and_then(|d: &toml::Value| d.as_table())
+    else {
+        return Ok(dependencies);
+    };
+    for (name, value) in table {
+        let version: Option<String> = match value {
+            toml::Value::String(version) => Some(version.clone()),
+            _ => value
+                .get("version")
+                .and_then(|v: &toml::Value| v.as_str())
+                .map(|v: &str| v.to_string()),
+        };
+        let default_features: bool = value
+            .get("default-features")
+            .or_else(|| value.get("default_features"))
+            .and_then(|v: &toml::Value| v.as_bool())
+            .unwrap_or(true);
+        let features: Vec<String> = value
+            .get("features")
+            .and_then(|v: &toml::Value| v.as_array())
+            .map(|features: &Vec<toml::Value>| {
+                features
+                    .iter()
+                    .filter_map(|f: &toml::Value| f.as_str().map(|f: &str| f.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        dependencies.insert(
+            name.clone(),
+            WorkspaceDependency {
+                version,
+                default_features,
+                features,
+            },
+        );
     }
-    Ok(())
+    Ok(dependencies)
+}
+
+/// Resolve the manifests formatted by fmt
+///
+/// Without a selection the workspace manifest and every member manifest are
+/// formatted. Paths select manifests given directly, below a directory or of
+/// a package, `--changed` keeps the manifests changed since the reference.
+///
+/// # Arguments
+///
+/// - `&Path`: Path to Cargo.toml
+/// - `&[Package]`: Workspace packages
+/// - `&[IgnoreRule]`: Configured exclude rules
+/// - `&Args`: The parsed arguments
+///
+/// # Returns
+///
+/// - `Result<Vec<PathBuf>, FmtError>`: Manifests to format
+async fn select_manifest_files(
+    manifest_path: &Path,
+    packages: &[Package],
+    excludes: &[IgnoreRule],
+    args: &Args,
+) -> Result<Vec<PathBuf>, FmtError> {
+    let mut manifests: Vec<PathBuf> = vec![manifest_path.to_path_buf()];
+    manifests.extend(
+        packages
+            .iter()
+            .map(|package: &Package| package.path.join("Cargo.toml")),
+    );
+    let mut seen: Vec<PathBuf> = Vec::new();
+    manifests.retain(|manifest: &PathBuf| {
+        let key: PathBuf = cache_file_key(manifest);
+        let keep: bool =
+            manifest.is_file() && !is_ignored(excludes, manifest, false) && !seen.contains(&key);
+        seen.push(key);
+        keep
+    });
+    let reference: Option<&str> = match &args.changed_since {
+        Some(reference) => Some(reference.as_str()),
+        None if args.changed => Some("HEAD"),
+        None => None,
+    };
+    if args.paths.is_empty() && reference.is_none() {
+        return Ok(manifests);
+    }
+    let mut selected: Vec<PathBuf> = if args.paths.is_empty() {
+        manifests.clone()
+    } else {
+        Vec::new()
+    };
+    for path_arg in &args.paths {
+        let path: &Path = Path::new(path_arg);
+        if path.is_dir() {
+            let dir: PathBuf = cache_file_key(path);
+            selected.extend(
+                manifests
+                    .iter()
+                    .filter(|manifest: &&PathBuf| cache_file_key(manifest).starts_with(&dir))
+                    .cloned(),
+            );
+        } else if path.is_file()
+            && path
+                .file_name()
+                .is_some_and(|name: &std::ffi::OsStr| name == "Cargo.toml")
+        {
+            selected.push(path.to_path_buf());
+        } else if let Some(package) = packages.iter().find(|p: &&Package| &p.name == path_arg) {
+            selected.push(package.path.join("Cargo.toml"));
+        }
+    }
+    if let Some(reference) = reference {
+        let changed_keys: Vec<PathBuf> = changed_files(&manifest_dir(manifest_path), reference)
+            .await?
+            .iter()
+            .map(|path: &PathBuf| cache_file_key(path))
+            .collect();
+        selected.retain(|manifest: &PathBuf| changed_keys.contains(&cache_file_key(manifest)));
+    }
+    let mut seen: Vec<PathBuf> = Vec::new();
+    selected.retain(|manifest: &PathBuf| {
+        let key: PathBuf = cache_file_key(manifest);
+        let is_new: bool = !seen.contains(&key);
+        seen.push(key);
+        is_new
+    });
+    Ok(selected)
+}
+
+/// Format a set of manifests
+///
+/// Warnings about dependencies that differ from `[workspace.dependencies]`
+/// are printed to stderr.
+///
+/// # Arguments
+///
+/// - `&[PathBuf]`: Manifests to format
+/// - `&HashMap<String, WorkspaceDependency>`: Workspace dependencies
+/// - `bool`: Write the formatted manifests back
+///
+/// # Returns
+///
+/// - `Result<Vec<ManifestChange>, FmtError>`: Manifests whose formatting changed
+fn format_manifest_files(
+    files: &[PathBuf],
+    workspace_dependencies: &HashMap<String, WorkspaceDependency>,
+    write_changes: bool,
+) -> Result<Vec<ManifestChange>, FmtError> {
+    let mut changes: Vec<ManifestChange> = Vec::new();
+    for file in files {
+        let original: String = read_to_string(file)?;
+        let (formatted, warnings): (String, Vec<String>) =
+            format_manifest(&original, workspace_dependencies);
+        for warning in warnings {
+            eprintln!("{}: {warning}", file.display());
+        }
+        if formatted == original {
+            continue;
+        }
+        if write_changes {
+            write(file, &formatted)?;
+        }
+        changes.push(ManifestChange {
+            path: file.clone(),
+            original,
+            formatted,
+        });
+    }
+    Ok(changes)
 }
 
 /// Check if cargo-clippy is installed
@@ -196,69 +2297,394 @@ async fn install_cargo_clippy() -> Result<(), std::io::Error> {
     Ok(())
 }
 
-/// Execute clippy fix command
+/// Make sure cargo-clippy is available
+///
+/// # Arguments
+///
+/// - `&ClippySettings`: Clippy settings deciding whether to install it
+///
+/// # Returns
+///
+/// - `Result<(), std::io::Error>`: Error if clippy is missing and may not be installed
+pub(crate) async fn ensure_cargo_clippy(settings: &ClippySettings) -> Result<(), std::io::Error> {
+    if is_cargo_clippy_installed().await {
+        return Ok(());
+    }
+    if settings.install {
+        return install_cargo_clippy().await;
+    }
+    Err(std::io::Error::other(
+        "cargo-clippy is not installed, run `rustup component add clippy` or set `install = true`",
+    ))
+}
+
+/// Read an optional list of strings from the clippy settings
+///
+/// # Arguments
+///
+/// - `&toml::Value`: `[*.metadata.hyperlane-cli.fmt.clippy]` table
+/// - `&str`: Setting name
+///
+/// # Returns
+///
+/// - `Result<Option<Vec<String>>, FmtError>`: Strings if set, error if the value is not a string list
+fn clippy_string_list(table: &toml::Value, name: &str) -> Result<Option<Vec<String>>, FmtError> {
+    let Some(value) = table.get(name) else {
+        return Ok(None);
+    };
+    value
+        .as_array()
+        .and_then(|items: &Vec<toml::Value>| {
+            items
+                .iter()
+                .map(|item: &toml::Value| item.as_str().map(|s: &str| s.to_string()))
+                .collect::<Option<Vec<String>>>()
+        })
+        .map(Some)
+        .ok_or_else(|| {
+            FmtError::InvalidConfig(format!("clippy `{name}` must be a list of strings"))
+        })
+}
+
+/// Read an optional boolean from the clippy settings
+///
+/// # Arguments
+///
+/// - `&toml::Value`: `[*.metadata.hyperlane-cli.fmt.clippy]` table
+/// - `&str`: Setting name
+///
+/// # Returns
+///
+/// - `Result<Option<bool>, FmtError>`: Value if set, error if it is not a boolean
+fn clippy_bool(table: &toml::Value, name: &str) -> Result<Option<bool>, FmtError> {
+    table
+        .get(name)
+        .map(|value: &toml::Value| {
+            value.as_bool().ok_or_else(|| {
+                FmtError::InvalidConfig(format!("clippy `{name}` must be a boolean"))
+            })
+        })
+        .transpose()
+}
+
+/// Load the clippy settings configured in a manifest
+///
+/// Settings live in `[workspace.metadata.hyperlane-cli.fmt.clippy]` and
+/// `[package.metadata.hyperlane-cli.fmt.clippy]`, package values win.
+///
+/// # Arguments
+///
+/// - `&Path`: Path to Cargo.toml
+///
+/// # Returns
+///
+/// - `Result<ClippySettings, FmtError>`: Clippy settings
+pub(crate) fn load_clippy_settings(manifest_path: &Path) -> Result<ClippySettings, FmtError> {
+    let mut settings: ClippySettings = ClippySettings::default();
+    if !manifest_path.is_file() {
+        return Ok(settings);
+    }
+    let doc: toml::Value = read_manifest(manifest_path)?;
+    for section in ["workspace", "package"] {
+        let Some(table) = doc
+            .get(section)
+            .and_then(|s: &toml::Value| s.get("metadata"))
+            .and_then(|m: &toml::Value| m.get(CONFIG_METADATA_KEY))
+            .and_then(|c: &toml::Value| c.get(FMT_METADATA_KEY))
+            .and_then(|f: &toml::Value| f.get(CLIPPY_METADATA_KEY))
+        else {
+            continue;
+        };
+        if let Some(enabled) = clippy_bool(table, "enabled")? {
+            settings.enabled = enabled;
+        }
+        if let Some(install) = clippy_bool(table, "install")? {
+            settings.install = install;
+        }
+        if let Some(all_targets) = clippy_bool(table, "all-targets")? {
+            settings.all_targets = all_targets;
+        }
+        if let Some(all_features) = clippy_bool(table, "all-features")? {
+            settings.all_features = all_features;
+        }
+        if let Some(features) = clippy_string_list(table, "features")? {
+            settings.features = features;
+        }
+        if let Some(cargo_args) = clippy_string_list(table, "args")? {
+            settings.cargo_args = cargo_args;
+        }
+        if let Some(deny) = clippy_string_list(table, "deny")? {
+            settings.deny = deny;
+        }
+        if let Some(warn) = clippy_string_list(table, "warn")? {
+            settings.warn = warn;
+        }
+        if let Some(allow) = clippy_string_list(table, "allow")? {
+            settings.allow = allow;
+        }
+    }
+    Ok(settings)
+}
+
+/// Build the arguments of a `cargo clippy` invocation
+///
+/// # Arguments
+///
+/// - `&ClippySettings`: Clippy settings
+/// - `Option<&[String]>`: Packages to check, None for the whole workspace
+/// - `Option<&str>`: Manifest path passed to cargo
+/// - `bool`: Apply fixes with `--fix --allow-dirty`
+///
+/// # Returns
+///
+/// - `Vec<String>`: Arguments following `cargo`
+pub(crate) fn clippy_command_args(
+    settings: &ClippySettings,
+    packages: Option<&[String]>,
+    manifest_path: Option<&str>,
+    fix: bool,
+) -> Vec<String> {
+    let mut command: Vec<String> = vec!["clippy".to_string()];
+    if fix {
+        command.push("--fix".to_string());
+        command.push("--allow-dirty".to_string());
+    }
+    match packages {
+        Some(packages) => {
+            for package in packages {
+                command.push("-p".to_string());
+                command.push(package.clone());
+            }
+        }
+        None => command.push("--workspace".to_string()),
+    }
+    if settings.all_targets {
+        command.push("--all-targets".to_string());
+    }
+    if settings.all_features {
+        command.push("--all-features".to_string());
+    } else if !settings.features.is_empty() {
+        command.push("--features".to_string());
+        command.push(settings.features.join(","));
+    }
+    if let Some(manifest_path) = manifest_path {
+        command.push("--manifest-path".to_string());
+        command.push(manifest_path.to_string());
+    }
+    command.extend(settings.cargo_args.iter().cloned());
+    let lints: Vec<String> = [
+        ("-D", &settings.deny),
+        ("-W", &settings.warn),
+        ("-A", &settings.allow),
+    ]
+    .iter()
+    .flat_map(|(flag, lints): &(&str, &Vec<String>)| {
+        lints
+            .iter()
+            .flat_map(move |lint: &String| [flag.to_string(), lint.clone()])
+    })
+    .collect();
+    if !lints.is_empty() {
+        command.push("--".to_string());
+        command.extend(lints);
+    }
+    command
+}
+
+/// Execute clippy command
 ///
 /// # Arguments
 ///
 /// - `&Args`: The parsed arguments
+/// - `&ClippySettings`: Clippy settings
+/// - `Option<&[String]>`: Packages to check, None for the whole workspace
+/// - `bool`: Apply fixes
 ///
 /// # Returns
 ///
 /// - `Result<(), std::io::Error>`: Success or error
-async fn execute_clippy_fix(args: &Args) -> Result<(), std::io::Error> {
-    if !is_cargo_clippy_installed().await {
-        install_cargo_clippy().await?;
-    }
+async fn execute_clippy(
+    args: &Args,
+    settings: &ClippySettings,
+    packages: Option<&[String]>,
+    fix: bool,
+) -> Result<(), std::io::Error> {
+    ensure_cargo_clippy(settings).await?;
     let mut cmd: Command = Command::new("cargo");
-    cmd.arg("clippy")
-        .arg("--fix")
-        .arg("--workspace")
-        .arg("--all-targets")
-        .arg("--allow-dirty");
-    if let Some(ref manifest_path) = args.manifest_path {
-        cmd.arg("--manifest-path").arg(manifest_path);
-    }
+    cmd.args(clippy_command_args(
+        settings,
+        packages,
+        args.manifest_path.as_deref(),
+        fix,
+    ));
     cmd.stdout(Stdio::inherit()).stderr(Stdio::inherit());
     let status: ExitStatus = cmd.status().await?;
     if !status.success() {
-        return Err(std::io::Error::other("cargo clippy --fix failed"));
+        return Err(std::io::Error::other("cargo clippy failed"));
     }
     Ok(())
 }
 
+/// Turn the differences found in check mode into a result
+///
+/// # Arguments
+///
+/// - `Vec<String>`: Descriptions of the differences found
+///
+/// # Returns
+///
+/// - `Result<(), FmtError>`: Success if nothing was found, otherwise `FmtError::CheckFailed`
+fn check_result(unformatted: Vec<String>) -> Result<(), FmtError> {
+    if unformatted.is_empty() {
+        return Ok(());
+    }
+    Err(FmtError::CheckFailed(unformatted))
+}
+
 /// Execute fmt command
 ///
+/// Files whose content hash matches the cache in the target directory are
+/// skipped. The derive pass, rustfmt and clippy only run for the remaining
+/// files and the packages they belong to. Manifests are formatted first and
+/// are not cached. Clippy only runs when enabled in
+/// the settings or with `--clippy`, `--clippy-only` skips the other stages.
+/// In check mode unformatted manifests, unsorted derive lists and files
+/// rustfmt would change are reported with a diff instead of being
+/// rewritten. Every check runs before the command fails with
+/// `FmtError::CheckFailed` listing all of them.
+///
 /// # Arguments
 ///
 /// - `&Args`: The parsed arguments
 ///
 /// # Returns
 ///
-/// - `Result<(), std::io::Error>`: Success or error
-pub(crate) async fn execute_fmt(args: &Args) -> Result<(), std::io::Error> {
+/// - `Result<(), FmtError>`: Success or error
+pub(crate) async fn execute_fmt(args: &Args) -> Result<(), FmtError> {
     let manifest_path: String = args
         .manifest_path
         .clone()
         .unwrap_or_else(|| "Cargo.toml".to_string());
-    if !args.check {
-        format_derive_attributes(&manifest_path).await?;
+    let manifest: &Path = Path::new(&manifest_path);
+    let order: DeriveOrder = load_derive_order(manifest)?;
+    let packages: Vec<Package> = discover_packages(manifest)?;
+    let clippy: ClippySettings = load_clippy_settings(manifest)?;
+    let run_clippy: bool = args.clippy.unwrap_or(clippy.enabled);
+    let excludes: Vec<IgnoreRule> = load_fmt_excludes(manifest)?;
+    let selection: Option<Vec<PathBuf>> =
+        select_fmt_files(manifest, &packages, &excludes, args).await?;
+    if args.clippy_only {
+        let touched: Option<Vec<String>> =
+            selection.map(|files: Vec<PathBuf>| touched_packages(&packages, &files));
+        if touched
+            .as_ref()
+            .is_some_and(|touched: &Vec<String>| touched.is_empty())
+        {
+            return Ok(());
+        }
+        execute_clippy(args, &clippy, touched.as_deref(), !args.check).await?;
+        return Ok(());
     }
-    let mut cmd: Command = Command::new("cargo");
-    cmd.arg("fmt");
-    if args.check {
-        cmd.arg("--check");
+    let manifests: Vec<PathBuf> =
+        select_manifest_files(manifest, &packages, &excludes, args).await?;
+    let manifest_changes: Vec<ManifestChange> = format_manifest_files(
+        &manifests,
+        &load_workspace_dependencies(manifest)?,
+        !args.check,
+    )?;
+    for change in &manifest_changes {
+        let path: String = change
+            .path
+            .strip_prefix(".")
+            .unwrap_or(&change.path)
+            .display()
+            .to_string();
+        if args.check {
+            eprintln!("{path}: manifest is not formatted");
+        }
+        if args.check || args.diff {
+            print!(
+                "{}",
+                format_unified_diff(&path, &change.original, &change.formatted)
+            );
+        }
     }
-    if let Some(ref manifest_path) = args.manifest_path {
-        cmd.arg("--manifest-path").arg(manifest_path);
+    if !args.check && !manifest_changes.is_empty() {
+        println!("Formatted {} manifests", manifest_changes.len());
     }
-    cmd.stdout(Stdio::inherit()).stderr(Stdio::inherit());
-    let status: ExitStatus = cmd.status().await?;
-    if !status.success() {
-        return Err(std::io::Error::other("cargo fmt failed"));
+    let mut unformatted: Vec<String> = Vec::new();
+    if args.check && !manifest_changes.is_empty() {
+        unformatted.push(format!(
+            "{} manifests are not formatted",
+            manifest_changes.len()
+        ));
+    }
+    let candidates: Vec<PathBuf> = match selection {
+        Some(files) => files,
+        None => find_rust_files(manifest, &excludes).await?,
+    };
+    let cache_path: PathBuf = fmt_cache_path(manifest);
+    let cached_clippy: Option<&ClippySettings> = (run_clippy && !args.check).then_some(&clippy);
+    let mut cache: FmtCache =
+        FmtCache::load(&cache_path, fmt_cache_key(manifest, &order, cached_clippy));
+    let mut files: Vec<PathBuf> = Vec::new();
+    for file in candidates {
+        let hash: u64 = content_hash(&std::fs::read(&file)?);
+        if !cache.is_fresh(&cache_file_key(&file), hash) {
+            files.push(file);
+        }
+    }
+    if files.is_empty() {
+        println!("No files changed since the last fmt run");
+        return check_result(unformatted);
+    }
+    let changes: Vec<DeriveFileChange> =
+        format_derive_attributes(&files, &order, !args.check).await?;
+    for change in &changes {
+        let path: String = change
+            .path
+            .strip_prefix(".")
+            .unwrap_or(&change.path)
+            .display()
+            .to_string();
+        if args.check {
+            for line in &change.lines {
+                eprintln!("{path}:{line}: derive attributes are not sorted");
+            }
+        }
+        if args.check || args.diff {
+            print!(
+                "{}",
+                format_unified_diff(&path, &change.original, &change.formatted)
+            );
+        }
+    }
+    if !args.check && !changes.is_empty() {
+        println!("Sorted derive attributes in {} files", changes.len());
+    }
+    if args.check && !changes.is_empty() {
+        unformatted.push(format!(
+            "derive attributes are not sorted in {} files",
+            changes.len()
+        ));
+    }
+    if !run_rustfmt(manifest, &packages, &files, args.check).await? {
+        unformatted.push("rustfmt found unformatted files".to_string());
+    }
+    check_result(unformatted)?;
+    if !args.check && run_clippy {
+        let touched: Vec<String> = touched_packages(&packages, &files);
+        if touched.len() == packages.len() {
+            execute_clippy(args, &clippy, None, true).await?;
+        } else if !touched.is_empty() {
+            execute_clippy(args, &clippy, Some(&touched), true).await?;
+        }
     }
-    if !args.check {
-        execute_clippy_fix(args).await?;
+    for file in &files {
+        let hash: u64 = content_hash(&std::fs::read(file)?);
+        cache.entries.insert(cache_file_key(file), hash);
     }
+    cache.save(&cache_path)?;
     Ok(())
 }
 
@@ -0,0 +1,177 @@
+use crate::*;
+
+impl FromStr for DerivePreset {
+    type Err = FmtError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "alphabetical" | "alpha" => Ok(Self::Alphabetical),
+            "std" | "std-first" => Ok(Self::Std),
+            _ => Err(FmtError::InvalidDeriveConfig(format!(
+                "unknown preset `{s}`"
+            ))),
+        }
+    }
+}
+
+impl DerivePreset {
+    /// Get the pattern groups of the preset
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<Vec<String>>`: Ordered groups of trait patterns
+    pub(crate) fn groups(&self) -> Vec<Vec<String>> {
+        let group = |traits: &[&str]| -> Vec<String> {
+            traits.iter().map(|name: &&str| name.to_string()).collect()
+        };
+        match self {
+            Self::Alphabetical => Vec::new(),
+            Self::Std => vec![group(STD_DERIVE_ORDER), group(SERDE_DERIVE_ORDER)],
+        }
+    }
+}
+
+impl From<DerivePreset> for DeriveOrder {
+    fn from(preset: DerivePreset) -> Self {
+        Self {
+            groups: preset.groups(),
+        }
+    }
+}
+
+impl DeriveOrder {
+    /// Check whether a trait path matches a pattern
+    ///
+    /// Patterns without `::` match the last path segment, patterns with
+    /// `::` match the full path and a trailing `*` matches any suffix.
+    ///
+    /// # Arguments
+    ///
+    /// - `&str`: Pattern from the configuration
+    /// - `&str`: Trait path
+    ///
+    /// # Returns
+    ///
+    /// - `bool`: True if the path matches
+    fn matches(pattern: &str, path: &str) -> bool {
+        let name: &str = if pattern.contains("::") {
+            path
+        } else {
+            path.rsplit("::").next().unwrap_or(path)
+        };
+        match pattern.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => name == pattern,
+        }
+    }
+
+    /// Build the sort key of a trait path
+    ///
+    /// # Arguments
+    ///
+    /// - `&str`: Trait path
+    ///
+    /// # Returns
+    ///
+    /// - `(usize, usize, String)`: Group index, pattern index and lowercased path
+    pub(crate) fn sort_key(&self, path: &str) -> (usize, usize, String) {
+        for (group_index, group) in self.groups.iter().enumerate() {
+            if let Some(pattern_index) = group
+                .iter()
+                .position(|pattern: &String| Self::matches(pattern, path))
+            {
+                return (group_index, pattern_index, path.to_lowercase());
+            }
+        }
+        (self.groups.len(), 0, path.to_lowercase())
+    }
+}
+
+impl FmtCache {
+    /// Load a cache file
+    ///
+    /// A missing or unreadable file, or one written with a different key,
+    /// yields an empty cache.
+    ///
+    /// # Arguments
+    ///
+    /// - `&Path`: Path to the cache file
+    /// - `u64`: Hash of the current settings
+    ///
+    /// # Returns
+    ///
+    /// - `FmtCache`: Loaded cache
+    pub(crate) fn load(path: &Path, key: u64) -> Self {
+        let mut cache: Self = Self {
+            key,
+            entries: HashMap::new(),
+        };
+        let Ok(content) = read_to_string(path) else {
+            return cache;
+        };
+        let mut lines: std::str::Lines<'_> = content.lines();
+        if lines.next() != Some(format!("key {key:016x}").as_str()) {
+            return cache;
+        }
+        for line in lines {
+            if let Some((hash, file)) = line.split_once(' ')
+                && let Ok(hash) = u64::from_str_radix(hash, 16)
+            {
+                cache.entries.insert(PathBuf::from(file), hash);
+            }
+        }
+        cache
+    }
+
+    /// Write the cache file
+    ///
+    /// # Arguments
+    ///
+    /// - `&Path`: Path to the cache file
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(), std::io::Error>`: Success or error
+    pub(crate) fn save(&self, path: &Path) -> Result<(), std::io::Error> {
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        let mut entries: Vec<(&PathBuf, &u64)> = self.entries.iter().collect();
+        entries.sort();
+        let mut content: String = format!("key {:016x}\n", self.key);
+        for (file, hash) in entries {
+            content.push_str(&format!("{hash:016x} {}\n", file.display()));
+        }
+        write(path, content)
+    }
+
+    /// Check whether a file is unchanged since it was formatted
+    ///
+    /// # Arguments
+    ///
+    /// - `&Path`: Canonical file path
+    /// - `u64`: Current content hash
+    ///
+    /// # Returns
+    ///
+    /// - `bool`: True if the cached hash matches
+    pub(crate) fn is_fresh(&self, file: &Path, hash: u64) -> bool {
+        self.entries.get(file) == Some(&hash)
+    }
+}
+
+impl Default for ClippySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            install: false,
+            all_targets: true,
+            all_features: false,
+            features: Vec::new(),
+            cargo_args: Vec::new(),
+            deny: Vec::new(),
+            warn: Vec::new(),
+            allow: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::*;
+
+/// Byte range of the trait list inside a `derive(...)` attribute
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct DeriveSpan {
+    /// Offset of the first byte after the opening parenthesis
+    pub start: usize,
+    /// Offset of the closing parenthesis
+    pub end: usize,
+}
+
+/// Replacement text for an unsorted derive list
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct DeriveEdit {
+    /// Range of the original trait list
+    pub span: DeriveSpan,
+    /// Sorted trait list
+    pub replacement: String,
+}
+
+/// Derive changes of a single file
+#[derive(Clone, Debug)]
+pub(crate) struct DeriveFileChange {
+    /// Path to the Rust file
+    pub path: PathBuf,
+    /// Lines of the unsorted derive lists
+    pub lines: Vec<usize>,
+    /// Content before sorting
+    pub original: String,
+    /// Content after sorting
+    pub formatted: String,
+}
+
+/// A single trait of a derive list with the comments attached to it
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct DeriveEntry {
+    /// Trait path with whitespace removed
+    pub path: String,
+    /// Comments on the lines before the trait
+    pub leading_comments: Vec<String>,
+    /// Comments after the trait on the same line
+    pub trailing_comments: Vec<String>,
+}
+
+/// Ordering applied to the traits of a derive list
+///
+/// Traits matching a pattern of an earlier group come first. Within a group
+/// traits follow the order of the patterns, traits matching no group are
+/// sorted case-insensitively after all groups.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct DeriveOrder {
+    /// Groups of trait patterns such as `Debug`, `serde::Serialize` or `serde::*`
+    pub groups: Vec<Vec<String>>,
+}
+
+/// Content hashes of files that were formatted by a previous fmt run
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct FmtCache {
+    /// Hash of the settings the entries were produced with
+    pub key: u64,
+    /// Content hash per canonical file path
+    pub entries: HashMap<PathBuf, u64>,
+}
+
+/// A `.gitignore` style pattern scoped to a directory
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct IgnoreRule {
+    /// Directory the pattern is relative to
+    pub base: PathBuf,
+    /// Glob pattern without leading `/`, trailing `/` or `!`
+    pub pattern: String,
+    /// The pattern re-includes matching paths
+    pub negated: bool,
+    /// The pattern only matches directories
+    pub dir_only: bool,
+    /// The pattern is matched against the whole relative path instead of the name
+    pub anchored: bool,
+}
+
+/// Settings of the clippy stage of fmt and of `lint`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ClippySettings {
+    /// Run `cargo clippy --fix` after formatting
+    pub enabled: bool,
+    /// Install clippy with rustup when it is missing
+    pub install: bool,
+    /// Check all targets instead of only lib and bins
+    pub all_targets: bool,
+    /// Activate all features
+    pub all_features: bool,
+    /// Features to activate
+    pub features: Vec<String>,
+    /// Extra cargo arguments such as `--target <TRIPLE>`
+    pub cargo_args: Vec<String>,
+    /// Lints passed with `-D`
+    pub deny: Vec<String>,
+    /// Lints passed with `-W`
+    pub warn: Vec<String>,
+    /// Lints passed with `-A`
+    pub allow: Vec<String>,
+}
+
+/// Bracket and multi-line string state while scanning TOML lines
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct TomlScanState {
+    /// Number of open arrays and inline tables
+    pub depth: usize,
+    /// Quote byte of the open multi-line string, if any
+    pub multiline: Option<u8>,
+}
+
+/// A key/value entry of a manifest table with the comments above it
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct ManifestEntry {
+    /// First segment of the key with quotes removed
+    pub key: String,
+    /// Comment lines directly above the entry
+    pub comments: Vec<String>,
+    /// Lines of the entry, more than one for multi-line values
+    pub lines: Vec<String>,
+}
+
+/// A table of a manifest
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct ManifestSection {
+    /// Header line, None for the lines before the first table
+    pub header: Option<String>,
+    /// Segments of the table name with quotes removed
+    pub name: Vec<String>,
+    /// Entries, comments and blank lines of the table
+    pub items: Vec<ManifestItem>,
+}
+
+/// Formatting changes of a single manifest
+#[derive(Clone, Debug)]
+pub(crate) struct ManifestChange {
+    /// Path to the Cargo.toml
+    pub path: PathBuf,
+    /// Content before formatting
+    pub original: String,
+    /// Content after formatting
+    pub formatted: String,
+}
+
+/// An entry of `[workspace.dependencies]`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct WorkspaceDependency {
+    /// Version requirement, None if the entry has no version
+    pub version: Option<String>,
+    /// Whether the entry keeps the default features
+    pub default_features: bool,
+    /// Features enabled by the entry
+    pub features: Vec<String>,
+}
@@ -11,3 +11,497 @@ fn test_format_path_integration() {
     let result: Result<(), std::io::Error> = rt.block_on(format_path(&tmp_dir));
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_sort_derives_in_source_single_line() {
+    let source: &str = "#[derive(Debug, Clone, serde::Serialize, Clone)] // keep\nstruct A;\n";
+    assert_eq!(
+        sort_derives_in_source(source, &DeriveOrder::default()),
+        "#[derive(Clone, Debug, serde::Serialize)] // keep\nstruct A;\n"
+    );
+    let cfg_attr: &str = "#[cfg_attr(feature = \"derive(x)\", derive(Serialize, Deserialize))]\n";
+    assert_eq!(
+        sort_derives_in_source(cfg_attr, &DeriveOrder::default()),
+        "#[cfg_attr(feature = \"derive(x)\", derive(Deserialize, Serialize))]\n"
+    );
+}
+
+#[test]
+fn test_sort_derives_in_source_multi_line() {
+    let source: &str = "#[derive(\n    PartialEq,\n    // needed for logs\n    Debug, // trailing\n    Clone\n)]\nstruct A;\n";
+    assert_eq!(
+        sort_derives_in_source(source, &DeriveOrder::default()),
+        "#[derive(\n    Clone,\n    // needed for logs\n    Debug, // trailing\n    PartialEq,\n)]\nstruct A;\n"
+    );
+}
+
+#[test]
+fn test_sort_derives_ignores_comments_and_strings() {
+    let source: &str = "// #[derive(B, A)]\n/* #[derive(B, A)] */\nconst S: &str = \"#[derive(B, A)]\";\nconst R: &str = r#\"#[derive(B, A)]\"#;\nfn f<'a>(c: char) -> bool { c == '#' }\n#[derive(B, A)]\nstruct A;\n";
+    let expected: String = source.replace("#[derive(B, A)]\nstruct", "#[derive(A, B)]\nstruct");
+    assert_eq!(
+        sort_derives_in_source(source, &DeriveOrder::default()),
+        expected
+    );
+}
+
+#[test]
+fn test_derive_order_std_preset() {
+    let order: DeriveOrder = DeriveOrder::from(DerivePreset::Std);
+    let source: &str = "#[derive(Zeroize, serde::Deserialize, Default, Serialize, Hash, PartialEq, Debug, Clone, Eq)]\n";
+    assert_eq!(
+        sort_derives_in_source(source, &order),
+        "#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, serde::Deserialize, Zeroize)]\n"
+    );
+}
+
+#[test]
+fn test_load_derive_order() {
+    let root: PathBuf = PathBuf::from("./tmp/test_derive_order");
+    let _ = std::fs::remove_dir_all(&root);
+    create_dir_all(&root).unwrap();
+    let manifest: PathBuf = root.join("Cargo.toml");
+    write(
+        &manifest,
+        "[workspace]\nmembers = []\n\n[workspace.metadata.hyperlane-cli.fmt.derive]\npreset = \"std\"\norder = [\"Clone\"]\ngroups = [[\"Debug\"], [\"serde::*\"]]\n",
+    )
+    .unwrap();
+    let order: DeriveOrder = load_derive_order(&manifest).unwrap();
+    assert_eq!(
+        order.groups,
+        vec![vec!["Clone"], vec!["Debug"], vec!["serde::*"]]
+    );
+    assert_eq!(
+        sort_derives_in_source("#[derive(Ord, serde::Serialize, Debug, Clone)]", &order),
+        "#[derive(Clone, Debug, serde::Serialize, Ord)]"
+    );
+    write(
+        &manifest,
+        "[workspace]\n\n[workspace.metadata.hyperlane-cli.fmt.derive]\npreset = \"random\"\n",
+    )
+    .unwrap();
+    assert!(matches!(
+        load_derive_order(&manifest),
+        Err(FmtError::InvalidDeriveConfig(_))
+    ));
+    assert_eq!(
+        load_derive_order(&root.join("missing.toml")).unwrap(),
+        DeriveOrder::default()
+    );
+}
+
+#[test]
+fn test_format_unified_diff() {
+    let old: &str = "a\nb\nc\nd\ne\nf\ng\nh\n#[derive(B, A)]\ni\n";
+    let new: &str = "a\nb\nc\nd\ne\nf\ng\nh\n#[derive(A, B)]\ni\n";
+    assert_eq!(
+        format_unified_diff("src/lib.rs", old, new),
+        "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -6,5 +6,5 @@\n f\n g\n h\n-#[derive(B, A)]\n+#[derive(A, B)]\n i\n"
+    );
+    assert!(format_unified_diff("src/lib.rs", old, old).is_empty());
+    let removed: Vec<DiffLine> = diff_lines(&["x", "y"], &[]);
+    assert_eq!(
+        removed,
+        vec![DiffLine::Removed("x"), DiffLine::Removed("y")]
+    );
+}
+
+#[test]
+fn test_derive_edits_lines() {
+    let source: &str = "struct A;\n#[derive(Clone, Debug)]\nstruct B;\n#[derive(\n    Debug,\n    Clone,\n)]\nstruct C;\n";
+    let edits: Vec<DeriveEdit> = derive_edits(source, &DeriveOrder::default());
+    assert_eq!(edits.len(), 1);
+    assert_eq!(source[..edits[0].span.start].matches('\n').count() + 1, 4);
+    assert_eq!(edits[0].replacement, "\n    Clone,\n    Debug,\n");
+}
+
+#[test]
+fn test_fmt_cache_round_trip() {
+    let path: PathBuf = PathBuf::from("./tmp/test_fmt_cache/target/hyperlane-cli/fmt-cache");
+    let _ = std::fs::remove_dir_all("./tmp/test_fmt_cache");
+    let hash: u64 = content_hash(b"fn main() {}\n");
+    assert_ne!(hash, content_hash(b"fn main() { }\n"));
+    let mut cache: FmtCache = FmtCache::load(&path, 7);
+    assert!(cache.entries.is_empty());
+    cache.entries.insert(PathBuf::from("/src/main.rs"), hash);
+    cache.save(&path).unwrap();
+    let loaded: FmtCache = FmtCache::load(&path, 7);
+    assert!(loaded.is_fresh(Path::new("/src/main.rs"), hash));
+    assert!(!loaded.is_fresh(Path::new("/src/main.rs"), hash + 1));
+    assert!(FmtCache::load(&path, 8).entries.is_empty());
+}
+
+#[test]
+fn test_execute_fmt_check_runs_every_check() {
+    let root: PathBuf = PathBuf::from("./tmp/test_fmt_check_all");
+    let _ = std::fs::remove_dir_all(&root);
+    create_dir_all(root.join("src")).unwrap();
+    write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"fmt-check\"\nversion = \"0.1.0\"\nedition = \"2024\"\n",
+    )
+    .unwrap();
+    let source: &str = "#[derive(Debug, Clone)]\nstruct Unit;\n\nfn main(){let _unit=Unit;}\n";
+    write(root.join("src/main.rs"), source).unwrap();
+    let raw_args: Vec<String> = [
+        "hyperlane-cli",
+        "fmt",
+        "--check",
+        "--no-clippy",
+        "--manifest-path",
+        "./tmp/test_fmt_check_all/Cargo.toml",
+    ]
+    .iter()
+    .map(|arg: &&str| arg.to_string())
+    .collect();
+    let rt: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    let result: Result<(), FmtError> = rt.block_on(execute_fmt(&parse_args_from(&raw_args)));
+    match result {
+        Err(FmtError::CheckFailed(unformatted)) => {
+            assert_eq!(unformatted.len(), 2);
+            assert!(unformatted[0].contains("derive"));
+            assert!(unformatted[1].contains("rustfmt"));
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+    assert_eq!(
+        std::fs::read_to_string(root.join("src/main.rs")).unwrap(),
+        source
+    );
+}
+
+#[test]
+fn test_package_source_roots() {
+    let root: PathBuf = PathBuf::from("./tmp/test_fmt_roots");
+    let _ = std::fs::remove_dir_all(&root);
+    for dir in ["src", "tests", "examples", "codegen/lib", "tools"] {
+        create_dir_all(root.join(dir)).unwrap();
+    }
+    write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"roots\"\nversion = \"0.1.0\"\n\n[lib]\npath = \"codegen/lib/mod.rs\"\n\n[[bin]]\nname = \"tool\"\npath = \"tools/tool.rs\"\n\n[[bin]]\nname = \"main\"\npath = \"src/main.rs\"\n\n[[example]]\nname = \"top\"\npath = \"top.rs\"\n",
+    )
+    .unwrap();
+    for file in [
+        "build.rs",
+        "top.rs",
+        "src/main.rs",
+        "codegen/lib/mod.rs",
+        "tools/tool.rs",
+    ] {
+        write(root.join(file), "\n").unwrap();
+    }
+    let roots: Vec<PathBuf> = package_source_roots(&root);
+    assert_eq!(
+        roots,
+        vec![
+            root.join("src"),
+            root.join("tests"),
+            root.join("examples"),
+            root.join("build.rs"),
+            root.join("codegen/lib"),
+            root.join("tools"),
+            root.join("top.rs"),
+        ]
+    );
+}
+
+#[test]
+fn test_ignore_rules() {
+    let base: &Path = Path::new("./ws");
+    let rules: Vec<IgnoreRule> = [
+        "# comment",
+        "generated/",
+        "/src/legacy/**",
+        "*.pb.rs",
+        "!keep.pb.rs",
+    ]
+    .iter()
+    .filter_map(|line: &&str| parse_ignore_rule(base, line))
+    .collect();
+    assert_eq!(rules.len(), 4);
+    assert!(is_ignored(&rules, Path::new("ws/src/generated"), true));
+    assert!(!is_ignored(&rules, Path::new("ws/src/generated"), false));
+    assert!(is_ignored(
+        &rules,
+        Path::new("./ws/src/legacy/a/b.rs"),
+        false
+    ));
+    assert!(!is_ignored(
+        &rules,
+        Path::new("ws/crates/src/legacy/a.rs"),
+        false
+    ));
+    assert!(is_ignored(&rules, Path::new("ws/src/api.pb.rs"), false));
+    assert!(!is_ignored(&rules, Path::new("ws/src/keep.pb.rs"), false));
+    assert!(!is_ignored(&rules, Path::new("other/api.pb.rs"), false));
+}
+
+#[test]
+fn test_load_fmt_excludes() {
+    let root: PathBuf = PathBuf::from("./tmp/test_fmt_excludes");
+    let _ = std::fs::remove_dir_all(&root);
+    create_dir_all(&root).unwrap();
+    let manifest: PathBuf = root.join("Cargo.toml");
+    write(
+        &manifest,
+        "[workspace]\nmembers = []\n\n[workspace.metadata.hyperlane-cli.fmt]\nexclude = [\"src/generated/**\"]\n",
+    )
+    .unwrap();
+    let rules: Vec<IgnoreRule> = load_fmt_excludes(&manifest).unwrap();
+    assert!(is_ignored(
+        &rules,
+        &root.join("src/generated/api.rs"),
+        false
+    ));
+    assert!(!is_ignored(&rules, &root.join("src/lib.rs"), false));
+    write(
+        &manifest,
+        "[workspace]\n\n[workspace.metadata.hyperlane-cli.fmt]\nexclude = \"src\"\n",
+    )
+    .unwrap();
+    assert!(matches!(
+        load_fmt_excludes(&manifest),
+        Err(FmtError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn test_load_clippy_settings() {
+    let root: PathBuf = PathBuf::from("./tmp/test_fmt_clippy_settings");
+    let _ = std::fs::remove_dir_all(&root);
+    create_dir_all(&root).unwrap();
+    let manifest: PathBuf = root.join("Cargo.toml");
+    write(&manifest, "[workspace]\nmembers = []\n").unwrap();
+    let settings: ClippySettings = load_clippy_settings(&manifest).unwrap();
+    assert_eq!(settings, ClippySettings::default());
+    assert!(!settings.enabled);
+    assert_eq!(
+        clippy_command_args(&settings, None, None, true),
+        vec![
+            "clippy",
+            "--fix",
+            "--allow-dirty",
+            "--workspace",
+            "--all-targets"
+        ]
+    );
+    write(
+        &manifest,
+        "[workspace]\nmembers = []\n\n[workspace.metadata.hyperlane-cli.fmt.clippy]\nenabled = true\nall-targets = false\nfeatures = [\"a\", \"b\"]\nargs = [\"--target\", \"x86_64-unknown-linux-gnu\"]\ndeny = [\"warnings\"]\nallow = [\"clippy::too_many_arguments\"]\n",
+    )
+    .unwrap();
+    let settings: ClippySettings = load_clippy_settings(&manifest).unwrap();
+    assert!(settings.enabled);
+    assert_eq!(
+        clippy_command_args(
+            &settings,
+            Some(&["core".to_string()]),
+            Some("Cargo.toml"),
+            false
+        ),
+        vec![
+            "clippy",
+            "-p",
+            "core",
+            "--features",
+            "a,b",
+            "--manifest-path",
+            "Cargo.toml",
+            "--target",
+            "x86_64-unknown-linux-gnu",
+            "--",
+            "-D",
+            "warnings",
+            "-A",
+            "clippy::too_many_arguments",
+        ]
+    );
+    write(
+        &manifest,
+        "[workspace]\n\n[workspace.metadata.hyperlane-cli.fmt.clippy]\nenabled = \"yes\"\n",
+    )
+    .unwrap();
+    assert!(matches!(
+        load_clippy_settings(&manifest),
+        Err(FmtError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn test_format_manifest() {
+    let content: &str = r#"# Example manifest
+[package]
+version = "0.1.0"
+edition="2024"
+name = "app" # the binary
+custom = true
+
+[ dependencies ]
+# Runtime
+tokio = {version="1.0",features=["full",]}
+serde = { version = "^1.0.200", features = ["derive"] }
+regex = "1.10"
+anyhow = { path = "../anyhow" }
+
+local-core = { path = "../core" }
+
+[target.'cfg(unix)'.dependencies]
+nix = "0.29"
+libc = "0.2"
+
+[features]
+default = [
+    "std",
+]
+"#;
+    let dependency = |version: &str| -> WorkspaceDependency {
+        WorkspaceDependency {
+            version: Some(version.to_string()),
+            default_features: true,
+            features: Vec::new(),
+        }
+    };
+    let mut workspace: HashMap<String, WorkspaceDependency> = HashMap::new();
+    workspace.insert("serde".to_string(), dependency("1.0.200"));
+    workspace.insert("regex".to_string(), dependency("1.11"));
+    workspace.insert("anyhow".to_string(), dependency("1.0"));
+    let (formatted, warnings): (String, Vec<String>) = format_manifest(content, &workspace);
+    assert_eq!(
+        formatted,
+        r#"# Example manifest
+[package]
+name = "app" # the binary
+version = "0.1.0"
+edition = "2024"
+custom = true
+
+[dependencies]
+anyhow = { path = "../anyhow" }
+regex = "1.10"
+serde = { workspace = true, features = ["derive"] }
+# Runtime
+tokio = { version = "1.0", features = ["full"] }
+
+local-core = { path = "../core" }
+
+[target.'cfg(unix)'.dependencies]
+libc = "0.2"
+nix = "0.29"
+
+[features]
+default = [
+    "std",
+]
+"#
+    );
+    assert_eq!(
+        warnings,
+        vec![
+            "dependency `regex` requires \"1.10\" but [workspace.dependencies] has \"1.11\""
+                .to_string()
+        ]
+    );
+    assert_eq!(format_manifest(&formatted, &workspace).0, formatted);
+}
+
+#[test]
+fn test_format_manifest_keeps_non_inheritable_dependencies() {
+    let mut workspace: HashMap<String, WorkspaceDependency> = HashMap::new();
+    workspace.insert(
+        "regex".to_string(),
+        WorkspaceDependency {
+            version: Some("1".to_string()),
+            default_features: true,
+            features: Vec::new(),
+        },
+    );
+    workspace.insert(
+        "serde".to_string(),
+        WorkspaceDependency {
+            version: Some("1.0".to_string()),
+            default_features: false,
+            features: vec!["derive".to_string()],
+        },
+    );
+    let unchanged: &[&str] = &[
+        "[dependencies]\nregex = { version = \"1\", default-features = false, features = [\"std\"] }\n",
+        "[dependencies]\nregex = { version = \"1\", package = \"fancy-regex\" }\n",
+        "[dependencies]\nregex = { version = \"1\", public = true }\n",
+        "[dependencies]\nserde = \"1.0\"\n",
+        "[dependencies]\nserde = { version = \"1.0\", default-features = false }\n",
+    ];
+    for content in unchanged {
+        assert_eq!(format_manifest(content, &workspace).0, *content);
+    }
+    assert_eq!(
+        format_manifest(
+            "[dependencies]\nserde = { version = \"1.0\", default-features = false, features = [\"derive\", \"rc\"] }\n",
+            &workspace
+        )
+        .0,
+        "[dependencies]\nserde = { workspace = true, default-features = false, features = [\"derive\", \"rc\"] }\n"
+    );
+    assert_eq!(
+        format_manifest(
+            "[dependencies]\nregex = { version = \"1\", default-features = true, optional = true }\n",
+            &workspace
+        )
+        .0,
+        "[dependencies]\nregex = { workspace = true, default-features = true, optional = true }\n"
+    );
+}
+
+#[test]
+fn test_load_workspace_dependencies() {
+    let root: PathBuf = PathBuf::from("./tmp/test_fmt_workspace_dependencies");
+    let _ = std::fs::remove_dir_all(&root);
+    create_dir_all(&root).unwrap();
+    let manifest: PathBuf = root.join("Cargo.toml");
+    write(
+        &manifest,
+        "[workspace]\nmembers = []\n\n[workspace.dependencies]\nserde = { version = \"1.0\", features = [\"derive\"] }\nregex = \"1.11\"\ncore = { path = \"core\" }\n",
+    )
+    .unwrap();
+    let dependencies: HashMap<String, WorkspaceDependency> =
+        load_workspace_dependencies(&manifest).unwrap();
+    assert_eq!(dependencies["serde"].version, Some("1.0".to_string()));
+    assert_eq!(dependencies["serde"].features, vec!["derive".to_string()]);
+    assert_eq!(dependencies["regex"].version, Some("1.11".to_string()));
+    assert!(dependencies["regex"].default_features);
+    assert_eq!(dependencies["core"].version, None);
+}
+
+#[test]
+fn test_execute_fmt_cache_reruns_when_clippy_is_enabled() {
+    let root: PathBuf = PathBuf::from("./tmp/test_fmt_cache_clippy");
+    let _ = std::fs::remove_dir_all(&root);
+    create_dir_all(root.join("src")).unwrap();
+    write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"fmt-cache-clippy\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[package.metadata.hyperlane-cli.fmt.clippy]\nargs = [\"--no-such-flag\"]\n",
+    )
+    .unwrap();
+    write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+    let fmt_args = |clippy: &str| -> Args {
+        let raw_args: Vec<String> = [
+            "hyperlane-cli",
+            "fmt",
+            clippy,
+            "--manifest-path",
+            "./tmp/test_fmt_cache_clippy/Cargo.toml",
+        ]
+        .iter()
+        .map(|arg: &&str| arg.to_string())
+        .collect();
+        parse_args_from(&raw_args)
+    };
+    let rt: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        assert!(execute_fmt(&fmt_args("--no-clippy")).await.is_ok());
+        assert!(matches!(
+            execute_fmt(&fmt_args("--clippy")).await,
+            Err(FmtError::IoError(_))
+        ));
+    });
+}
@@ -0,0 +1,10 @@
+/// Error types for git operations
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum GitError {
+    /// Git command exited with an error
+    #[error("git {0} failed: {1}")]
+    CommandFailed(String, String),
+    /// IO error
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
@@ -0,0 +1,191 @@
+use crate::*;
+
+/// Run a git command and capture its standard output
+///
+/// # Arguments
+///
+/// - `&Path`: Working directory
+/// - `&[&str]`: Git arguments
+///
+/// # Returns
+///
+/// - `Result<String, GitError>`: Trimmed standard output or error
+pub(crate) async fn run_git(dir: &Path, git_args: &[&str]) -> Result<String, GitError> {
+    let output: std::process::Output = Command::new("git")
+        .args(git_args)
+        .current_dir(dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await?;
+    if !output.status.success() {
+        return Err(GitError::CommandFailed(
+            git_args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Build the release tag name of a package version
+///
+/// # Arguments
+///
+/// - `&str`: Package name
+/// - `&str`: Package version
+///
+/// # Returns
+///
+/// - `String`: Tag name in the form `<name>-v<version>`
+pub(crate) fn release_tag_name(name: &str, version: &str) -> String {
+    format!("{name}-v{version}")
+}
+
+/// Find the most recent release tag of a package
+///
+/// Tags of other packages whose names start with `<name>-v`, such as
+/// `foo-vendor-v1.0.0` for `foo`, are skipped because their remainder is
+/// not a version.
+///
+/// # Arguments
+///
+/// - `&Path`: Directory inside the repository
+/// - `&str`: Package name
+///
+/// # Returns
+///
+/// - `Result<Option<String>, GitError>`: Highest `<name>-v*` tag if any
+pub(crate) async fn last_release_tag(dir: &Path, name: &str) -> Result<Option<String>, GitError> {
+    let pattern: String = release_tag_name(name, "*");
+    let prefix: String = release_tag_name(name, "");
+    let tags: String = run_git(dir, &["tag", "--list", &pattern, "--sort=-v:refname"]).await?;
+    Ok(tags
+        .lines()
+        .find(|tag: &&str| {
+            tag.strip_prefix(&prefix)
+                .is_some_and(|version: &str| parse_version(version).is_some())
+        })
+        .map(|tag: &str| tag.to_string()))
+}
+
+/// Check whether a directory has changes compared to a git reference
+///
+/// Compares the reference with the working tree, so uncommitted edits count
+/// as changes.
+///
+/// # Arguments
+///
+/// - `&Path`: Directory to check
+/// - `&str`: Git reference such as a tag, branch or commit
+///
+/// # Returns
+///
+/// - `Result<bool, GitError>`: True if any file below the directory changed
+pub(crate) async fn has_changes_since(dir: &Path, reference: &str) -> Result<bool, GitError> {
+    let changed: String = run_git(dir, &["diff", "--name-only", reference, "--", "."]).await?;
+    Ok(!changed.is_empty())
+}
+
+/// List uncommitted changes of the working tree
+///
+/// # Arguments
+///
+/// - `&Path`: Directory inside the repository
+///
+/// # Returns
+///
+/// - `Result<Vec<String>, GitError>`: Porcelain status lines, empty if the tree is clean
+pub(crate) async fn uncommitted_changes(dir: &Path) -> Result<Vec<String>, GitError> {
+    let status: String = run_git(dir, &["status", "--porcelain"]).await?;
+    Ok(status.lines().map(|line: &str| line.to_string()).collect())
+}
+
+/// Find the nearest release tag reachable from HEAD
+///
+/// # Arguments
+///
+/// - `&Path`: Directory inside the repository
+///
+/// # Returns
+///
+/// - `Option<String>`: Closest `<name>-v<version>` tag, None if there is none
+pub(crate) async fn nearest_release_tag(dir: &Path) -> Option<String> {
+    run_git(
+        dir,
+        &["describe", "--tags", "--abbrev=0", "--match", "*-v*"],
+    )
+    .await
+    .ok()
+    .filter(|tag: &String| !tag.is_empty())
+}
+
+/// List commit subjects since a git reference
+///
+/// # Arguments
+///
+/// - `&Path`: Directory inside the repository
+/// - `Option<&str>`: Reference to start after, None for the whole history
+///
+/// # Returns
+///
+/// - `Result<Vec<String>, GitError>`: Subjects of non-merge commits, newest first
+pub(crate) async fn commit_subjects_since(
+    dir: &Path,
+    reference: Option<&str>,
+) -> Result<Vec<String>, GitError> {
+    let range: String = match reference {
+        Some(reference) => format!("{reference}..HEAD"),
+        None => "HEAD".to_string(),
+    };
+    let log: String = run_git(dir, &["log", "--no-merges", "--format=%s", &range]).await?;
+    Ok(log.lines().map(|line: &str| line.to_string()).collect())
+}
+
+/// Check whether a tag exists
+///
+/// # Arguments
+///
+/// - `&Path`: Directory inside the repository
+/// - `&str`: Tag name
+///
+/// # Returns
+///
+/// - `Result<bool, GitError>`: True if the tag exists
+pub(crate) async fn tag_exists(dir: &Path, tag: &str) -> Result<bool, GitError> {
+    let tags: String = run_git(dir, &["tag", "--list", tag]).await?;
+    Ok(!tags.is_empty())
+}
+
+/// List the files changed compared to a git reference
+///
+/// Includes uncommitted edits and untracked files that are not ignored.
+///
+/// # Arguments
+///
+/// - `&Path`: Directory to list changes below
+/// - `&str`: Git reference such as `HEAD`, a branch or a commit
+///
+/// # Returns
+///
+/// - `Result<Vec<PathBuf>, GitError>`: Changed paths joined onto the directory
+pub(crate) async fn changed_files(dir: &Path, reference: &str) -> Result<Vec<PathBuf>, GitError> {
+    let diff: String = run_git(
+        dir,
+        &["diff", "--name-only", "--relative", reference, "--", "."],
+    )
+    .await?;
+    let untracked: String = run_git(
+        dir,
+        &["ls-files", "--others", "--exclude-standard", "--", "."],
+    )
+    .await?;
+    let mut files: Vec<PathBuf> = diff
+        .lines()
+        .chain(untracked.lines())
+        .filter(|line: &&str| !line.is_empty())
+        .map(|line: &str| dir.join(line))
+        .collect();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
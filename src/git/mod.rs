@@ -0,0 +1,7 @@
+mod r#enum;
+mod r#fn;
+
+#[cfg(test)]
+mod test;
+
+pub(crate) use {r#enum::*, r#fn::*};
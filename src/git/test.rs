@@ -0,0 +1,141 @@
+use crate::*;
+
+#[test]
+fn test_release_tag_name() {
+    assert_eq!(release_tag_name("hyperlane", "0.1.0"), "hyperlane-v0.1.0");
+}
+
+#[test]
+fn test_last_release_tag_and_changes() {
+    let repo: PathBuf = PathBuf::from("./tmp/test_git_changes");
+    let _ = std::fs::remove_dir_all(&repo);
+    create_dir_all(repo.join("core")).unwrap();
+    create_dir_all(repo.join("app")).unwrap();
+    write(repo.join("core/lib.rs"), "// core\n").unwrap();
+    write(repo.join("app/main.rs"), "// app\n").unwrap();
+    let rt: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let commit_args: [&str; 8] = [
+            "-c",
+            "user.name=test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-qm",
+            "release",
+            "--no-gpg-sign",
+        ];
+        run_git(&repo, &["init", "-q"]).await.unwrap();
+        run_git(&repo, &["add", "."]).await.unwrap();
+        run_git(&repo, &commit_args).await.unwrap();
+        run_git(&repo, &["tag", "core-v0.1.0"]).await.unwrap();
+        run_git(&repo, &["tag", "core-v0.2.0"]).await.unwrap();
+        run_git(&repo, &["tag", "core-vendor-v2.0.0"])
+            .await
+            .unwrap();
+        assert_eq!(
+            last_release_tag(&repo, "core").await.unwrap(),
+            Some("core-v0.2.0".to_string())
+        );
+        assert_eq!(
+            last_release_tag(&repo, "core-vendor").await.unwrap(),
+            Some("core-vendor-v2.0.0".to_string())
+        );
+        assert_eq!(last_release_tag(&repo, "app").await.unwrap(), None);
+        write(repo.join("app/main.rs"), "// app changed\n").unwrap();
+        assert!(
+            !has_changes_since(&repo.join("core"), "core-v0.2.0")
+                .await
+                .unwrap()
+        );
+        assert!(
+            has_changes_since(&repo.join("app"), "core-v0.2.0")
+                .await
+                .unwrap()
+        );
+        assert!(has_changes_since(&repo, "missing-ref").await.is_err());
+    });
+}
+
+#[test]
+fn test_release_history_queries() {
+    let repo: PathBuf = PathBuf::from("./tmp/test_git_history");
+    let _ = std::fs::remove_dir_all(&repo);
+    create_dir_all(&repo).unwrap();
+    write(repo.join("lib.rs"), "// v1\n").unwrap();
+    let rt: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let commit = |message: &'static str| -> [&'static str; 8] {
+            [
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-qm",
+                message,
+                "--no-gpg-sign",
+            ]
+        };
+        run_git(&repo, &["init", "-q"]).await.unwrap();
+        run_git(&repo, &["add", "."]).await.unwrap();
+        run_git(&repo, &commit("Initial")).await.unwrap();
+        assert_eq!(nearest_release_tag(&repo).await, None);
+        run_git(&repo, &["tag", "core-v0.1.0"]).await.unwrap();
+        write(repo.join("lib.rs"), "// v2\n").unwrap();
+        assert_eq!(uncommitted_changes(&repo).await.unwrap().len(), 1);
+        run_git(&repo, &["add", "."]).await.unwrap();
+        run_git(&repo, &commit("Add feature")).await.unwrap();
+        assert!(uncommitted_changes(&repo).await.unwrap().is_empty());
+        assert_eq!(
+            nearest_release_tag(&repo).await,
+            Some("core-v0.1.0".to_string())
+        );
+        assert_eq!(
+            commit_subjects_since(&repo, Some("core-v0.1.0"))
+                .await
+                .unwrap(),
+            vec!["Add feature"]
+        );
+        assert_eq!(
+            commit_subjects_since(&repo, None).await.unwrap(),
+            vec!["Add feature", "Initial"]
+        );
+    });
+}
+
+#[test]
+fn test_changed_files() {
+    let repo: PathBuf = PathBuf::from("./tmp/test_git_changed_files");
+    let _ = std::fs::remove_dir_all(&repo);
+    create_dir_all(repo.join("src")).unwrap();
+    write(repo.join("src/lib.rs"), "// v1\n").unwrap();
+    write(repo.join("src/main.rs"), "fn main() {}\n").unwrap();
+    let rt: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        run_git(&repo, &["init", "-q"]).await.unwrap();
+        run_git(&repo, &["add", "."]).await.unwrap();
+        run_git(
+            &repo,
+            &[
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-qm",
+                "Initial",
+                "--no-gpg-sign",
+            ],
+        )
+        .await
+        .unwrap();
+        assert!(changed_files(&repo, "HEAD").await.unwrap().is_empty());
+        write(repo.join("src/lib.rs"), "// v2\n").unwrap();
+        write(repo.join("src/new.rs"), "\n").unwrap();
+        assert_eq!(
+            changed_files(&repo, "HEAD").await.unwrap(),
+            vec![repo.join("src/lib.rs"), repo.join("src/new.rs")]
+        );
+    });
+}
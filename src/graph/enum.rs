@@ -0,0 +1,27 @@
+use crate::*;
+
+/// Error types for graph operation
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum GraphError {
+    /// Unknown output format
+    #[error("Invalid graph format: {0}")]
+    InvalidFormat(String),
+    /// Unknown dependency edge kind
+    #[error("Invalid edge kind: {0} (expected normal, dev, build or all)")]
+    InvalidEdgeKind(String),
+    /// Package discovery or ordering failed
+    #[error(transparent)]
+    PublishError(#[from] PublishError),
+}
+
+/// Output format of the dependency graph
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum GraphFormat {
+    /// Graphviz DOT
+    #[default]
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+    /// JSON document with packages and edges
+    Json,
+}
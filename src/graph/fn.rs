@@ -0,0 +1,346 @@
+use crate::*;
+
+/// Parse a comma separated list of dependency edge kinds
+///
+/// # Arguments
+///
+/// - `&str`: Kinds such as `normal,dev` or `all`
+///
+/// # Returns
+///
+/// - `Result<Vec<DependencyKind>, GraphError>`: Parsed kinds or error for an unknown kind
+pub(crate) fn parse_edge_kinds(value: &str) -> Result<Vec<DependencyKind>, GraphError> {
+    let mut kinds: Vec<DependencyKind> = Vec::new();
+    for kind in value
+        .split(',')
+        .map(str::trim)
+        .filter(|s: &&str| !s.is_empty())
+    {
+        let parsed: &[DependencyKind] = match kind.to_lowercase().as_str() {
+            "normal" => &[DependencyKind::Normal],
+            "dev" => &[DependencyKind::Dev],
+            "build" => &[DependencyKind::Build],
+            "all" => &[
+                DependencyKind::Normal,
+                DependencyKind::Dev,
+                DependencyKind::Build,
+            ],
+            _ => return Err(GraphError::InvalidEdgeKind(kind.to_string())),
+        };
+        for parsed_kind in parsed {
+            if !kinds.contains(parsed_kind) {
+                kinds.push(*parsed_kind);
+            }
+        }
+    }
+    Ok(kinds)
+}
+
+/// Collect the dependency edges between workspace packages
+///
+/// # Arguments
+///
+/// - `&[Package]`: Workspace packages
+/// - `&[DependencyKind]`: Dependency kinds to include
+///
+/// # Returns
+///
+/// - `Vec<GraphEdge>`: Sorted edges without duplicates
+pub(crate) fn collect_graph_edges(
+    packages: &[Package],
+    kinds: &[DependencyKind],
+) -> Vec<GraphEdge> {
+    let mut edges: Vec<GraphEdge> = Vec::new();
+    for package in packages {
+        for dependency in &package.dependencies {
+            if !kinds.contains(&dependency.kind)
+                || !packages.iter().any(|p: &Package| p.name == dependency.name)
+            {
+                continue;
+            }
+            edges.push(GraphEdge {
+                from: package.name.clone(),
+                to: dependency.name.clone(),
+                kind: dependency.kind,
+            });
+        }
+    }
+    edges.sort();
+    edges.dedup();
+    edges
+}
+
+/// Keep a package and every package that transitively depends on it
+///
+/// # Arguments
+///
+/// - `&[Package]`: Workspace packages
+/// - `&[GraphEdge]`: Dependency edges
+/// - `&str`: Name of the depended-on package
+///
+/// # Returns
+///
+/// - `Result<Vec<Package>, GraphError>`: The package and its reverse dependencies
+pub(crate) fn reverse_dependencies(
+    packages: &[Package],
+    edges: &[GraphEdge],
+    name: &str,
+) -> Result<Vec<Package>, GraphError> {
+    if !packages.iter().any(|p: &Package| p.name == name) {
+        return Err(PublishError::PackageNotFound(name.to_string()).into());
+    }
+    let mut kept: Vec<String> = vec![name.to_string()];
+    let mut index: usize = 0;
+    while index < kept.len() {
+        let current: String = kept[index].clone();
+        for edge in edges {
+            if edge.to == current && !kept.contains(&edge.from) {
+                kept.push(edge.from.clone());
+            }
+        }
+        index += 1;
+    }
+    Ok(packages
+        .iter()
+        .filter(|p: &&Package| kept.contains(&p.name))
+        .cloned()
+        .collect())
+}
+
+/// Build a node identifier that is valid in Mermaid
+///
+/// # Arguments
+///
+/// - `&str`: Package name
+///
+/// # Returns
+///
+/// - `String`: Name with every non-alphanumeric character replaced by `_`
+fn mermaid_id(name: &str) -> String {
+    name.chars()
+        .map(|c: char| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Render the graph in Graphviz DOT format
+///
+/// # Arguments
+///
+/// - `&[Package]`: Packages shown as nodes
+/// - `&[GraphEdge]`: Dependency edges
+/// - `Option<&[Vec<Package>]>`: Publish levels drawn as clusters
+///
+/// # Returns
+///
+/// - `String`: DOT document
+pub(crate) fn format_graph_dot(
+    packages: &[Package],
+    edges: &[GraphEdge],
+    levels: Option<&[Vec<Package>]>,
+) -> String {
+    let node = |package: &Package| -> String {
+        format!(
+            "\"{}\" [label=\"{}\\n{}\"];",
+            package.name, package.name, package.version
+        )
+    };
+    let mut output: String = String::from("digraph workspace {\n    node [shape=box];\n");
+    match levels {
+        Some(levels) => {
+            for (index, level) in levels.iter().enumerate() {
+                output.push_str(&format!(
+                    "    subgraph cluster_level_{index} {{\n        label=\"level {index}\";\n        rank=same;\n"
+                ));
+                for package in level {
+                    output.push_str(&format!("        {}\n", node(package)));
+                }
+                output.push_str("    }\n");
+            }
+        }
+        None => {
+            for package in packages {
+                output.push_str(&format!("    {}\n", node(package)));
+            }
+        }
+    }
+    for edge in edges {
+        let style: &str = match edge.kind {
+            DependencyKind::Normal => "",
+            DependencyKind::Dev => " [style=dashed, label=\"dev\"]",
+            DependencyKind::Build => " [style=dotted, label=\"build\"]",
+        };
+        output.push_str(&format!(
+            "    \"{}\" -> \"{}\"{style};\n",
+            edge.from, edge.to
+        ));
+    }
+    output.push_str("}\n");
+    output
+}
+
+/// Render the graph as a Mermaid flowchart
+///
+/// # Arguments
+///
+/// - `&[Package]`: Packages shown as nodes
+/// - `&[GraphEdge]`: Dependency edges
+/// - `Option<&[Vec<Package>]>`: Publish levels drawn as subgraphs
+///
+/// # Returns
+///
+/// - `String`: Mermaid document
+pub(crate) fn format_graph_mermaid(
+    packages: &[Package],
+    edges: &[GraphEdge],
+    levels: Option<&[Vec<Package>]>,
+) -> String {
+    let node = |package: &Package| -> String {
+        format!(
+            "{}[\"{} {}\"]",
+            mermaid_id(&package.name),
+            package.name,
+            package.version
+        )
+    };
+    let mut output: String = String::from("graph TD\n");
+    match levels {
+        Some(levels) => {
+            for (index, level) in levels.iter().enumerate() {
+                output.push_str(&format!("    subgraph level_{index} [\"level {index}\"]\n"));
+                for package in level {
+                    output.push_str(&format!("        {}\n", node(package)));
+                }
+                output.push_str("    end\n");
+            }
+        }
+        None => {
+            for package in packages {
+                output.push_str(&format!("    {}\n", node(package)));
+            }
+        }
+    }
+    for edge in edges {
+        let arrow: &str = match edge.kind {
+            DependencyKind::Normal => "-->",
+            DependencyKind::Dev => "-.->|dev|",
+            DependencyKind::Build => "==>|build|",
+        };
+        output.push_str(&format!(
+            "    {} {arrow} {}\n",
+            mermaid_id(&edge.from),
+            mermaid_id(&edge.to)
+        ));
+    }
+    output
+}
+
+/// Render the graph as JSON
+///
+/// # Arguments
+///
+/// - `&[Package]`: Packages shown as nodes
+/// - `&[GraphEdge]`: Dependency edges
+/// - `Option<&[Vec<Package>]>`: Publish levels added to each package
+///
+/// # Returns
+///
+/// - `String`: JSON document with `packages` and `edges`
+pub(crate) fn format_graph_json(
+    packages: &[Package],
+    edges: &[GraphEdge],
+    levels: Option<&[Vec<Package>]>,
+) -> String {
+    let level_of = |name: &str| -> Option<usize> {
+        levels?
+            .iter()
+            .position(|level: &Vec<Package>| level.iter().any(|p: &Package| p.name == name))
+    };
+    let nodes: Vec<String> = packages
+        .iter()
+        .map(|package: &Package| {
+            let level: String = level_of(&package.name)
+                .map(|level: usize| format!(", \"level\": {level}"))
+                .unwrap_or_default();
+            format!(
+                "    {{\"name\": \"{}\", \"version\": \"{}\", \"path\": \"{}\"{level}}}",
+                escape_json(&package.name),
+                escape_json(&package.version),
+                escape_json(&package.path.display().to_string())
+            )
+        })
+        .collect();
+    let edge_entries: Vec<String> = edges
+        .iter()
+        .map(|edge: &GraphEdge| {
+            format!(
+                "    {{\"from\": \"{}\", \"to\": \"{}\", \"kind\": \"{}\"}}",
+                escape_json(&edge.from),
+                escape_json(&edge.to),
+                edge.kind.as_str()
+            )
+        })
+        .collect();
+    let list = |entries: &[String]| -> String {
+        if entries.is_empty() {
+            "[]".to_string()
+        } else {
+            format!("[\n{}\n  ]", entries.join(",\n"))
+        }
+    };
+    format!(
+        "{{\n  \"packages\": {},\n  \"edges\": {}\n}}\n",
+        list(&nodes),
+        list(&edge_entries)
+    )
+}
+
+/// Render the workspace dependency graph
+///
+/// # Arguments
+///
+/// - `&[Package]`: Workspace packages
+/// - `&GraphOptions`: Graph options
+///
+/// # Returns
+///
+/// - `Result<String, GraphError>`: Rendered graph
+pub(crate) fn render_graph(
+    packages: &[Package],
+    options: &GraphOptions,
+) -> Result<String, GraphError> {
+    let mut packages: Vec<Package> = packages.to_vec();
+    packages.sort_by(|a: &Package, b: &Package| a.name.cmp(&b.name));
+    let mut edges: Vec<GraphEdge> = collect_graph_edges(&packages, &options.edge_kinds);
+    if let Some(name) = &options.invert {
+        packages = reverse_dependencies(&packages, &edges, name)?;
+        edges = collect_graph_edges(&packages, &options.edge_kinds);
+    }
+    let levels: Option<Vec<Vec<Package>>> = if options.levels {
+        Some(topological_levels(&packages)?)
+    } else {
+        None
+    };
+    Ok(match options.format {
+        GraphFormat::Dot => format_graph_dot(&packages, &edges, levels.as_deref()),
+        GraphFormat::Mermaid => format_graph_mermaid(&packages, &edges, levels.as_deref()),
+        GraphFormat::Json => format_graph_json(&packages, &edges, levels.as_deref()),
+    })
+}
+
+/// Execute graph command
+///
+/// # Arguments
+///
+/// - `&str`: Path to workspace root Cargo.toml
+/// - `&GraphOptions`: Graph options
+///
+/// # Returns
+///
+/// - `Result<String, GraphError>`: Rendered graph
+pub(crate) fn execute_graph(
+    manifest_path: &str,
+    options: &GraphOptions,
+) -> Result<String, GraphError> {
+    let packages: Vec<Package> = discover_packages(Path::new(manifest_path))?;
+    render_graph(&packages, options)
+}
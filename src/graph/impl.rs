@@ -0,0 +1,35 @@
+use crate::*;
+
+impl FromStr for GraphFormat {
+    type Err = GraphError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dot" | "graphviz" => Ok(Self::Dot),
+            "mermaid" => Ok(Self::Mermaid),
+            "json" => Ok(Self::Json),
+            _ => Err(GraphError::InvalidFormat(s.to_string())),
+        }
+    }
+}
+
+impl TryFrom<&Args> for GraphOptions {
+    type Error = GraphError;
+
+    fn try_from(args: &Args) -> Result<Self, Self::Error> {
+        Ok(Self {
+            format: args
+                .graph_format
+                .as_deref()
+                .map(GraphFormat::from_str)
+                .transpose()?
+                .unwrap_or_default(),
+            edge_kinds: match args.edges.as_deref().map(parse_edge_kinds).transpose()? {
+                Some(kinds) if !kinds.is_empty() => kinds,
+                _ => vec![DependencyKind::Normal],
+            },
+            levels: args.levels,
+            invert: args.invert.clone(),
+        })
+    }
+}
@@ -0,0 +1,25 @@
+use crate::*;
+
+/// Options for the graph command
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct GraphOptions {
+    /// Output format
+    pub format: GraphFormat,
+    /// Dependency kinds drawn as edges
+    pub edge_kinds: Vec<DependencyKind>,
+    /// Group packages by publish level
+    pub levels: bool,
+    /// Only show this package and the packages depending on it
+    pub invert: Option<String>,
+}
+
+/// Dependency edge between two workspace packages
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) struct GraphEdge {
+    /// Dependent package
+    pub from: String,
+    /// Depended-on package
+    pub to: String,
+    /// Section the dependency is declared in
+    pub kind: DependencyKind,
+}
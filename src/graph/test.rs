@@ -0,0 +1,138 @@
+use crate::*;
+
+fn make_package(name: &str, dependencies: &[(&str, DependencyKind)]) -> Package {
+    Package {
+        name: name.to_string(),
+        version: "0.1.0".to_string(),
+        path: PathBuf::from(name),
+        local_dependencies: dependencies
+            .iter()
+            .filter(|(_, kind): &&(&str, DependencyKind)| *kind != DependencyKind::Dev)
+            .map(|(dep, _): &(&str, DependencyKind)| dep.to_string())
+            .collect(),
+        dependencies: dependencies
+            .iter()
+            .map(|(dep, kind): &(&str, DependencyKind)| LocalDependency {
+                name: dep.to_string(),
+                kind: *kind,
+                has_version: true,
+                target: None,
+            })
+            .collect(),
+        publish_registries: None,
+    }
+}
+
+fn sample_packages() -> Vec<Package> {
+    vec![
+        make_package("web-app", &[("core", DependencyKind::Normal)]),
+        make_package(
+            "core",
+            &[
+                ("macros", DependencyKind::Build),
+                ("testkit", DependencyKind::Dev),
+            ],
+        ),
+        make_package("macros", &[]),
+        make_package("testkit", &[("macros", DependencyKind::Normal)]),
+    ]
+}
+
+#[test]
+fn test_parse_edge_kinds() {
+    assert_eq!(
+        parse_edge_kinds("normal, dev").unwrap(),
+        vec![DependencyKind::Normal, DependencyKind::Dev]
+    );
+    assert_eq!(parse_edge_kinds("all,dev").unwrap().len(), 3);
+    assert!(parse_edge_kinds("optional").is_err());
+}
+
+#[test]
+fn test_graph_options_reject_invalid_values() {
+    let parse = |extra: &[&str]| -> Result<GraphOptions, GraphError> {
+        let raw_args: Vec<String> = ["hyperlane-cli", "graph"]
+            .iter()
+            .chain(extra)
+            .map(|arg: &&str| arg.to_string())
+            .collect();
+        GraphOptions::try_from(&parse_args_from(&raw_args))
+    };
+    let options: GraphOptions = parse(&["--format", "mermaid", "-e", "dev"]).unwrap();
+    assert_eq!(options.format, GraphFormat::Mermaid);
+    assert_eq!(options.edge_kinds, vec![DependencyKind::Dev]);
+    let options: GraphOptions = parse(&[]).unwrap();
+    assert_eq!(options.format, GraphFormat::Dot);
+    assert_eq!(options.edge_kinds, vec![DependencyKind::Normal]);
+    assert!(matches!(
+        parse(&["--format", "svg"]),
+        Err(GraphError::InvalidFormat(format)) if format == "svg"
+    ));
+    assert!(matches!(
+        parse(&["--edges", "normal,optional"]),
+        Err(GraphError::InvalidEdgeKind(kind)) if kind == "optional"
+    ));
+}
+
+#[test]
+fn test_render_graph_formats() {
+    let packages: Vec<Package> = sample_packages();
+    let options: GraphOptions = GraphOptions {
+        format: GraphFormat::Dot,
+        edge_kinds: vec![DependencyKind::Normal, DependencyKind::Build],
+        levels: true,
+        invert: None,
+    };
+    let dot: String = render_graph(&packages, &options).unwrap();
+    assert!(dot.starts_with("digraph workspace {"));
+    assert!(dot.contains("subgraph cluster_level_0 {"));
+    assert!(dot.contains("\"core\" -> \"macros\" [style=dotted, label=\"build\"];"));
+    assert!(dot.contains("\"web-app\" -> \"core\";"));
+    assert!(!dot.contains("label=\"dev\""));
+    let mermaid: String = render_graph(
+        &packages,
+        &GraphOptions {
+            format: GraphFormat::Mermaid,
+            levels: false,
+            ..options.clone()
+        },
+    )
+    .unwrap();
+    assert!(mermaid.contains("    web_app[\"web-app 0.1.0\"]\n"));
+    assert!(mermaid.contains("    web_app --> core\n"));
+    assert!(mermaid.contains("    core ==>|build| macros\n"));
+    let json: String = render_graph(
+        &packages,
+        &GraphOptions {
+            format: GraphFormat::Json,
+            ..options
+        },
+    )
+    .unwrap();
+    assert!(json.contains(
+        "{\"name\": \"macros\", \"version\": \"0.1.0\", \"path\": \"macros\", \"level\": 0}"
+    ));
+    assert!(json.contains("{\"from\": \"core\", \"to\": \"macros\", \"kind\": \"build\"}"));
+}
+
+#[test]
+fn test_render_graph_invert() {
+    let packages: Vec<Package> = sample_packages();
+    let options: GraphOptions = GraphOptions {
+        format: GraphFormat::Json,
+        edge_kinds: vec![DependencyKind::Normal, DependencyKind::Dev],
+        levels: false,
+        invert: Some("testkit".to_string()),
+    };
+    let json: String = render_graph(&packages, &options).unwrap();
+    assert!(json.contains("\"name\": \"core\""));
+    assert!(json.contains("\"name\": \"web-app\""));
+    assert!(json.contains("\"name\": \"testkit\""));
+    assert!(!json.contains("\"name\": \"macros\""));
+    assert!(json.contains("{\"from\": \"core\", \"to\": \"testkit\", \"kind\": \"dev\"}"));
+    let missing: GraphOptions = GraphOptions {
+        invert: Some("missing".to_string()),
+        ..options
+    };
+    assert!(render_graph(&packages, &missing).is_err());
+}
@@ -4,9 +4,16 @@ pub(crate) fn print_help() {
     println!();
     println!("Commands:");
     println!("  bump      Bump version in Cargo.toml");
-    println!("  fmt       Format Rust code using cargo fmt");
+    println!("  fmt       Format Rust code with rustfmt, derive sorting and optional clippy");
     println!("  watch     Watch files and run cargo run using cargo-watch");
     println!("  publish   Publish packages in monorepo with topological ordering");
+    println!("  release   Bump, tag and publish a release of the workspace");
+    println!("  exec      Run a command in every package in dependency order (alias: foreach)");
+    println!("  info      Show packages, versions, dependencies and release tags");
+    println!("  graph     Print the workspace dependency graph (DOT, Mermaid or JSON)");
+    println!(
+        "  lint      Summarise clippy diagnostics or check the module layout and docs (clippy|layout|docs)"
+    );
     println!("  new       Create a new project from template");
     println!(
         "  template  Generate template components (controller|domain|exception|mapper|model|repository|service|utils|view)"
@@ -31,13 +38,134 @@ pub(crate) fn print_help() {
         "  --rc            Add or bump rc version (0.1.0 -> 0.1.0-rc, 0.1.0-beta.1 -> 0.1.0-rc.1)"
     );
     println!("  --release       Remove pre-release identifier (0.1.0-alpha -> 0.1.0)");
+    println!(
+        "  --workspace     Bump every workspace member and update local dependency requirements"
+    );
     println!("  --manifest-path <PATH>  Path to Cargo.toml [default: Cargo.toml]");
     println!();
+    println!("Release Options:");
+    println!(
+        "  --patch|--minor|--major|--alpha|--beta|--rc|--release  Bump type [default: --patch]"
+    );
+    println!("  -y, --yes               Answer yes at the publish, rollback and push checkpoints");
+    println!("  --manifest-path <PATH>  Path to workspace Cargo.toml [default: Cargo.toml]");
+    println!("  Publish options apply to the publish step.");
+    println!();
+    println!("Exec Options:");
+    println!("  -- <COMMAND> [ARGS]...  Command run in each package directory");
+    println!("  -p, --package <NAME>    Run only in the given package (repeatable)");
+    println!("  --exclude <NAME>        Skip the given package (repeatable)");
+    println!("  --changed               Run only in packages changed since their last release tag");
+    println!("  --changed-since <REF>   Run only in packages changed since a git reference");
+    println!("  --with-dependents       Also run in dependents of changed packages");
+    println!(
+        "  -j, --jobs <N>          Run packages of a dependency level in parallel [default: 1]"
+    );
+    println!("  --keep-going            Continue after a package fails instead of stopping");
+    println!();
+    println!("Info Options:");
+    println!("  --json                  Print the package list as JSON");
+    println!("  --manifest-path <PATH>  Path to workspace Cargo.toml [default: Cargo.toml]");
+    println!();
+    println!("Graph Options:");
+    println!("  --format <FMT>          Output format: dot, mermaid or json [default: dot]");
+    println!("  -e, --edges <KINDS>     Edge kinds: normal, dev, build or all [default: normal]");
+    println!("  --levels                Group packages by publish level");
+    println!("  -i, --invert <NAME>     Only show the package and its reverse dependencies");
+    println!("  --manifest-path <PATH>  Path to workspace Cargo.toml [default: Cargo.toml]");
+    println!();
     println!("Fmt Options:");
-    println!("  --check         Check formatting without making changes");
+    println!("  --check         Check formatting without making changes, unsorted derives");
+    println!("                  and manifests are reported with a diff and exit with code 2");
+    println!("  --diff          Print a diff of the derive and manifest changes that were written");
+    println!("  --changed       Only format files changed compared to HEAD");
+    println!("  --changed-since <REF>   Only format files changed compared to REF");
+    println!("  <PATHS>...      Only format these files, directories, packages or Cargo.toml");
+    println!(
+        "  Files unchanged since the last run are skipped using target/hyperlane-cli/fmt-cache"
+    );
+    println!("  Formats src, tests, examples, benches, build scripts and custom target paths,");
+    println!("  skipping .gitignore matches and the exclude patterns in");
+    println!("  [workspace.metadata.hyperlane-cli.fmt] exclude = [\"src/generated/**\"]");
+    println!("  Cargo.toml files get sorted dependency tables, canonical [package] key order,");
+    println!("  normalised inline tables and `workspace = true` for dependencies matching");
+    println!("  [workspace.dependencies]; comments are kept and blank lines separate groups");
+    println!("  --clippy        Run cargo clippy --fix after formatting");
+    println!("  --no-clippy     Skip clippy even if enabled in the configuration");
+    println!("  --clippy-only   Only run clippy, without --fix when combined with --check");
     println!("  --manifest-path <PATH>  Path to Cargo.toml");
     println!();
+    println!("Lint Options:");
+    println!("  clippy                  Run clippy and group its diagnostics by lint [default]");
+    println!("  layout                  Check module directories under src against the layout");
+    println!("                          generated by template: items in the matching const, enum,");
+    println!("                          fn, impl, static or struct file and mod.rs declarations");
+    println!("  docs                    Check that # Arguments and # Returns doc sections list");
+    println!("                          the parameter and return types of each function");
+    println!("  --fix                   Move misplaced items, create missing submodules and");
+    println!("                          regenerate mod.rs of modules holding only layout files;");
+    println!("                          for docs, rewrite the sections with skeleton bullets");
+    println!("  -p, --package <NAME>    Lint only the given package (repeatable)");
+    println!("  --manifest-path <PATH>  Path to workspace Cargo.toml [default: Cargo.toml]");
+    println!();
     println!("Publish Options:");
     println!("  --manifest-path <PATH>  Path to workspace Cargo.toml [default: Cargo.toml]");
-    println!("  --max-retries <N>       Maximum retry attempts per package [default: 3]");
+    println!(
+        "  --max-retries <N>       Maximum retry attempts per package for transient failures [default: 3]"
+    );
+    println!("  --retry-max-delay <SECS>  Upper bound of the retry backoff [default: 60]");
+    println!("  --retry-jitter <MS>     Maximum random jitter added to each retry [default: 1000]");
+    println!("  -p, --package <NAME>    Publish only the given package (repeatable)");
+    println!("  --exclude <NAME>        Skip the given package (repeatable)");
+    println!(
+        "  --from <NAME>           Resume publishing from the given package in topological order"
+    );
+    println!(
+        "  -j, --jobs <N>          Maximum concurrent publishes per dependency level [default: 1]"
+    );
+    println!(
+        "  --index-url <URL>       Registry index polled after each publish [default: crates.io sparse index]"
+    );
+    println!(
+        "  --index-timeout <SECS>  Seconds to wait for a version to appear in the index, 0 disables [default: 300]"
+    );
+    println!(
+        "  --changed               Publish only packages changed since their last <name>-v<version> tag"
+    );
+    println!(
+        "  --changed-since <REF>   Publish only packages changed since the given git reference"
+    );
+    println!("  --with-dependents       Also publish dependents of changed packages");
+    println!("  --registry <NAME>       Registry to publish to");
+    println!("  --index <URL>           Registry index to publish to");
+    println!("  -F, --features <LIST>   Features to activate when verifying");
+    println!("  --no-verify             Skip the verification build");
+    println!("  --target <TRIPLE>       Target to verify for");
+    println!("  --report <PATH>         Write a publish report (.json, .xml or .md)");
+    println!("  --report-format <FMT>   Report format: json, junit or markdown");
+    println!("  -- <ARGS>...            Extra arguments passed to cargo publish");
+    println!();
+    println!("  Per-package settings (registry, index, features, no-verify, target, args)");
+    println!("  can be set in [package.metadata.hyperlane-cli.publish].");
+    println!();
+    println!("Hooks:");
+    println!("  Shell commands in [workspace.metadata.hyperlane-cli.hooks] or");
+    println!("  [package.metadata.hyperlane-cli.hooks], keyed pre-<command> / post-<command>");
+    println!("  for bump, publish, publish-package, template, new and fmt. A failing pre hook");
+    println!("  aborts the command. Hooks receive HYPERLANE_HOOK, HYPERLANE_COMMAND,");
+    println!("  HYPERLANE_PACKAGE, HYPERLANE_NEW_VERSION, HYPERLANE_TARGET_DIR and");
+    println!("  HYPERLANE_RESULT (post hooks).");
+    println!();
+    println!("Derive Ordering:");
+    println!("  Configured in [workspace.metadata.hyperlane-cli.fmt.derive] or the package");
+    println!("  table. preset = \"alphabetical\" (default) or \"std\" (std traits, then serde,");
+    println!("  then the rest). groups = [[\"Debug\", ...], [\"serde::*\"]] replaces the preset");
+    println!("  groups and order = [...] adds a group in front. Used by fmt and template.");
+    println!();
+    println!("Clippy:");
+    println!("  Configured in [workspace.metadata.hyperlane-cli.fmt.clippy] or the package");
+    println!("  table and used by fmt and lint. enabled = true runs clippy after fmt,");
+    println!("  install = true installs it with rustup when missing. all-targets (default");
+    println!("  true), all-features, features = [...], args = [\"--target\", ...] and");
+    println!("  deny / warn / allow = [\"clippy::lint\", ...] shape the invocation.");
 }
@@ -0,0 +1,20 @@
+/// Table inside the tool metadata that holds the hook commands
+pub(crate) const HOOKS_METADATA_KEY: &str = "hooks";
+
+/// Environment variable with the name of the running hook
+pub(crate) const HOOK_ENV_HOOK: &str = "HYPERLANE_HOOK";
+
+/// Environment variable with the command the hook belongs to
+pub(crate) const HOOK_ENV_COMMAND: &str = "HYPERLANE_COMMAND";
+
+/// Environment variable with the package the hook runs for
+pub(crate) const HOOK_ENV_PACKAGE: &str = "HYPERLANE_PACKAGE";
+
+/// Environment variable with the version of the package
+pub(crate) const HOOK_ENV_NEW_VERSION: &str = "HYPERLANE_NEW_VERSION";
+
+/// Environment variable with the directory the command works on
+pub(crate) const HOOK_ENV_TARGET_DIR: &str = "HYPERLANE_TARGET_DIR";
+
+/// Environment variable with the outcome of the command in post hooks
+pub(crate) const HOOK_ENV_RESULT: &str = "HYPERLANE_RESULT";
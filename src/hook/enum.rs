@@ -0,0 +1,27 @@
+use crate::*;
+
+/// Error types for lifecycle hooks
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum HookError {
+    /// Hook command exited with an error
+    #[error("{0} hook `{1}` failed with {2}")]
+    Failed(String, String, ExitStatus),
+    /// Hook configuration is not a string or a list of strings
+    #[error("Invalid hook configuration for {0}")]
+    InvalidConfig(String),
+    /// Workspace manifest could not be read
+    #[error(transparent)]
+    WorkspaceError(#[from] WorkspaceError),
+    /// IO error
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+/// Point in the command lifecycle at which a hook runs
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum HookStage {
+    /// Before the command, a failure aborts the command
+    Pre,
+    /// After the command, with its result
+    Post,
+}
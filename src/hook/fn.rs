@@ -0,0 +1,204 @@
+use crate::*;
+
+/// Read hook commands from a metadata table
+///
+/// # Arguments
+///
+/// - `Option<&toml::Value>`: `[*.metadata.hyperlane-cli.hooks]` table if present
+/// - `&mut HashMap<String, Vec<String>>`: Hooks to insert into, existing entries are replaced
+///
+/// # Returns
+///
+/// - `Result<(), HookError>`: Error if a hook is not a string or list of strings
+fn collect_hooks(
+    table: Option<&toml::Value>,
+    hooks: &mut HashMap<String, Vec<String>>,
+) -> Result<(), HookError> {
+    let Some(table) = table.and_then(|t: &toml::Value| t.as_table()) else {
+        return Ok(());
+    };
+    for (name, value) in table {
+        let commands: Vec<String> = match value {
+            toml::Value::String(command) => vec![command.clone()],
+            toml::Value::Array(commands) => commands
+                .iter()
+                .map(|c: &toml::Value| c.as_str().map(|s: &str| s.to_string()))
+                .collect::<Option<Vec<String>>>()
+                .ok_or_else(|| HookError::InvalidConfig(name.clone()))?,
+            _ => return Err(HookError::InvalidConfig(name.clone())),
+        };
+        hooks.insert(name.clone(), commands);
+    }
+    Ok(())
+}
+
+/// Load the hooks configured in a manifest
+///
+/// Hooks live in `[workspace.metadata.hyperlane-cli.hooks]` and
+/// `[package.metadata.hyperlane-cli.hooks]`, package entries win. Each key
+/// such as `pre-bump` or `post-publish-package` maps to a shell command or
+/// a list of commands. A missing manifest yields no hooks.
+///
+/// # Arguments
+///
+/// - `&Path`: Path to Cargo.toml
+///
+/// # Returns
+///
+/// - `Result<HookConfig, HookError>`: Configured hooks
+pub(crate) fn load_hook_config(manifest_path: &Path) -> Result<HookConfig, HookError> {
+    let mut config: HookConfig = HookConfig {
+        dir: manifest_dir(manifest_path),
+        hooks: HashMap::new(),
+    };
+    if !manifest_path.is_file() {
+        return Ok(config);
+    }
+    let doc: toml::Value = read_manifest(manifest_path)?;
+    for section in ["workspace", "package"] {
+        let table: Option<&toml::Value> = doc
+            .get(section)
+            .and_then(|s: &toml::Value| s.get("metadata"))
+            .and_then(|m: &toml::Value| m.get(CONFIG_METADATA_KEY))
+            .and_then(|c: &toml::Value| c.get(HOOKS_METADATA_KEY));
+        collect_hooks(table, &mut config.hooks)?;
+    }
+    Ok(config)
+}
+
+/// Run the hook commands of a lifecycle stage
+///
+/// Commands run through the shell in the manifest directory with the
+/// context exported as `HYPERLANE_*` environment variables.
+///
+/// # Arguments
+///
+/// - `&HookConfig`: Configured hooks
+/// - `HookStage`: Stage to run
+/// - `&HookContext`: Context of the command
+///
+/// # Returns
+///
+/// - `Result<(), HookError>`: Error of the first failing command
+pub(crate) async fn run_hooks(
+    config: &HookConfig,
+    stage: HookStage,
+    context: &HookContext,
+) -> Result<(), HookError> {
+    let name: String = format!("{}-{}", stage.as_str(), context.command);
+    let Some(commands) = config.hooks.get(&name) else {
+        return Ok(());
+    };
+    let (shell, shell_flag): (&str, &str) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    for command in commands {
+        println!("Running {name} hook: {command}");
+        let status: ExitStatus = Command::new(shell)
+            .arg(shell_flag)
+            .arg(command)
+            .current_dir(&config.dir)
+            .env(HOOK_ENV_HOOK, &name)
+            .envs(context.env_vars())
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .await?;
+        if !status.success() {
+            return Err(HookError::Failed(name, command.clone(), status));
+        }
+    }
+    Ok(())
+}
+
+/// Run the post hooks of a command and report failures as warnings
+///
+/// # Arguments
+///
+/// - `&HookConfig`: Configured hooks
+/// - `&HookContext`: Context of the command
+/// - `bool`: Whether the command succeeded
+pub(crate) async fn run_post_hooks(config: &HookConfig, context: &HookContext, success: bool) {
+    let context: HookContext = HookContext {
+        success: Some(success),
+        ..context.clone()
+    };
+    if let Err(error) = run_hooks(config, HookStage::Post, &context).await {
+        eprintln!("Warning: {error}");
+    }
+}
+
+/// Run a command between its pre and post hooks
+///
+/// The hooks are read from the manifest, a missing manifest runs no hooks.
+/// A failing pre hook aborts before the command runs. The post hooks are
+/// told whether the command returned `Ok` and only warn when they fail.
+///
+/// # Arguments
+///
+/// - `HookContext`: Context of the command, `target_dir` defaults to the manifest directory
+/// - `&Path`: Path to Cargo.toml
+/// - `F`: Command to run
+///
+/// # Returns
+///
+/// - `Result<T, E>`: Result of the command, or the error of loading or running the pre hooks
+pub(crate) async fn run_with_hooks<T, E, F>(
+    context: HookContext,
+    manifest_path: &Path,
+    command: F,
+) -> Result<T, E>
+where
+    F: Future<Output = Result<T, E>>,
+    E: From<HookError>,
+{
+    run_with_hooks_outcome(
+        context,
+        manifest_path,
+        command,
+        |_: &T, _: &mut HookContext| true,
+    )
+    .await
+}
+
+/// Run a command between its pre and post hooks, inspecting its result
+///
+/// Like `run_with_hooks`, but `outcome` decides whether an `Ok` result
+/// counts as success and can add it to the context of the post hooks.
+///
+/// # Arguments
+///
+/// - `HookContext`: Context of the command, `target_dir` defaults to the manifest directory
+/// - `&Path`: Path to Cargo.toml
+/// - `F`: Command to run
+/// - `O`: Outcome of an `Ok` result, returns whether the command succeeded
+///
+/// # Returns
+///
+/// - `Result<T, E>`: Result of the command, or the error of loading or running the pre hooks
+pub(crate) async fn run_with_hooks_outcome<T, E, F, O>(
+    mut context: HookContext,
+    manifest_path: &Path,
+    command: F,
+    outcome: O,
+) -> Result<T, E>
+where
+    F: Future<Output = Result<T, E>>,
+    E: From<HookError>,
+    O: FnOnce(&T, &mut HookContext) -> bool,
+{
+    let hooks: HookConfig = load_hook_config(manifest_path)?;
+    if context.target_dir.is_none() {
+        context.target_dir = Some(hooks.dir.clone());
+    }
+    run_hooks(&hooks, HookStage::Pre, &context).await?;
+    let result: Result<T, E> = command.await;
+    let success: bool = match &result {
+        Ok(value) => outcome(value, &mut context),
+        Err(_) => false,
+    };
+    run_post_hooks(&hooks, &context, success).await;
+    result
+}
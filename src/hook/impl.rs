@@ -0,0 +1,56 @@
+use crate::*;
+
+impl HookStage {
+    /// Get the prefix of hook names for the stage
+    ///
+    /// # Returns
+    ///
+    /// - `&'static str`: `pre` or `post`
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pre => "pre",
+            Self::Post => "post",
+        }
+    }
+}
+
+impl HookContext {
+    /// Create a hook context for a command
+    ///
+    /// # Arguments
+    ///
+    /// - `&str`: Command name
+    ///
+    /// # Returns
+    ///
+    /// - `Self`: Context without package, version or result
+    pub(crate) fn new(command: &str) -> Self {
+        Self {
+            command: command.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Build the environment variables of the context
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<(&'static str, String)>`: Variable names and values that are set
+    pub(crate) fn env_vars(&self) -> Vec<(&'static str, String)> {
+        let mut vars: Vec<(&'static str, String)> = vec![(HOOK_ENV_COMMAND, self.command.clone())];
+        if let Some(package) = &self.package {
+            vars.push((HOOK_ENV_PACKAGE, package.clone()));
+        }
+        if let Some(new_version) = &self.new_version {
+            vars.push((HOOK_ENV_NEW_VERSION, new_version.clone()));
+        }
+        if let Some(target_dir) = &self.target_dir {
+            vars.push((HOOK_ENV_TARGET_DIR, target_dir.display().to_string()));
+        }
+        if let Some(success) = self.success {
+            let result: &str = if success { "success" } else { "failure" };
+            vars.push((HOOK_ENV_RESULT, result.to_string()));
+        }
+        vars
+    }
+}
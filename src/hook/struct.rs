@@ -0,0 +1,25 @@
+use crate::*;
+
+/// Hook commands configured for a workspace
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct HookConfig {
+    /// Directory the hook commands run in
+    pub dir: PathBuf,
+    /// Shell commands keyed by hook name such as `pre-bump`
+    pub hooks: HashMap<String, Vec<String>>,
+}
+
+/// Context passed to hook commands as environment variables
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct HookContext {
+    /// Command the hook belongs to, such as `bump` or `publish-package`
+    pub command: String,
+    /// Package the command works on
+    pub package: Option<String>,
+    /// New version of the package
+    pub new_version: Option<String>,
+    /// Directory the command works on
+    pub target_dir: Option<PathBuf>,
+    /// Whether the command succeeded, set for post hooks
+    pub success: Option<bool>,
+}
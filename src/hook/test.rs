@@ -0,0 +1,117 @@
+use crate::*;
+
+#[test]
+fn test_load_hook_config() {
+    let root: PathBuf = PathBuf::from("./tmp/test_hook_config");
+    let _ = std::fs::remove_dir_all(&root);
+    create_dir_all(&root).unwrap();
+    write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = []\n\n[workspace.metadata.hyperlane-cli.hooks]\npre-publish = \"cargo test\"\npost-bump = [\"echo one\", \"echo two\"]\n\n[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[package.metadata.hyperlane-cli.hooks]\npre-publish = \"cargo test --all-features\"\n",
+    )
+    .unwrap();
+    let config: HookConfig = load_hook_config(&root.join("Cargo.toml")).unwrap();
+    assert_eq!(config.dir, root);
+    assert_eq!(
+        config.hooks.get("pre-publish"),
+        Some(&vec!["cargo test --all-features".to_string()])
+    );
+    assert_eq!(
+        config.hooks.get("post-bump"),
+        Some(&vec!["echo one".to_string(), "echo two".to_string()])
+    );
+    let missing: HookConfig = load_hook_config(&root.join("missing/Cargo.toml")).unwrap();
+    assert!(missing.hooks.is_empty());
+    write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[package.metadata.hyperlane-cli.hooks]\npre-fmt = 1\n",
+    )
+    .unwrap();
+    assert!(matches!(
+        load_hook_config(&root.join("Cargo.toml")),
+        Err(HookError::InvalidConfig(name)) if name == "pre-fmt"
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_hooks_with_context() {
+    let root: PathBuf = PathBuf::from("./tmp/test_hook_run");
+    let _ = std::fs::remove_dir_all(&root);
+    create_dir_all(&root).unwrap();
+    let mut hooks: HashMap<String, Vec<String>> = HashMap::new();
+    hooks.insert(
+        "post-bump".to_string(),
+        vec![
+            "echo \"$HYPERLANE_HOOK $HYPERLANE_COMMAND $HYPERLANE_PACKAGE $HYPERLANE_NEW_VERSION $HYPERLANE_RESULT\" > hook.txt"
+                .to_string(),
+        ],
+    );
+    hooks.insert("pre-bump".to_string(), vec!["exit 3".to_string()]);
+    let config: HookConfig = HookConfig {
+        dir: root.clone(),
+        hooks,
+    };
+    let context: HookContext = HookContext {
+        package: Some("core".to_string()),
+        new_version: Some("0.2.0".to_string()),
+        ..HookContext::new("bump")
+    };
+    let rt: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let result: Result<(), HookError> = run_hooks(&config, HookStage::Pre, &context).await;
+        assert!(matches!(result, Err(HookError::Failed(name, _, _)) if name == "pre-bump"));
+        run_post_hooks(&config, &context, true).await;
+        assert!(
+            run_hooks(&config, HookStage::Pre, &HookContext::new("fmt"))
+                .await
+                .is_ok()
+        );
+    });
+    assert_eq!(
+        read_to_string(root.join("hook.txt")).unwrap(),
+        "post-bump bump core 0.2.0 success\n"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_with_hooks() {
+    let root: PathBuf = PathBuf::from("./tmp/test_hook_with");
+    let _ = std::fs::remove_dir_all(&root);
+    create_dir_all(&root).unwrap();
+    write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[package.metadata.hyperlane-cli.hooks]\npre-publish = \"exit 2\"\npost-bump = \"echo \\\"$HYPERLANE_PACKAGE $HYPERLANE_NEW_VERSION $HYPERLANE_RESULT\\\" > hook.txt\"\n",
+    )
+    .unwrap();
+    let manifest_path: PathBuf = root.join("Cargo.toml");
+    let rt: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let mut ran: bool = false;
+        let result: Result<(), HookError> =
+            run_with_hooks(HookContext::new("publish"), &manifest_path, async {
+                ran = true;
+                Ok(())
+            })
+            .await;
+        assert!(matches!(result, Err(HookError::Failed(name, _, _)) if name == "pre-publish"));
+        assert!(!ran);
+        let bumped: Vec<(String, String)> = vec![("app".to_string(), "0.2.0".to_string())];
+        let result: Result<Vec<(String, String)>, HookError> = run_with_hooks_outcome(
+            HookContext::new("bump"),
+            &manifest_path,
+            async { Ok(bumped.clone()) },
+            |bumped: &Vec<(String, String)>, context: &mut HookContext| {
+                add_bumped_versions(bumped, context);
+                false
+            },
+        )
+        .await;
+        assert_eq!(result.unwrap(), bumped);
+    });
+    assert_eq!(
+        read_to_string(root.join("hook.txt")).unwrap(),
+        "app 0.2.0 failure\n"
+    );
+}
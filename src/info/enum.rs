@@ -0,0 +1,12 @@
+use crate::*;
+
+/// Error types for info operation
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum InfoError {
+    /// Package discovery failed
+    #[error(transparent)]
+    PublishError(#[from] PublishError),
+    /// Package manifest could not be read
+    #[error(transparent)]
+    WorkspaceError(#[from] WorkspaceError),
+}
@@ -0,0 +1,243 @@
+use crate::*;
+
+/// List the features declared by a package
+///
+/// # Arguments
+///
+/// - `&toml::Value`: Parsed package manifest
+///
+/// # Returns
+///
+/// - `Vec<String>`: Sorted feature names
+fn package_features(doc: &toml::Value) -> Vec<String> {
+    let mut features: Vec<String> = doc
+        .get("features")
+        .and_then(|f: &toml::Value| f.as_table())
+        .map(|table: &toml::Table| table.keys().cloned().collect())
+        .unwrap_or_default();
+    features.sort();
+    features
+}
+
+/// List the binary targets of a package
+///
+/// Includes `[[bin]]` entries and, unless `autobins = false`, the
+/// `src/main.rs`, `src/bin/*.rs` and `src/bin/*/main.rs` targets.
+///
+/// # Arguments
+///
+/// - `&toml::Value`: Parsed package manifest
+/// - `&Package`: Package the manifest belongs to
+///
+/// # Returns
+///
+/// - `Vec<String>`: Sorted binary names
+fn package_binaries(doc: &toml::Value, package: &Package) -> Vec<String> {
+    let mut binaries: Vec<String> = doc
+        .get("bin")
+        .and_then(|b: &toml::Value| b.as_array())
+        .map(|bins: &Vec<toml::Value>| {
+            bins.iter()
+                .filter_map(|bin: &toml::Value| bin.get("name")?.as_str())
+                .map(|name: &str| name.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    let autobins: bool = doc
+        .get("package")
+        .and_then(|p: &toml::Value| p.get("autobins"))
+        .and_then(|a: &toml::Value| a.as_bool())
+        .unwrap_or(true);
+    if autobins {
+        if package.path.join("src/main.rs").is_file() {
+            binaries.push(package.name.clone());
+        }
+        if let Ok(entries) = std::fs::read_dir(package.path.join("src/bin")) {
+            for entry in entries.flatten() {
+                let path: PathBuf = entry.path();
+                let name: Option<String> = if path.is_dir() && path.join("main.rs").is_file() {
+                    path.file_name()
+                        .map(|n: &std::ffi::OsStr| n.to_string_lossy().to_string())
+                } else if path
+                    .extension()
+                    .is_some_and(|e: &std::ffi::OsStr| e == "rs")
+                {
+                    path.file_stem()
+                        .map(|n: &std::ffi::OsStr| n.to_string_lossy().to_string())
+                } else {
+                    None
+                };
+                binaries.extend(name);
+            }
+        }
+    }
+    binaries.sort();
+    binaries.dedup();
+    binaries
+}
+
+/// Collect the summary of every package
+///
+/// # Arguments
+///
+/// - `&[Package]`: Workspace packages
+///
+/// # Returns
+///
+/// - `Result<Vec<PackageInfo>, InfoError>`: Package summaries in discovery order
+pub(crate) async fn collect_package_info(
+    packages: &[Package],
+) -> Result<Vec<PackageInfo>, InfoError> {
+    let mut infos: Vec<PackageInfo> = Vec::new();
+    for package in packages {
+        let doc: toml::Value = read_manifest(&package.path.join("Cargo.toml"))?;
+        let mut dependencies: Vec<String> = package
+            .dependencies
+            .iter()
+            .map(|dependency: &LocalDependency| dependency.name.clone())
+            .filter(|name: &String| packages.iter().any(|p: &Package| &p.name == name))
+            .collect();
+        dependencies.sort();
+        dependencies.dedup();
+        let mut dependents: Vec<String> = packages
+            .iter()
+            .filter(|p: &&Package| {
+                p.dependencies
+                    .iter()
+                    .any(|dependency: &LocalDependency| dependency.name == package.name)
+            })
+            .map(|p: &Package| p.name.clone())
+            .collect();
+        dependents.sort();
+        let tag: String = release_tag_name(&package.name, &package.version);
+        let tagged: Option<bool> = tag_exists(&package.path, &tag).await.ok();
+        infos.push(PackageInfo {
+            name: package.name.clone(),
+            version: package.version.clone(),
+            path: package.path.display().to_string(),
+            publish_registries: package.publish_registries.clone(),
+            dependencies,
+            dependents,
+            features: package_features(&doc),
+            binaries: package_binaries(&doc, package),
+            tag,
+            tagged,
+        });
+    }
+    Ok(infos)
+}
+
+/// Format package summaries as text
+///
+/// # Arguments
+///
+/// - `&[PackageInfo]`: Package summaries
+///
+/// # Returns
+///
+/// - `String`: One block per package
+pub(crate) fn format_info_text(infos: &[PackageInfo]) -> String {
+    let list = |values: &[String]| -> String {
+        if values.is_empty() {
+            "-".to_string()
+        } else {
+            values.join(", ")
+        }
+    };
+    let mut output: String = String::new();
+    for (index, info) in infos.iter().enumerate() {
+        if index > 0 {
+            output.push('\n');
+        }
+        let publish: String = match &info.publish_registries {
+            None => "yes".to_string(),
+            Some(registries) if registries.is_empty() => "no".to_string(),
+            Some(registries) => format!("only to {}", registries.join(", ")),
+        };
+        let tagged: String = match info.tagged {
+            Some(true) => format!("yes ({})", info.tag),
+            Some(false) => format!("no ({})", info.tag),
+            None => "unknown (not a git repository)".to_string(),
+        };
+        output.push_str(&format!("{} {}\n", info.name, info.version));
+        output.push_str(&format!("  path:         {}\n", info.path));
+        output.push_str(&format!("  publish:      {publish}\n"));
+        output.push_str(&format!("  dependencies: {}\n", list(&info.dependencies)));
+        output.push_str(&format!("  dependents:   {}\n", list(&info.dependents)));
+        output.push_str(&format!("  features:     {}\n", list(&info.features)));
+        output.push_str(&format!("  binaries:     {}\n", list(&info.binaries)));
+        output.push_str(&format!("  tagged:       {tagged}\n"));
+    }
+    output
+}
+
+/// Format package summaries as JSON
+///
+/// # Arguments
+///
+/// - `&[PackageInfo]`: Package summaries
+///
+/// # Returns
+///
+/// - `String`: JSON document with a `packages` array
+pub(crate) fn format_info_json(infos: &[PackageInfo]) -> String {
+    let array = |values: &[String]| -> String {
+        let items: Vec<String> = values
+            .iter()
+            .map(|value: &String| format!("\"{}\"", escape_json(value)))
+            .collect();
+        format!("[{}]", items.join(", "))
+    };
+    let entries: Vec<String> = infos
+        .iter()
+        .map(|info: &PackageInfo| {
+            let publish: String = match &info.publish_registries {
+                None => "true".to_string(),
+                Some(registries) if registries.is_empty() => "false".to_string(),
+                Some(registries) => array(registries),
+            };
+            let tagged: &str = match info.tagged {
+                Some(true) => "true",
+                Some(false) => "false",
+                None => "null",
+            };
+            let fields: Vec<String> = vec![
+                format!("\"name\": \"{}\"", escape_json(&info.name)),
+                format!("\"version\": \"{}\"", escape_json(&info.version)),
+                format!("\"path\": \"{}\"", escape_json(&info.path)),
+                format!("\"publish\": {publish}"),
+                format!("\"dependencies\": {}", array(&info.dependencies)),
+                format!("\"dependents\": {}", array(&info.dependents)),
+                format!("\"features\": {}", array(&info.features)),
+                format!("\"binaries\": {}", array(&info.binaries)),
+                format!("\"tag\": \"{}\"", escape_json(&info.tag)),
+                format!("\"tagged\": {tagged}"),
+            ];
+            format!("    {{\n      {}\n    }}", fields.join(",\n      "))
+        })
+        .collect();
+    if entries.is_empty() {
+        return "{\n  \"packages\": []\n}\n".to_string();
+    }
+    format!("{{\n  \"packages\": [\n{}\n  ]\n}}\n", entries.join(",\n"))
+}
+
+/// Execute info command
+///
+/// # Arguments
+///
+/// - `&str`: Path to workspace root Cargo.toml
+/// - `bool`: Print JSON instead of text
+///
+/// # Returns
+///
+/// - `Result<String, InfoError>`: Formatted package summaries
+pub(crate) async fn execute_info(manifest_path: &str, json: bool) -> Result<String, InfoError> {
+    let packages: Vec<Package> = discover_packages(Path::new(manifest_path))?;
+    let infos: Vec<PackageInfo> = collect_package_info(&packages).await?;
+    Ok(if json {
+        format_info_json(&infos)
+    } else {
+        format_info_text(&infos)
+    })
+}
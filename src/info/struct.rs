@@ -0,0 +1,24 @@
+/// Summary of a workspace package
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct PackageInfo {
+    /// Package name
+    pub name: String,
+    /// Package version
+    pub version: String,
+    /// Package directory
+    pub path: String,
+    /// Registries the package may be published to, None for any
+    pub publish_registries: Option<Vec<String>>,
+    /// Local packages this package depends on
+    pub dependencies: Vec<String>,
+    /// Local packages depending on this package
+    pub dependents: Vec<String>,
+    /// Declared features
+    pub features: Vec<String>,
+    /// Binary targets
+    pub binaries: Vec<String>,
+    /// Release tag of the current version
+    pub tag: String,
+    /// Whether the release tag exists, None outside a git repository
+    pub tagged: Option<bool>,
+}
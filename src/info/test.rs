@@ -0,0 +1,47 @@
+use crate::*;
+
+#[test]
+fn test_collect_package_info() {
+    let root: PathBuf = PathBuf::from("./tmp/test_info");
+    let _ = std::fs::remove_dir_all(&root);
+    create_dir_all(root.join("app/src/bin/tool")).unwrap();
+    create_dir_all(root.join("core/src")).unwrap();
+    write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"app\", \"core\"]\n",
+    )
+    .unwrap();
+    write(
+        root.join("app/Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.2.0\"\npublish = false\n\n[features]\ndefault = []\ntls = []\n\n[[bin]]\nname = \"admin\"\npath = \"src/admin.rs\"\n\n[dependencies]\ncore = { path = \"../core\" }\n",
+    )
+    .unwrap();
+    write(root.join("app/src/main.rs"), "fn main() {}\n").unwrap();
+    write(root.join("app/src/bin/tool/main.rs"), "fn main() {}\n").unwrap();
+    write(
+        root.join("core/Cargo.toml"),
+        "[package]\nname = \"core\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    write(root.join("core/src/lib.rs"), "\n").unwrap();
+    let packages: Vec<Package> = discover_packages(&root.join("Cargo.toml")).unwrap();
+    let rt: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    let infos: Vec<PackageInfo> = rt.block_on(collect_package_info(&packages)).unwrap();
+    let app: &PackageInfo = &infos[0];
+    assert_eq!(app.publish_registries, Some(vec![]));
+    assert_eq!(app.dependencies, vec!["core"]);
+    assert_eq!(app.features, vec!["default", "tls"]);
+    assert_eq!(app.binaries, vec!["admin", "app", "tool"]);
+    assert_eq!(app.tag, "app-v0.2.0");
+    let core: &PackageInfo = &infos[1];
+    assert_eq!(core.dependents, vec!["app"]);
+    assert!(core.binaries.is_empty());
+    let text: String = format_info_text(&infos);
+    assert!(text.starts_with("app 0.2.0\n"));
+    assert!(text.contains("  publish:      no\n"));
+    assert!(text.contains("  dependents:   app\n"));
+    let json: String = format_info_json(&infos);
+    assert!(json.contains("\"publish\": false"));
+    assert!(json.contains("\"binaries\": [\"admin\", \"app\", \"tool\"]"));
+    assert!(json.contains("\"tag\": \"core-v0.1.0\""));
+}
@@ -0,0 +1,6 @@
+/// Submodules of the module directory layout in declaration order
+pub(crate) const LAYOUT_MODULES: &[&str] =
+    &["const", "enum", "fn", "impl", "static", "struct", "test"];
+
+/// Content of a newly created layout submodule file
+pub(crate) const LAYOUT_FILE_HEADER: &str = "use super::*;\n";
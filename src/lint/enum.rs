@@ -0,0 +1,106 @@
+use crate::*;
+
+/// Error types for lint operation
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum LintError {
+    /// Unknown lint type
+    #[error("Invalid lint type: {0} (expected clippy, layout or docs)")]
+    InvalidLintType(String),
+    /// Clippy could not be started or did not produce a report
+    #[error("cargo clippy failed: {0}")]
+    ClippyFailed(String),
+    /// Manifest settings could not be loaded
+    #[error(transparent)]
+    FmtError(#[from] FmtError),
+    /// Packages could not be discovered
+    #[error(transparent)]
+    PublishError(#[from] PublishError),
+    /// IO error
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+/// Kind of check run by the lint command
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum LintType {
+    /// Clippy diagnostics grouped by lint
+    #[default]
+    Clippy,
+    /// Module directory layout
+    Layout,
+    /// `# Arguments` and `# Returns` doc sections
+    Docs,
+}
+
+/// A deviation from the module directory layout
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum LayoutIssue {
+    /// An item lives in the wrong submodule file
+    MisplacedItem {
+        /// Item keyword such as `struct` or `impl`
+        kind: String,
+        /// Item name, the implemented type for `impl` blocks
+        name: String,
+        /// Submodule the item belongs in
+        expected: String,
+    },
+    /// mod.rs declares a submodule whose file does not exist
+    MissingSubmodule(String),
+    /// A submodule file exists but mod.rs does not declare it
+    UndeclaredSubmodule(String),
+    /// mod.rs does not declare or re-export the layout submodules
+    ModRsMismatch,
+}
+
+/// A doc comment that does not match its function signature
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum DocIssue {
+    /// Parameters are not documented in `# Arguments`
+    MissingArguments,
+    /// `# Arguments` is present but the function takes no parameters
+    UnexpectedArguments,
+    /// `# Arguments` lists a different number of parameters
+    ArgumentCount {
+        /// Number of documented parameters
+        documented: usize,
+        /// Number of parameters in the signature
+        expected: usize,
+    },
+    /// A documented parameter type differs from the signature
+    ArgumentType {
+        /// Position of the parameter, starting at 1
+        position: usize,
+        /// Documented type
+        documented: String,
+        /// Type in the signature
+        expected: String,
+    },
+    /// The return value is not documented in `# Returns`
+    MissingReturns,
+    /// `# Returns` is present but the function returns nothing
+    UnexpectedReturns,
+    /// The documented return type differs from the signature
+    ReturnType {
+        /// Documented type
+        documented: String,
+        /// Type in the signature
+        expected: String,
+    },
+}
+
+/// Parsed JSON value
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum JsonValue {
+    /// `null`
+    Null,
+    /// `true` or `false`
+    Bool(bool),
+    /// Any number
+    Number(f64),
+    /// String with escapes resolved
+    String(String),
+    /// Array
+    Array(Vec<JsonValue>),
+    /// Object with keys in document order
+    Object(Vec<(String, JsonValue)>),
+}
@@ -0,0 +1,1833 @@
+use crate::*;
+
+/// Parse a JSON string literal starting at the opening quote
+///
+/// # Arguments
+///
+/// - `&[char]`: Document characters
+/// - `&mut usize`: Position of the opening quote, moved past the closing quote
+///
+/// # Returns
+///
+/// - `Option<String>`: Unescaped string, None if the literal is malformed
+fn parse_json_string(chars: &[char], pos: &mut usize) -> Option<String> {
+    *pos += 1;
+    let mut value: String = String::new();
+    while let Some(&c) = chars.get(*pos) {
+        *pos += 1;
+        match c {
+            '"' => return Some(value),
+            '\\' => {
+                let escaped: char = *chars.get(*pos)?;
+                *pos += 1;
+                match escaped {
+                    'n' => value.push('\n'),
+                    't' => value.push('\t'),
+                    'r' => value.push('\r'),
+                    'b' => value.push('\u{8}'),
+                    'f' => value.push('\u{c}'),
+                    'u' => {
+                        let hex: String = chars.get(*pos..*pos + 4)?.iter().collect();
+                        *pos += 4;
+                        let mut code: u32 = u32::from_str_radix(&hex, 16).ok()?;
+                        if (0xD800..0xDC00).contains(&code)
+                            && chars.get(*pos) == Some(&'\\')
+                            && chars.get(*pos + 1) == Some(&'u')
+                        {
+                            let low_hex: String = chars.get(*pos + 2..*pos + 6)?.iter().collect();
+                            let low: u32 = u32::from_str_radix(&low_hex, 16).ok()?;
+                            if (0xDC00..0xE000).contains(&low) {
+                                code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                                *pos += 6;
+                            }
+                        }
+                        value.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+                    other => value.push(other),
+                }
+            }
+            other => value.push(other),
+        }
+    }
+    None
+}
+
+/// Parse the JSON value at a position
+///
+/// # Arguments
+///
+/// - `&[char]`: Document characters
+/// - `&mut usize`: Position of the value, moved past it
+///
+/// # Returns
+///
+/// - `Option<JsonValue>`: Parsed value, None if it is malformed
+fn parse_json_value(chars: &[char], pos: &mut usize) -> Option<JsonValue> {
+    let skip = |pos: &mut usize| {
+        while chars.get(*pos).is_some_and(|c: &char| c.is_whitespace()) {
+            *pos += 1;
+        }
+    };
+    skip(pos);
+    match *chars.get(*pos)? {
+        '{' => {
+            *pos += 1;
+            let mut fields: Vec<(String, JsonValue)> = Vec::new();
+            skip(pos);
+            if chars.get(*pos) == Some(&'}') {
+                *pos += 1;
+                return Some(JsonValue::Object(fields));
+            }
+            loop {
+                skip(pos);
+                if chars.get(*pos) != Some(&'"') {
+                    return None;
+                }
+                let key: String = parse_json_string(chars, pos)?;
+                skip(pos);
+                if chars.get(*pos) != Some(&':') {
+                    return None;
+                }
+                *pos += 1;
+                fields.push((key, parse_json_value(chars, pos)?));
+                skip(pos);
+                match chars.get(*pos)? {
+                    ',' => *pos += 1,
+                    '}' => {
+                        *pos += 1;
+                        return Some(JsonValue::Object(fields));
+                    }
+                    _ => return None,
+                }
+            }
+        }
+        '[' => {
+            *pos += 1;
+            let mut items: Vec<JsonValue> = Vec::new();
+            skip(pos);
+            if chars.get(*pos) == Some(&']') {
+                *pos += 1;
+                return Some(JsonValue::Array(items));
+            }
+            loop {
+                items.push(parse_json_value(chars, pos)?);
+                skip(pos);
+                match chars.get(*pos)? {
+                    ',' => *pos += 1,
+                    ']' => {
+                        *pos += 1;
+                        return Some(JsonValue::Array(items));
+                    }
+                    _ => return None,
+                }
+            }
+        }
+        '"' => parse_json_string(chars, pos).map(JsonValue::String),
+        _ => {
+            let start: usize = *pos;
+            while chars
+                .get(*pos)
+                .is_some_and(|c: &char| c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.'))
+            {
+                *pos += 1;
+            }
+            let word: String = chars[start..*pos].iter().collect();
+            match word.as_str() {
+                "null" => Some(JsonValue::Null),
+                "true" => Some(JsonValue::Bool(true)),
+                "false" => Some(JsonValue::Bool(false)),
+                number => number.parse::<f64>().ok().map(JsonValue::Number),
+            }
+        }
+    }
+}
+
+/// Parse a JSON document
+///
+/// # Arguments
+///
+/// - `&str`: JSON text
+///
+/// # Returns
+///
+/// - `Option<JsonValue>`: Parsed value, None if the text is not a single JSON value
+pub(crate) fn parse_json(text: &str) -> Option<JsonValue> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut pos: usize = 0;
+    let value: JsonValue = parse_json_value(&chars, &mut pos)?;
+    chars[pos..]
+        .iter()
+        .all(|c: &char| c.is_whitespace())
+        .then_some(value)
+}
+
+/// Parse the diagnostics of `cargo clippy --message-format=json`
+///
+/// Only compiler messages with a primary span are kept, summary lines such as
+/// `N warnings emitted` are skipped. A diagnostic reported for several
+/// targets of the same package is kept once.
+///
+/// # Arguments
+///
+/// - `&str`: Standard output of cargo, one JSON message per line
+///
+/// # Returns
+///
+/// - `Vec<ClippyDiagnostic>`: Diagnostics in reporting order
+pub(crate) fn parse_clippy_diagnostics(output: &str) -> Vec<ClippyDiagnostic> {
+    let mut diagnostics: Vec<ClippyDiagnostic> = Vec::new();
+    for line in output.lines() {
+        let Some(value) = parse_json(line) else {
+            continue;
+        };
+        if value.get("reason").and_then(JsonValue::as_str) != Some("compiler-message") {
+            continue;
+        }
+        let Some(message) = value.get("message") else {
+            continue;
+        };
+        let Some(span) =
+            message
+                .get("spans")
+                .and_then(JsonValue::as_array)
+                .and_then(|spans: &[JsonValue]| {
+                    spans.iter().find(|span: &&JsonValue| {
+                        span.get("is_primary").and_then(JsonValue::as_bool) == Some(true)
+                    })
+                })
+        else {
+            continue;
+        };
+        let diagnostic: ClippyDiagnostic = ClippyDiagnostic {
+            lint: message
+                .get("code")
+                .and_then(|code: &JsonValue| code.get("code"))
+                .and_then(JsonValue::as_str)
+                .unwrap_or("rustc")
+                .to_string(),
+            level: message
+                .get("level")
+                .and_then(JsonValue::as_str)
+                .unwrap_or("warning")
+                .to_string(),
+            message: message
+                .get("message")
+                .and_then(JsonValue::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            file: span
+                .get("file_name")
+                .and_then(JsonValue::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            line: span
+                .get("line_start")
+                .and_then(JsonValue::as_u64)
+                .unwrap_or_default() as usize,
+        };
+        if !diagnostics.contains(&diagnostic) {
+            diagnostics.push(diagnostic);
+        }
+    }
+    diagnostics
+}
+
+/// Format clippy diagnostics as a summary grouped by lint
+///
+/// Lints are listed by number of diagnostics, most frequent first.
+///
+/// # Arguments
+///
+/// - `&[ClippyDiagnostic]`: Diagnostics
+///
+/// # Returns
+///
+/// - `String`: One block per lint followed by a total line
+pub(crate) fn format_lint_summary(diagnostics: &[ClippyDiagnostic]) -> String {
+    if diagnostics.is_empty() {
+        return "No clippy diagnostics\n".to_string();
+    }
+    let mut groups: Vec<(&str, Vec<&ClippyDiagnostic>)> = Vec::new();
+    for diagnostic in diagnostics {
+        match groups
+            .iter_mut()
+            .find(|(lint, _): &&mut (&str, Vec<&ClippyDiagnostic>)| *lint == diagnostic.lint)
+        {
+            Some((_, group)) => group.push(diagnostic),
+            None => groups.push((&diagnostic.lint, vec![diagnostic])),
+        }
+    }
+    groups.sort_by(
+        |(a_lint, a): &(&str, Vec<&ClippyDiagnostic>),
+         (b_lint, b): &(&str, Vec<&ClippyDiagnostic>)| {
+            b.len().cmp(&a.len()).then_with(|| a_lint.cmp(b_lint))
+        },
+    );
+    let mut output: String = String::new();
+    for (lint, group) in &groups {
+        output.push_str(&format!("{lint} ({})\n", group.len()));
+        for diagnostic in group {
+            output.push_str(&format!(
+                "  {}:{}: {}: {}\n",
+                diagnostic.file, diagnostic.line, diagnostic.level, diagnostic.message
+            ));
+        }
+    }
+    let errors: usize = diagnostics
+        .iter()
+        .filter(|diagnostic: &&ClippyDiagnostic| diagnostic.level.starts_with("error"))
+        .count();
+    output.push_str(&format!(
+        "{} warnings and {errors} errors in {} lints\n",
+        diagnostics.len() - errors,
+        groups.len()
+    ));
+    output
+}
+
+/// Run clippy without applying fixes and collect its diagnostics
+///
+/// Uses the clippy settings of the manifest, the same ones fmt applies.
+///
+/// # Arguments
+///
+/// - `&Args`: The parsed arguments
+///
+/// # Returns
+///
+/// - `Result<ClippyReport, LintError>`: Diagnostics and clippy exit status
+pub(crate) async fn execute_clippy_lint(args: &Args) -> Result<ClippyReport, LintError> {
+    let manifest_path: &str = args.manifest_path.as_deref().unwrap_or("Cargo.toml");
+    let settings: ClippySettings = load_clippy_settings(Path::new(manifest_path))?;
+    ensure_cargo_clippy(&settings).await?;
+    let packages: Option<&[String]> = (!args.packages.is_empty()).then_some(&args.packages[..]);
+    let mut command_args: Vec<String> =
+        clippy_command_args(&settings, packages, args.manifest_path.as_deref(), false);
+    command_args.insert(1, "--message-format=json".to_string());
+    let output: std::process::Output = Command::new("cargo")
+        .args(&command_args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await?;
+    let stdout: String = String::from_utf8_lossy(&output.stdout).to_string();
+    let diagnostics: Vec<ClippyDiagnostic> = parse_clippy_diagnostics(&stdout);
+    if !output.status.success() && diagnostics.is_empty() {
+        return Err(LintError::ClippyFailed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(ClippyReport {
+        diagnostics,
+        success: output.status.success(),
+    })
+}
+
+/// Read an identifier, including a raw `r#` prefix
+///
+/// # Arguments
+///
+/// - `&str`: Rust source code
+/// - `usize`: Offset of the identifier
+///
+/// # Returns
+///
+/// - `(&str, usize)`: Identifier without the raw prefix and the offset after it
+fn read_identifier(source: &str, index: usize) -> (&str, usize) {
+    let bytes: &[u8] = source.as_bytes();
+    let start: usize = if bytes[index..].starts_with(b"r#") {
+        index + 2
+    } else {
+        index
+    };
+    let mut end: usize = start;
+    while end < bytes.len() && is_ident_byte(bytes[end]) {
+        end += 1;
+    }
+    (&source[start..end], end)
+}
+
+/// Skip whitespace and comments
+///
+/// # Arguments
+///
+/// - `&[u8]`: Source bytes
+/// - `usize`: Start offset
+///
+/// # Returns
+///
+/// - `usize`: Offset of the next code byte
+fn skip_trivia(bytes: &[u8], mut index: usize) -> usize {
+    loop {
+        index = skip_whitespace(bytes, index);
+        match bytes.get(index..index + 2) {
+            Some(b"//") | Some(b"/*") => {
+                index = skip_non_code(bytes, index).unwrap_or(bytes.len());
+            }
+            _ => return index,
+        }
+    }
+}
+
+/// Find the start of the line containing an offset
+///
+/// # Arguments
+///
+/// - `&[u8]`: Source bytes
+/// - `usize`: Offset
+///
+/// # Returns
+///
+/// - `usize`: Offset after the preceding newline
+fn line_start(bytes: &[u8], index: usize) -> usize {
+    bytes[..index]
+        .iter()
+        .rposition(|byte: &u8| *byte == b'\n')
+        .map_or(0, |newline: usize| newline + 1)
+}
+
+/// List the top-level items of a Rust file
+///
+/// Doc comments and attributes directly above an item belong to it. Items
+/// are found with the tokenizer used for derive sorting, so braces in
+/// strings and comments are ignored.
+///
+/// # Arguments
+///
+/// - `&str`: Rust source code
+///
+/// # Returns
+///
+/// - `Vec<RustItem>`: Items in source order
+pub(crate) fn parse_rust_items(source: &str) -> Vec<RustItem> {
+    let bytes: &[u8] = source.as_bytes();
+    let mut items: Vec<RustItem> = Vec::new();
+    let mut pending: Option<usize> = None;
+    let mut pending_attribute: bool = false;
+    let mut previous_end: usize = 0;
+    let mut index: usize = 0;
+    while index < bytes.len() {
+        if bytes[index].is_ascii_whitespace() {
+            let end: usize = skip_whitespace(bytes, index);
+            let newlines: usize = bytes[index..end]
+                .iter()
+                .filter(|byte: &&u8| **byte == b'\n')
+                .count();
+            if newlines >= 2 && !pending_attribute {
+                pending = None;
+            }
+            index = end;
+            continue;
+        }
+        let own_line: usize = line_start(bytes, index).max(previous_end);
+        if let Some(next) = skip_non_code(bytes, index) {
+            if bytes[own_line..index].iter().all(u8::is_ascii_whitespace) {
+                pending.get_or_insert(own_line);
+            }
+            index = next;
+            continue;
+        }
+        if bytes[index] == b'#' {
+            pending.get_or_insert(own_line);
+            pending_attribute = true;
+            let mut open: usize = skip_whitespace(bytes, index + 1);
+            if bytes.get(open) == Some(&b'!') {
+                open = skip_whitespace(bytes, open + 1);
+            }
+            index = match bytes.get(open) {
+                Some(b'[') => {
+                    find_group_end(bytes, open + 1).map_or(bytes.len(), |end: usize| end + 1)
+                }
+                _ => open,
+            };
+            continue;
+        }
+        if !is_ident_byte(bytes[index]) {
+            index += 1;
+            continue;
+        }
+        let start: usize = pending.take().unwrap_or(own_line);
+        pending_attribute = false;
+        let mut cursor: usize = index;
+        let (kind, keyword_at): (String, usize) = loop {
+            cursor = skip_trivia(bytes, cursor);
+            if cursor >= bytes.len() || !is_ident_byte(bytes[cursor]) {
+                break ("macro".to_string(), cursor);
+            }
+            let (word, after): (&str, usize) = read_identifier(source, cursor);
+            let next: usize = skip_trivia(bytes, after);
+            match word {
+                "pub" => {
+                    cursor = if bytes.get(next) == Some(&b'(') {
+                        find_group_end(bytes, next + 1).map_or(bytes.len(), |end: usize| end + 1)
+                    } else {
+                        after
+                    };
+                }
+                "async" | "unsafe" | "default" | "safe" => cursor = after,
+                "extern" => {
+                    cursor = match bytes.get(next) {
+                        Some(b'"') => skip_non_code(bytes, next).unwrap_or(bytes.len()),
+                        _ => after,
+                    };
+                    if skip_trivia(bytes, cursor) < bytes.len()
+                        && bytes[skip_trivia(bytes, cursor)] == b'{'
+                    {
+                        break ("extern".to_string(), index);
+                    }
+                }
+                "const"
+                    if matches!(
+                        read_identifier(source, next.min(bytes.len())).0,
+                        "fn" | "unsafe" | "async" | "extern"
+                    ) =>
+                {
+                    cursor = after;
+                }
+                "struct" | "enum" | "union" | "fn" | "impl" | "const" | "static" | "trait"
+                | "type" | "mod" | "use" | "crate" => break (word.to_string(), cursor),
+                _ => break ("macro".to_string(), cursor),
+            }
+        };
+        let (keyword, after_keyword): (&str, usize) = read_identifier(source, keyword_at);
+        let name: String = match kind.as_str() {
+            "impl" => {
+                let mut name_start: usize = skip_trivia(bytes, after_keyword);
+                if bytes.get(name_start) == Some(&b'<') {
+                    let mut depth: usize = 0;
+                    while name_start < bytes.len() {
+                        match bytes[name_start] {
+                            b'<' => depth += 1,
+                            b'>' => {
+                                depth -= 1;
+                                if depth == 0 {
+                                    name_start += 1;
+                                    break;
+                                }
+                            }
+                            _ => {}
+                        }
+                        name_start += 1;
+                    }
+                }
+                let name_end: usize = source[name_start..]
+                    .find(['{', ';'])
+                    .map_or(bytes.len(), |offset: usize| name_start + offset);
+                let header: &str = &source[name_start..name_end];
+                let header: &str = header.split(" where").next().unwrap_or(header);
+                header.split_whitespace().collect::<Vec<&str>>().join(" ")
+            }
+            "use" | "macro" | "extern" | "crate" => String::new(),
+            _ => read_identifier(source, skip_trivia(bytes, after_keyword).min(bytes.len()))
+                .0
+                .to_string(),
+        };
+        let kind: String = if kind == "macro" && keyword == "macro_rules" {
+            "macro_rules".to_string()
+        } else if kind == "crate" {
+            "extern crate".to_string()
+        } else {
+            kind
+        };
+        let semicolon_only: bool = matches!(
+            kind.as_str(),
+            "const" | "static" | "use" | "type" | "extern crate"
+        );
+        let mut end: usize = after_keyword;
+        let mut depth: usize = 0;
+        while end < bytes.len() {
+            if let Some(next) = skip_non_code(bytes, end) {
+                end = next;
+                continue;
+            }
+            match bytes[end] {
+                b'(' | b'[' | b'{' => depth += 1,
+                b')' | b']' => depth = depth.saturating_sub(1),
+                b'}' => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 && !semicolon_only {
+                        end += 1;
+                        break;
+                    }
+                }
+                b';' if depth == 0 => {
+                    end += 1;
+                    break;
+                }
+                _ => {}
+            }
+            end += 1;
+        }
+        let end: usize = end.min(bytes.len());
+        let line_end: usize = bytes[end..]
+            .iter()
+            .position(|byte: &u8| *byte == b'\n')
+            .map_or(bytes.len(), |offset: usize| end + offset + 1);
+        let end: usize = if bytes[end..line_end].iter().all(u8::is_ascii_whitespace) {
+            line_end
+        } else {
+            end
+        };
+        items.push(RustItem {
+            kind,
+            name,
+            line: 1 + bytes[..keyword_at]
+                .iter()
+                .filter(|byte: &&u8| **byte == b'\n')
+                .count(),
+            start,
+            end,
+        });
+        previous_end = end;
+        index = end;
+    }
+    items
+}
+
+/// Find the layout submodule an item kind belongs in
+///
+/// # Arguments
+///
+/// - `&str`: Item keyword
+///
+/// # Returns
+///
+/// - `Option<&'static str>`: Submodule name, None for items that may live anywhere
+fn layout_module_for_item(kind: &str) -> Option<&'static str> {
+    match kind {
+        "struct" | "union" => Some("struct"),
+        "enum" => Some("enum"),
+        "fn" => Some("fn"),
+        "impl" => Some("impl"),
+        "const" => Some("const"),
+        "static" => Some("static"),
+        _ => None,
+    }
+}
+
+/// Find the directories below a root that contain a mod.rs
+///
+/// # Arguments
+///
+/// - `&Path`: Directory to search
+/// - `&mut Vec<PathBuf>`: Module directories, extended in place
+///
+/// # Returns
+///
+/// - `Result<(), std::io::Error>`: Success or error
+fn find_module_dirs(dir: &Path, dirs: &mut Vec<PathBuf>) -> Result<(), std::io::Error> {
+    if dir.join("mod.rs").is_file() {
+        dirs.push(dir.to_path_buf());
+    }
+    let mut children: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .flatten()
+        .map(|entry: std::fs::DirEntry| entry.path())
+        .filter(|path: &PathBuf| path.is_dir())
+        .collect();
+    children.sort();
+    for child in children {
+        find_module_dirs(&child, dirs)?;
+    }
+    Ok(())
+}
+
+/// Check whether a module directory only holds layout submodules
+///
+/// # Arguments
+///
+/// - `&Path`: Module directory
+///
+/// # Returns
+///
+/// - `Result<bool, std::io::Error>`: True if every Rust file is mod.rs or a layout submodule and there are no child modules
+fn is_leaf_layout_module(dir: &Path) -> Result<bool, std::io::Error> {
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        let path: PathBuf = entry.path();
+        if path.is_dir() {
+            let has_rust: bool =
+                std::fs::read_dir(&path)?
+                    .flatten()
+                    .any(|child: std::fs::DirEntry| {
+                        child
+                            .path()
+                            .extension()
+                            .is_some_and(|ext: &std::ffi::OsStr| ext == "rs")
+                    });
+            if has_rust {
+                return Ok(false);
+            }
+        } else if path
+            .extension()
+            .is_some_and(|ext: &std::ffi::OsStr| ext == "rs")
+        {
+            let stem: String = path
+                .file_stem()
+                .map(|s: &std::ffi::OsStr| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if stem != "mod" && !LAYOUT_MODULES.contains(&stem.as_str()) {
+                return Ok(false);
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// List the layout submodules present in a module directory
+///
+/// # Arguments
+///
+/// - `&Path`: Module directory
+///
+/// # Returns
+///
+/// - `Vec<&'static str>`: Submodule names in declaration order
+fn present_layout_modules(dir: &Path) -> Vec<&'static str> {
+    LAYOUT_MODULES
+        .iter()
+        .copied()
+        .filter(|module: &&str| dir.join(format!("{module}.rs")).is_file())
+        .collect()
+}
+
+/// Read the declarations and re-exports of a mod.rs
+///
+/// Declarations and re-exports are read by meaning, so `r#` prefixes, the
+/// visibility of the re-exports and their order do not matter. Private
+/// `use` items are kept as written.
+///
+/// # Arguments
+///
+/// - `&str`: mod.rs source
+///
+/// # Returns
+///
+/// - `Option<ModRsLayout>`: Declarations and re-exports, None if one of them has a form that cannot be regenerated
+pub(crate) fn parse_mod_rs(source: &str) -> Option<ModRsLayout> {
+    let mut layout: ModRsLayout = ModRsLayout::default();
+    for item in parse_rust_items(source) {
+        let text: &str = &source[item.start..item.end];
+        match item.kind.as_str() {
+            "mod" => {
+                if !text.trim_end().ends_with(';') {
+                    return None;
+                }
+                let cfg_test: bool = text
+                    .split_whitespace()
+                    .collect::<String>()
+                    .contains("#[cfg(test)]");
+                match (item.name.as_str(), cfg_test) {
+                    ("test", true) => layout.test = true,
+                    (_, true) => return None,
+                    (name, false) => layout.modules.push(name.to_string()),
+                }
+            }
+            "use" => {
+                let code: String = text
+                    .lines()
+                    .map(str::trim)
+                    .skip_while(|line: &&str| line.starts_with("#[") || line.starts_with("//"))
+                    .collect::<Vec<&str>>()
+                    .join(" ");
+                let compact: String = code.split_whitespace().collect();
+                let (visibility, tree): (&str, &str) = compact.split_once("use")?;
+                if visibility.is_empty() {
+                    layout.imports.push(code);
+                    continue;
+                }
+                if !visibility.starts_with("pub")
+                    || (!layout.visibility.is_empty() && layout.visibility != visibility)
+                {
+                    return None;
+                }
+                layout.visibility = visibility.to_string();
+                let tree: &str = tree.strip_suffix(';')?;
+                let tree: &str = tree
+                    .strip_prefix('{')
+                    .and_then(|tree: &str| tree.strip_suffix('}'))
+                    .unwrap_or(tree);
+                for path in tree.split(',').filter(|path: &&str| !path.is_empty()) {
+                    let module: &str = path.strip_suffix("::*")?;
+                    let module: &str = module.strip_prefix("r#").unwrap_or(module);
+                    if !LAYOUT_MODULES.contains(&module) {
+                        return None;
+                    }
+                    layout.exports.push(module.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(layout)
+}
+
+/// Check whether a mod.rs declares and re-exports the layout submodules
+///
+/// Every submodule except `test` is declared, `test` only behind
+/// `#[cfg(test)]`, and every submodule except `impl` and `test` is
+/// re-exported with a glob.
+///
+/// # Arguments
+///
+/// - `&ModRsLayout`: Declarations and re-exports of the mod.rs
+/// - `&[&str]`: Layout submodules present in the directory
+///
+/// # Returns
+///
+/// - `bool`: True if the mod.rs matches the submodules
+fn mod_rs_matches(layout: &ModRsLayout, present: &[&str]) -> bool {
+    let sorted = |names: &[String]| -> Vec<String> {
+        let mut names: Vec<String> = names.to_vec();
+        names.sort();
+        names.dedup();
+        names
+    };
+    let expected = |skip: &[&str]| -> Vec<String> {
+        let mut names: Vec<String> = present
+            .iter()
+            .filter(|module: &&&str| !skip.contains(module))
+            .map(|module: &&str| module.to_string())
+            .collect();
+        names.sort();
+        names
+    };
+    sorted(&layout.modules) == expected(&["test"])
+        && sorted(&layout.exports) == expected(&["impl", "test"])
+        && layout.test == present.contains(&"test")
+}
+
+/// Render a mod.rs that declares and re-exports the layout submodules
+///
+/// The visibility of the existing re-exports and the private `use` items
+/// are kept, `pub(crate)` is used when there were no re-exports.
+///
+/// # Arguments
+///
+/// - `&ModRsLayout`: Declarations and re-exports of the current mod.rs
+/// - `&[&str]`: Layout submodules present in the directory
+///
+/// # Returns
+///
+/// - `String`: mod.rs content
+fn render_layout_mod_rs(layout: &ModRsLayout, present: &[&str]) -> String {
+    let mut content: String = String::new();
+    for module in present.iter().filter(|module: &&&str| **module != "test") {
+        content.push_str(&format!("mod r#{module};\n"));
+    }
+    if present.contains(&"test") {
+        content.push_str("\n#[cfg(test)]\nmod test;\n");
+    }
+    let exports: Vec<String> = present
+        .iter()
+        .filter(|module: &&&str| !matches!(**module, "impl" | "test"))
+        .map(|module: &&str| format!("r#{module}::*"))
+        .collect();
+    let visibility: &str = if layout.visibility.is_empty() {
+        "pub(crate)"
+    } else {
+        &layout.visibility
+    };
+    match exports.as_slice() {
+        [] => {}
+        [export] => content.push_str(&format!("\n{visibility} use {export};\n")),
+        _ => content.push_str(&format!("\n{visibility} use {{{}}};\n", exports.join(", "))),
+    }
+    if !layout.imports.is_empty() {
+        content.push_str(&format!("\n{}\n", layout.imports.join("\n")));
+    }
+    content
+}
+
+/// Check a module directory against the layout convention
+///
+/// # Arguments
+///
+/// - `&Path`: Module directory containing a mod.rs
+///
+/// # Returns
+///
+/// - `Result<Vec<LayoutFinding>, std::io::Error>`: Issues in file order
+pub(crate) fn lint_layout_module(dir: &Path) -> Result<Vec<LayoutFinding>, std::io::Error> {
+    let mut findings: Vec<LayoutFinding> = Vec::new();
+    let mod_rs: PathBuf = dir.join("mod.rs");
+    let mod_source: String = read_to_string(&mod_rs)?;
+    let leaf: bool = is_leaf_layout_module(dir)?;
+    let present: Vec<&str> = present_layout_modules(dir);
+    let mod_items: Vec<RustItem> = parse_rust_items(&mod_source);
+    let declared: Vec<&RustItem> = mod_items
+        .iter()
+        .filter(|item: &&RustItem| {
+            item.kind == "mod" && !mod_source[..item.end].trim_end().ends_with('}')
+        })
+        .collect();
+    let regenerable: bool = leaf
+        && declared
+            .iter()
+            .all(|item: &&RustItem| LAYOUT_MODULES.contains(&item.name.as_str()));
+    for item in &declared {
+        let exists: bool = dir.join(format!("{}.rs", item.name)).is_file()
+            || dir.join(&item.name).join("mod.rs").is_file();
+        if !exists {
+            findings.push(LayoutFinding {
+                path: mod_rs.clone(),
+                line: item.line,
+                issue: LayoutIssue::MissingSubmodule(item.name.clone()),
+                fixable: LAYOUT_MODULES.contains(&item.name.as_str()),
+            });
+        }
+    }
+    for module in &present {
+        if !declared.iter().any(|item: &&RustItem| item.name == *module) {
+            findings.push(LayoutFinding {
+                path: mod_rs.clone(),
+                line: 0,
+                issue: LayoutIssue::UndeclaredSubmodule(module.to_string()),
+                fixable: regenerable,
+            });
+        }
+    }
+    if leaf && findings.is_empty() {
+        let layout: Option<ModRsLayout> = parse_mod_rs(&mod_source);
+        if !layout
+            .as_ref()
+            .is_some_and(|layout: &ModRsLayout| mod_rs_matches(layout, &present))
+        {
+            findings.push(LayoutFinding {
+                path: mod_rs.clone(),
+                line: 0,
+                issue: LayoutIssue::ModRsMismatch,
+                fixable: regenerable && layout.is_some(),
+            });
+        }
+    }
+    let mut files: Vec<(PathBuf, Option<&str>)> = vec![(mod_rs.clone(), None)];
+    files.extend(
+        present
+            .iter()
+            .filter(|module: &&&str| **module != "test")
+            .map(|module: &&str| (dir.join(format!("{module}.rs")), Some(*module))),
+    );
+    for (path, module) in files {
+        let source: String = if module.is_none() {
+            mod_source.clone()
+        } else {
+            read_to_string(&path)?
+        };
+        for item in parse_rust_items(&source) {
+            let Some(expected) = layout_module_for_item(&item.kind) else {
+                continue;
+            };
+            if module != Some(expected) {
+                findings.push(LayoutFinding {
+                    path: path.clone(),
+                    line: item.line,
+                    issue: LayoutIssue::MisplacedItem {
+                        kind: item.kind.clone(),
+                        name: item.name.clone(),
+                        expected: expected.to_string(),
+                    },
+                    fixable: true,
+                });
+            }
+        }
+    }
+    Ok(findings)
+}
+
+/// Resolve the mechanical layout issues of a module directory
+///
+/// Misplaced items are moved to the end of their submodule file, missing
+/// layout submodules are created and mod.rs of a leaf module is rewritten
+/// with `render_layout_mod_rs`. A mod.rs that `parse_mod_rs` cannot read is
+/// left untouched. A new submodule file starts with the `use` items of the
+/// file the item came from.
+///
+/// # Arguments
+///
+/// - `&Path`: Module directory containing a mod.rs
+/// - `&[LayoutFinding]`: Issues found by `lint_layout_module`
+///
+/// # Returns
+///
+/// - `Result<(), std::io::Error>`: Success or error
+pub(crate) fn fix_layout_module(
+    dir: &Path,
+    findings: &[LayoutFinding],
+) -> Result<(), std::io::Error> {
+    let mut sources: Vec<PathBuf> = findings
+        .iter()
+        .filter(|finding: &&LayoutFinding| {
+            matches!(finding.issue, LayoutIssue::MisplacedItem { .. })
+        })
+        .map(|finding: &LayoutFinding| finding.path.clone())
+        .collect();
+    sources.dedup();
+    for path in sources {
+        let mut source: String = read_to_string(&path)?;
+        let items: Vec<RustItem> = parse_rust_items(&source);
+        let imports: String = items
+            .iter()
+            .filter(|item: &&RustItem| item.kind == "use")
+            .map(|item: &RustItem| source[item.start..item.end].trim_end().to_string())
+            .collect::<Vec<String>>()
+            .join("\n");
+        let module: Option<String> = path
+            .file_stem()
+            .map(|s: &std::ffi::OsStr| s.to_string_lossy().to_string());
+        let moves: Vec<(&RustItem, &str)> = items
+            .iter()
+            .filter_map(|item: &RustItem| {
+                let expected: &str = layout_module_for_item(&item.kind)?;
+                (module.as_deref() != Some(expected)).then_some((item, expected))
+            })
+            .collect();
+        for (item, expected) in &moves {
+            let target: PathBuf = dir.join(format!("{expected}.rs"));
+            let existing: String = if target.is_file() {
+                read_to_string(&target)?
+            } else if imports.is_empty() {
+                LAYOUT_FILE_HEADER.to_string()
+            } else {
+                format!("{imports}\n")
+            };
+            write(
+                &target,
+                format!(
+                    "{}\n\n{}\n",
+                    existing.trim_end(),
+                    source[item.start..item.end].trim()
+                ),
+            )?;
+        }
+        for (item, _) in moves.iter().rev() {
+            source.replace_range(item.start..item.end, "");
+        }
+        while source.contains("\n\n\n") {
+            source = source.replace("\n\n\n", "\n\n");
+        }
+        write(&path, format!("{}\n", source.trim()))?;
+    }
+    for finding in findings {
+        if let LayoutIssue::MissingSubmodule(name) = &finding.issue
+            && finding.fixable
+        {
+            let target: PathBuf = dir.join(format!("{name}.rs"));
+            if !target.is_file() {
+                write(&target, LAYOUT_FILE_HEADER)?;
+            }
+        }
+    }
+    if is_leaf_layout_module(dir)? {
+        let mod_source: String = read_to_string(dir.join("mod.rs"))?;
+        let only_layout: bool = parse_rust_items(&mod_source).iter().all(|item: &RustItem| {
+            item.kind == "use"
+                || (item.kind == "mod" && LAYOUT_MODULES.contains(&item.name.as_str()))
+        });
+        let present: Vec<&str> = present_layout_modules(dir);
+        if only_layout
+            && let Some(layout) = parse_mod_rs(&mod_source)
+            && !mod_rs_matches(&layout, &present)
+        {
+            write(dir.join("mod.rs"), render_layout_mod_rs(&layout, &present))?;
+        }
+    }
+    Ok(())
+}
+
+/// Check the module layout of every package
+///
+/// Every directory below a package's `src` that contains a mod.rs is
+/// checked. With `--fix` the mechanical issues are resolved first and the
+/// remaining ones are reported.
+///
+/// # Arguments
+///
+/// - `&Args`: The parsed arguments
+///
+/// # Returns
+///
+/// - `Result<LayoutReport, LintError>`: Remaining issues and the number of fixed ones
+pub(crate) fn execute_layout_lint(args: &Args) -> Result<LayoutReport, LintError> {
+    let manifest_path: &str = args.manifest_path.as_deref().unwrap_or("Cargo.toml");
+    let packages: Vec<Package> = discover_packages(Path::new(manifest_path))?;
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for package in &packages {
+        if !args.packages.is_empty() && !args.packages.contains(&package.name) {
+            continue;
+        }
+        let src: PathBuf = package.path.join("src");
+        if src.is_dir() {
+            find_module_dirs(&src, &mut dirs)?;
+        }
+    }
+    let mut report: LayoutReport = LayoutReport::default();
+    for dir in dirs {
+        let mut findings: Vec<LayoutFinding> = lint_layout_module(&dir)?;
+        if args.fix
+            && findings
+                .iter()
+                .any(|finding: &LayoutFinding| finding.fixable)
+        {
+            fix_layout_module(&dir, &findings)?;
+            let remaining: Vec<LayoutFinding> = lint_layout_module(&dir)?;
+            report.fixed += findings.len().saturating_sub(remaining.len());
+            findings = remaining;
+        }
+        report.findings.extend(findings);
+    }
+    Ok(report)
+}
+
+/// Format layout issues
+///
+/// # Arguments
+///
+/// - `&LayoutReport`: Layout lint result
+///
+/// # Returns
+///
+/// - `String`: One line per issue followed by a total line
+pub(crate) fn format_layout_report(report: &LayoutReport) -> String {
+    let mut output: String = String::new();
+    if report.fixed > 0 {
+        output.push_str(&format!("Fixed {} layout issues\n", report.fixed));
+    }
+    if report.findings.is_empty() {
+        output.push_str("No layout issues\n");
+        return output;
+    }
+    for finding in &report.findings {
+        let path: String = finding
+            .path
+            .strip_prefix(".")
+            .unwrap_or(&finding.path)
+            .display()
+            .to_string();
+        if finding.line > 0 {
+            output.push_str(&format!("{path}:{}: {}\n", finding.line, finding.issue));
+        } else {
+            output.push_str(&format!("{path}: {}\n", finding.issue));
+        }
+    }
+    let fixable: usize = report
+        .findings
+        .iter()
+        .filter(|finding: &&LayoutFinding| finding.fixable)
+        .count();
+    output.push_str(&format!(
+        "{} layout issues ({fixable} fixable with --fix)\n",
+        report.findings.len()
+    ));
+    output
+}
+
+/// Split text at commas outside of brackets and generics
+///
+/// # Arguments
+///
+/// - `&str`: Text such as a parameter list
+///
+/// # Returns
+///
+/// - `Vec<&str>`: Non-empty trimmed parts
+fn split_top_level(text: &str) -> Vec<&str> {
+    let bytes: &[u8] = text.as_bytes();
+    let mut parts: Vec<&str> = Vec::new();
+    let mut depth: usize = 0;
+    let mut start: usize = 0;
+    let mut index: usize = 0;
+    while index < bytes.len() {
+        if let Some(next) = skip_non_code(bytes, index) {
+            index = next;
+            continue;
+        }
+        match bytes[index] {
+            b'-' if bytes.get(index + 1) == Some(&b'>') => index += 1,
+            b'<' | b'(' | b'[' | b'{' => depth += 1,
+            b'>' | b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+            b',' if depth == 0 => {
+                parts.push(text[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    parts.push(text[start..].trim());
+    parts.retain(|part: &&str| !part.is_empty());
+    parts
+}
+
+/// Get the type of a function parameter
+///
+/// # Arguments
+///
+/// - `&str`: Parameter such as `path: &Path`
+///
+/// # Returns
+///
+/// - `Option<String>`: Type with collapsed whitespace, None for the `self` receiver
+fn param_type(param: &str) -> Option<String> {
+    let bytes: &[u8] = param.as_bytes();
+    let colon: Option<usize> = (0..bytes.len()).find(|index: &usize| {
+        bytes[*index] == b':'
+            && bytes.get(index + 1) != Some(&b':')
+            && (*index == 0 || bytes[index - 1] != b':')
+    });
+    let pattern: &str = colon.map_or(param, |colon: usize| &param[..colon]);
+    if pattern.trim().trim_start_matches("mut ").trim() == "self" || colon.is_none() {
+        return None;
+    }
+    let colon: usize = colon?;
+    Some(
+        param[colon + 1..]
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" "),
+    )
+}
+
+/// Parse the parameter and return types of a function
+///
+/// # Arguments
+///
+/// - `&str`: Source starting at or before the `fn` keyword
+///
+/// # Returns
+///
+/// - `Option<FnSignature>`: Signature, None if no `fn` keyword is found
+pub(crate) fn parse_fn_signature(text: &str) -> Option<FnSignature> {
+    let bytes: &[u8] = text.as_bytes();
+    let keyword: usize = text
+        .match_indices("fn")
+        .map(|(index, _): (usize, &str)| index)
+        .find(|index: &usize| {
+            (*index == 0 || !is_ident_byte(bytes[index - 1]))
+                && bytes
+                    .get(index + 2)
+                    .is_some_and(|byte: &u8| byte.is_ascii_whitespace())
+        })?;
+    let (name, mut cursor): (&str, usize) = read_identifier(text, skip_trivia(bytes, keyword + 2));
+    cursor = skip_trivia(bytes, cursor);
+    if bytes.get(cursor) == Some(&b'<') {
+        let mut depth: usize = 0;
+        while cursor < bytes.len() {
+            match bytes[cursor] {
+                b'-' if bytes.get(cursor + 1) == Some(&b'>') => cursor += 1,
+                b'<' => depth += 1,
+                b'>' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        cursor += 1;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+            cursor += 1;
+        }
+        cursor = skip_trivia(bytes, cursor);
+    }
+    if bytes.get(cursor) != Some(&b'(') {
+        return None;
+    }
+    let close: usize = find_group_end(bytes, cursor + 1)?;
+    let params: Vec<String> = split_top_level(&text[cursor + 1..close])
+        .into_iter()
+        .filter_map(param_type)
+        .collect();
+    let after: usize = skip_trivia(bytes, close + 1);
+    let returns: Option<String> = if bytes[after..].starts_with(b"->") {
+        let start: usize = after + 2;
+        let mut end: usize = start;
+        let mut depth: usize = 0;
+        while end < bytes.len() {
+            if let Some(next) = skip_non_code(bytes, end) {
+                end = next;
+                continue;
+            }
+            match bytes[end] {
+                b'-' if bytes.get(end + 1) == Some(&b'>') => end += 1,
+                b'<' | b'(' | b'[' => depth += 1,
+                b'>' | b')' | b']' => depth = depth.saturating_sub(1),
+                b'{' | b';' if depth == 0 => break,
+                b'w' if depth == 0
+                    && bytes[end..].starts_with(b"where")
+                    && !is_ident_byte(bytes[end - 1])
+                    && !bytes.get(end + 5).copied().is_some_and(is_ident_byte) =>
+                {
+                    break;
+                }
+                _ => {}
+            }
+            end += 1;
+        }
+        let returns: String = text[start..end.min(bytes.len())]
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+        (!returns.is_empty() && returns != "()").then_some(returns)
+    } else {
+        None
+    };
+    Some(FnSignature {
+        name: name.to_string(),
+        params,
+        returns,
+        self_type: None,
+    })
+}
+
+/// Split doc comment lines into the text before the first section and the sections
+///
+/// # Arguments
+///
+/// - `&[String]`: Doc comment lines without the `///` prefix
+///
+/// # Returns
+///
+/// - `(Vec<String>, Vec<(String, Vec<String>)>)`: Leading lines and sections with their title and body
+fn doc_sections(doc: &[String]) -> (Vec<String>, Vec<(String, Vec<String>)>) {
+    let mut preamble: Vec<String> = Vec::new();
+    let mut sections: Vec<(String, Vec<String>)> = Vec::new();
+    for line in doc {
+        if let Some(title) = line.strip_prefix("# ") {
+            sections.push((title.trim().to_string(), Vec::new()));
+        } else if let Some((_, body)) = sections.last_mut() {
+            body.push(line.clone());
+        } else {
+            preamble.push(line.clone());
+        }
+    }
+    (preamble, sections)
+}
+
+/// Read the `` - `Type`: description `` bullets of a doc section
+///
+/// # Arguments
+///
+/// - `&[String]`: Section body
+///
+/// # Returns
+///
+/// - `Vec<(String, String)>`: Documented types and their descriptions
+fn doc_bullets(body: &[String]) -> Vec<(String, String)> {
+    body.iter()
+        .filter_map(|line: &String| {
+            let rest: &str = line.strip_prefix("- `")?;
+            let close: usize = rest.find('`')?;
+            let description: &str = rest[close + 1..].trim_start_matches(':').trim();
+            Some((rest[..close].to_string(), description.to_string()))
+        })
+        .collect()
+}
+
+/// Normalise a type for comparison
+///
+/// Whitespace, lifetimes and module paths are dropped, so `&'a std::path::Path`
+/// and `&Path` compare equal.
+///
+/// # Arguments
+///
+/// - `&str`: Type as written in a signature or doc comment
+/// - `Option<&str>`: Type that `Self` stands for
+///
+/// # Returns
+///
+/// - `String`: Normalised type
+fn normalize_type(ty: &str, self_type: Option<&str>) -> String {
+    let bytes: &[u8] = ty.as_bytes();
+    let mut normalized: String = String::new();
+    let mut index: usize = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'\'' {
+            index += 1;
+            while index < bytes.len() && is_ident_byte(bytes[index]) {
+                index += 1;
+            }
+            index = skip_whitespace(bytes, index);
+            if bytes.get(index) == Some(&b',') {
+                index += 1;
+            }
+        } else if is_ident_byte(bytes[index]) {
+            let (ident, next): (&str, usize) = read_identifier(ty, index);
+            if bytes[next..].starts_with(b"::") {
+                index = next + 2;
+            } else {
+                match self_type {
+                    Some(self_type) if ident == "Self" => {
+                        normalized.push_str(&normalize_type(self_type, None));
+                    }
+                    _ => normalized.push_str(ident),
+                }
+                index = next;
+            }
+        } else {
+            if !bytes[index].is_ascii_whitespace() {
+                normalized.push(bytes[index] as char);
+            }
+            index += 1;
+        }
+    }
+    normalized.replace(",>", ">").replace("<>", "")
+}
+
+/// Compare two types ignoring whitespace, lifetimes and module paths
+///
+/// # Arguments
+///
+/// - `&str`: First type
+/// - `&str`: Second type
+/// - `Option<&str>`: Type that `Self` stands for
+///
+/// # Returns
+///
+/// - `bool`: True if the types are the same
+fn same_type(a: &str, b: &str, self_type: Option<&str>) -> bool {
+    normalize_type(a, self_type) == normalize_type(b, self_type)
+}
+
+/// Check the `# Arguments` and `# Returns` sections of a function
+///
+/// # Arguments
+///
+/// - `&FnSignature`: Function signature
+/// - `&[String]`: Doc comment lines without the `///` prefix
+///
+/// # Returns
+///
+/// - `Vec<DocIssue>`: Issues, empty if the docs match the signature
+pub(crate) fn check_fn_docs(signature: &FnSignature, doc: &[String]) -> Vec<DocIssue> {
+    let mut issues: Vec<DocIssue> = Vec::new();
+    let (_, sections): (Vec<String>, Vec<(String, Vec<String>)>) = doc_sections(doc);
+    let section = |title: &str| -> Option<Vec<(String, String)>> {
+        sections
+            .iter()
+            .find(|(name, _): &&(String, Vec<String>)| name == title)
+            .map(|(_, body): &(String, Vec<String>)| doc_bullets(body))
+    };
+    match (signature.params.is_empty(), section("Arguments")) {
+        (false, None) => issues.push(DocIssue::MissingArguments),
+        (true, Some(_)) => issues.push(DocIssue::UnexpectedArguments),
+        (false, Some(bullets)) if bullets.len() != signature.params.len() => {
+            issues.push(DocIssue::ArgumentCount {
+                documented: bullets.len(),
+                expected: signature.params.len(),
+            });
+        }
+        (false, Some(bullets)) => {
+            for (index, ((documented, _), expected)) in
+                bullets.iter().zip(&signature.params).enumerate()
+            {
+                if !same_type(documented, expected, signature.self_type.as_deref()) {
+                    issues.push(DocIssue::ArgumentType {
+                        position: index + 1,
+                        documented: documented.clone(),
+                        expected: expected.clone(),
+                    });
+                }
+            }
+        }
+        (true, None) => {}
+    }
+    match (&signature.returns, section("Returns")) {
+        (Some(_), None) => issues.push(DocIssue::MissingReturns),
+        (None, Some(_)) => issues.push(DocIssue::UnexpectedReturns),
+        (Some(expected), Some(bullets)) => {
+            let documented: String = bullets
+                .first()
+                .map(|(documented, _): &(String, String)| documented.clone())
+                .unwrap_or_default();
+            if !same_type(&documented, expected, signature.self_type.as_deref()) {
+                issues.push(DocIssue::ReturnType {
+                    documented,
+                    expected: expected.clone(),
+                });
+            }
+        }
+        (None, None) => {}
+    }
+    issues
+}
+
+/// Rewrite doc comment lines so their sections match a signature
+///
+/// Descriptions of parameters whose type is still documented are kept,
+/// new parameters get an empty skeleton bullet.
+///
+/// # Arguments
+///
+/// - `&FnSignature`: Function signature
+/// - `&[String]`: Doc comment lines without the `///` prefix
+///
+/// # Returns
+///
+/// - `Vec<String>`: New doc comment lines without the `///` prefix
+pub(crate) fn render_fn_docs(signature: &FnSignature, doc: &[String]) -> Vec<String> {
+    let (mut lines, sections): (Vec<String>, Vec<(String, Vec<String>)>) = doc_sections(doc);
+    while lines.last().is_some_and(|line: &String| line.is_empty()) {
+        lines.pop();
+    }
+    let bullets = |title: &str| -> Vec<(String, String)> {
+        sections
+            .iter()
+            .find(|(name, _): &&(String, Vec<String>)| name == title)
+            .map(|(_, body): &(String, Vec<String>)| doc_bullets(body))
+            .unwrap_or_default()
+    };
+    let mut section = |title: &str, entries: Vec<(String, String)>| {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(format!("# {title}"));
+        lines.push(String::new());
+        for (ty, description) in entries {
+            if description.is_empty() {
+                lines.push(format!("- `{ty}`:"));
+            } else {
+                lines.push(format!("- `{ty}`: {description}"));
+            }
+        }
+    };
+    let documented: Vec<(String, String)> = bullets("Arguments");
+    let mut used: Vec<bool> = vec![false; documented.len()];
+    let mut arguments: Vec<(String, String)> = Vec::new();
+    for (index, expected) in signature.params.iter().enumerate() {
+        let matching: Option<usize> = (0..documented.len())
+            .find(|i: &usize| {
+                !used[*i] && same_type(&documented[*i].0, expected, signature.self_type.as_deref())
+            })
+            .or_else(|| {
+                (documented.len() == signature.params.len() && !used[index]).then_some(index)
+            });
+        let description: String = match matching {
+            Some(i) => {
+                used[i] = true;
+                documented[i].1.clone()
+            }
+            None => String::new(),
+        };
+        arguments.push((expected.clone(), description));
+    }
+    if !arguments.is_empty() {
+        section("Arguments", arguments);
+    }
+    if let Some(expected) = &signature.returns {
+        let description: String = bullets("Returns")
+            .first()
+            .map(|(_, description): &(String, String)| description.clone())
+            .unwrap_or_default();
+        section("Returns", vec![(expected.clone(), description)]);
+    }
+    for (title, body) in &sections {
+        if title == "Arguments" || title == "Returns" {
+            continue;
+        }
+        let start: usize = body
+            .iter()
+            .position(|line: &String| !line.is_empty())
+            .unwrap_or(body.len());
+        let end: usize = body
+            .iter()
+            .rposition(|line: &String| !line.is_empty())
+            .map_or(start, |end: usize| end + 1);
+        lines.push(String::new());
+        lines.push(format!("# {title}"));
+        lines.push(String::new());
+        lines.extend(body[start..end].iter().cloned());
+    }
+    lines
+}
+
+/// Find the offset of the start of a line
+///
+/// # Arguments
+///
+/// - `&str`: Source text
+/// - `usize`: Line number starting at 1
+///
+/// # Returns
+///
+/// - `usize`: Offset of the first byte of the line
+fn line_offset(source: &str, line: usize) -> usize {
+    source
+        .match_indices('\n')
+        .nth(line.saturating_sub(2))
+        .map_or(
+            0,
+            |(index, _): (usize, &str)| {
+                if line <= 1 { 0 } else { index + 1 }
+            },
+        )
+}
+
+/// List the functions whose docs are checked
+///
+/// Top-level functions and methods of inherent `impl` blocks are included,
+/// trait implementations and functions marked as tests or entry points
+/// are not.
+///
+/// # Arguments
+///
+/// - `&str`: Rust source code
+///
+/// # Returns
+///
+/// - `Vec<(RustItem, Option<String>)>`: Function items with offsets into the source and the implemented type of methods
+fn documented_fns(source: &str) -> Vec<(RustItem, Option<String>)> {
+    let bytes: &[u8] = source.as_bytes();
+    let mut fns: Vec<(RustItem, Option<String>)> = Vec::new();
+    for item in parse_rust_items(source) {
+        if item.kind == "fn" {
+            fns.push((item, None));
+        } else if item.kind == "impl" && !item.name.contains(" for ") {
+            let mut open: usize = line_offset(source, item.line);
+            while open < item.end && bytes[open] != b'{' {
+                open = skip_non_code(bytes, open).unwrap_or(open + 1);
+            }
+            let Some(close) = find_group_end(bytes, open + 1) else {
+                continue;
+            };
+            let base_line: usize = source[..open + 1].matches('\n').count();
+            for method in parse_rust_items(&source[open + 1..close]) {
+                if method.kind == "fn" {
+                    fns.push((
+                        RustItem {
+                            start: method.start + open + 1,
+                            end: method.end + open + 1,
+                            line: method.line + base_line,
+                            ..method
+                        },
+                        Some(item.name.clone()),
+                    ));
+                }
+            }
+        }
+    }
+    fns.retain(|(item, _): &(RustItem, Option<String>)| {
+        !source[item.start..line_offset(source, item.line)]
+            .lines()
+            .filter_map(|line: &str| line.trim().strip_prefix("#["))
+            .any(|attribute: &str| {
+                let path: &str = attribute
+                    .split(['(', ']'])
+                    .next()
+                    .unwrap_or_default()
+                    .trim();
+                path.ends_with("test") || path.ends_with("main")
+            })
+    });
+    fns
+}
+
+/// Read the doc comment lines of an item
+///
+/// # Arguments
+///
+/// - `&str`: Rust source code
+/// - `&RustItem`: Item
+///
+/// # Returns
+///
+/// - `Vec<String>`: Doc comment lines without the `///` prefix
+fn item_doc_lines(source: &str, item: &RustItem) -> Vec<String> {
+    source[item.start..line_offset(source, item.line)]
+        .lines()
+        .filter_map(|line: &str| {
+            let doc: &str = line.trim_start().strip_prefix("///")?;
+            (!doc.starts_with('/')).then(|| doc.strip_prefix(' ').unwrap_or(doc).to_string())
+        })
+        .collect()
+}
+
+/// Parse the signature of a function item
+///
+/// # Arguments
+///
+/// - `&str`: Rust source code
+/// - `&RustItem`: Function item
+/// - `Option<&str>`: Implemented type of a method
+///
+/// # Returns
+///
+/// - `Option<FnSignature>`: Signature, None if it cannot be parsed
+fn item_signature(source: &str, item: &RustItem, self_type: Option<&str>) -> Option<FnSignature> {
+    let mut signature: FnSignature =
+        parse_fn_signature(&source[line_offset(source, item.line)..item.end])?;
+    signature.self_type = self_type.map(str::to_string);
+    Some(signature)
+}
+
+/// Check the docs of every function in a file
+///
+/// # Arguments
+///
+/// - `&Path`: Path reported in the findings
+/// - `&str`: Rust source code
+///
+/// # Returns
+///
+/// - `Vec<DocFinding>`: Issues in source order
+pub(crate) fn lint_docs_source(path: &Path, source: &str) -> Vec<DocFinding> {
+    let mut findings: Vec<DocFinding> = Vec::new();
+    for (item, self_type) in documented_fns(source) {
+        let Some(signature) = item_signature(source, &item, self_type.as_deref()) else {
+            continue;
+        };
+        for issue in check_fn_docs(&signature, &item_doc_lines(source, &item)) {
+            findings.push(DocFinding {
+                path: path.to_path_buf(),
+                line: item.line,
+                function: signature.name.clone(),
+                issue,
+            });
+        }
+    }
+    findings
+}
+
+/// Insert skeleton `# Arguments` and `# Returns` sections where docs do not match
+///
+/// # Arguments
+///
+/// - `&str`: Rust source code
+///
+/// # Returns
+///
+/// - `(String, usize)`: Updated source and the number of rewritten doc comments
+pub(crate) fn fix_docs_source(source: &str) -> (String, usize) {
+    let mut updated: String = source.to_string();
+    let mut fixed: usize = 0;
+    for (item, self_type) in documented_fns(source).iter().rev() {
+        let keyword_line: usize = line_offset(source, item.line);
+        let Some(signature) = item_signature(source, item, self_type.as_deref()) else {
+            continue;
+        };
+        let doc: Vec<String> = item_doc_lines(source, item);
+        if check_fn_docs(&signature, &doc).is_empty() {
+            continue;
+        }
+        let indent: &str = &source[keyword_line..]
+            [..source[keyword_line..].len() - source[keyword_line..].trim_start().len()];
+        let prefix: &str = &source[item.start..keyword_line];
+        let is_doc = |line: &str| -> bool {
+            line.trim_start().starts_with("///") && !line.trim_start().starts_with("////")
+        };
+        let rendered: String = render_fn_docs(&signature, &doc)
+            .iter()
+            .map(|line: &String| {
+                if line.is_empty() {
+                    format!("{indent}///\n")
+                } else {
+                    format!("{indent}/// {line}\n")
+                }
+            })
+            .collect();
+        let mut new_prefix: String = String::new();
+        let mut inserted: bool = false;
+        for line in prefix.split_inclusive('\n') {
+            if is_doc(line) {
+                if !inserted {
+                    new_prefix.push_str(&rendered);
+                    inserted = true;
+                }
+                continue;
+            }
+            if !inserted && line.trim_start().starts_with("#[") {
+                new_prefix.push_str(&rendered);
+                inserted = true;
+            }
+            new_prefix.push_str(line);
+        }
+        if !inserted {
+            new_prefix.push_str(&rendered);
+        }
+        updated.replace_range(item.start..keyword_line, &new_prefix);
+        fixed += 1;
+    }
+    (updated, fixed)
+}
+
+/// Check the function docs of every package
+///
+/// Files are discovered like fmt does, honouring `.gitignore` and the
+/// configured excludes. Test modules and integration tests are skipped.
+///
+/// # Arguments
+///
+/// - `&Args`: The parsed arguments
+///
+/// # Returns
+///
+/// - `Result<DocReport, LintError>`: Remaining issues and the number of fixed functions
+pub(crate) async fn execute_docs_lint(args: &Args) -> Result<DocReport, LintError> {
+    let manifest_path: &Path = Path::new(args.manifest_path.as_deref().unwrap_or("Cargo.toml"));
+    let packages: Vec<Package> = discover_packages(manifest_path)?;
+    let excludes: Vec<IgnoreRule> = load_fmt_excludes(manifest_path)?;
+    let workspace_root: PathBuf = manifest_dir(manifest_path);
+    let mut files: Vec<PathBuf> = Vec::new();
+    for package in &packages {
+        if args.packages.is_empty() || args.packages.contains(&package.name) {
+            find_package_rust_files(&workspace_root, &package.path, &excludes, &mut files).await?;
+        }
+    }
+    files.retain(|file: &PathBuf| {
+        file.file_name()
+            .is_none_or(|name: &std::ffi::OsStr| name != "test.rs")
+            && !file
+                .components()
+                .any(|component: std::path::Component| component.as_os_str() == "tests")
+    });
+    files.sort();
+    files.dedup();
+    let mut report: DocReport = DocReport::default();
+    for file in files {
+        let mut source: String = read_to_string(&file)?;
+        if args.fix {
+            let (updated, fixed): (String, usize) = fix_docs_source(&source);
+            if fixed > 0 {
+                write(&file, &updated)?;
+                report.fixed += fixed;
+                source = updated;
+            }
+        }
+        report.findings.extend(lint_docs_source(&file, &source));
+    }
+    Ok(report)
+}
+
+/// Format doc issues
+///
+/// # Arguments
+///
+/// - `&DocReport`: Docs lint result
+///
+/// # Returns
+///
+/// - `String`: One line per issue followed by a total line
+pub(crate) fn format_docs_report(report: &DocReport) -> String {
+    let mut output: String = String::new();
+    if report.fixed > 0 {
+        output.push_str(&format!(
+            "Inserted doc sections in {} functions\n",
+            report.fixed
+        ));
+    }
+    if report.findings.is_empty() {
+        output.push_str("No doc issues\n");
+        return output;
+    }
+    for finding in &report.findings {
+        let path: String = finding
+            .path
+            .strip_prefix(".")
+            .unwrap_or(&finding.path)
+            .display()
+            .to_string();
+        output.push_str(&format!(
+            "{path}:{}: `{}`: {}\n",
+            finding.line, finding.function, finding.issue
+        ));
+    }
+    output.push_str(&format!("{} doc issues\n", report.findings.len()));
+    output
+}
@@ -0,0 +1,142 @@
+use crate::*;
+
+impl FromStr for LintType {
+    type Err = LintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "clippy" => Ok(Self::Clippy),
+            "layout" => Ok(Self::Layout),
+            "docs" => Ok(Self::Docs),
+            _ => Err(LintError::InvalidLintType(s.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for LayoutIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MisplacedItem {
+                kind,
+                name,
+                expected,
+            } => write!(f, "{kind} `{name}` belongs in {expected}.rs"),
+            Self::MissingSubmodule(name) => {
+                write!(
+                    f,
+                    "module `{name}` is declared but {name}.rs does not exist"
+                )
+            }
+            Self::UndeclaredSubmodule(name) => write!(f, "{name}.rs is not declared in mod.rs"),
+            Self::ModRsMismatch => {
+                write!(f, "mod.rs does not declare and re-export its submodules")
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for DocIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingArguments => write!(f, "missing `# Arguments` section"),
+            Self::UnexpectedArguments => {
+                write!(f, "`# Arguments` section on a function without parameters")
+            }
+            Self::ArgumentCount {
+                documented,
+                expected,
+            } => write!(
+                f,
+                "`# Arguments` lists {documented} parameters but the function takes {expected}"
+            ),
+            Self::ArgumentType {
+                position,
+                documented,
+                expected,
+            } => write!(
+                f,
+                "argument {position} is documented as `{documented}` but is `{expected}`"
+            ),
+            Self::MissingReturns => write!(f, "missing `# Returns` section"),
+            Self::UnexpectedReturns => {
+                write!(f, "`# Returns` section on a function that returns nothing")
+            }
+            Self::ReturnType {
+                documented,
+                expected,
+            } => write!(
+                f,
+                "return value is documented as `{documented}` but is `{expected}`"
+            ),
+        }
+    }
+}
+
+impl JsonValue {
+    /// Get a field of an object
+    ///
+    /// # Arguments
+    ///
+    /// - `&str`: Field name
+    ///
+    /// # Returns
+    ///
+    /// - `Option<&JsonValue>`: Field value, None if missing or not an object
+    pub(crate) fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            Self::Object(fields) => fields
+                .iter()
+                .find(|(name, _): &&(String, JsonValue)| name == key)
+                .map(|(_, value): &(String, JsonValue)| value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a string
+    ///
+    /// # Returns
+    ///
+    /// - `Option<&str>`: String value, None for other kinds
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as an array
+    ///
+    /// # Returns
+    ///
+    /// - `Option<&[JsonValue]>`: Array items, None for other kinds
+    pub(crate) fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            Self::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a boolean
+    ///
+    /// # Returns
+    ///
+    /// - `Option<bool>`: Boolean value, None for other kinds
+    pub(crate) fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as an unsigned integer
+    ///
+    /// # Returns
+    ///
+    /// - `Option<u64>`: Integer value, None for other kinds or fractional numbers
+    pub(crate) fn as_u64(&self) -> Option<u64> {
+        match self {
+            Self::Number(value) if *value >= 0.0 && value.fract() == 0.0 => Some(*value as u64),
+            _ => None,
+        }
+    }
+}
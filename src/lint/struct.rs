@@ -0,0 +1,112 @@
+use crate::*;
+
+/// A diagnostic reported by clippy
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ClippyDiagnostic {
+    /// Lint name such as `clippy::needless_return`, `rustc` if there is none
+    pub lint: String,
+    /// Level such as `warning` or `error`
+    pub level: String,
+    /// Diagnostic message
+    pub message: String,
+    /// File of the primary span
+    pub file: String,
+    /// Line of the primary span
+    pub line: usize,
+}
+
+/// Result of a clippy lint run
+#[derive(Clone, Debug)]
+pub(crate) struct ClippyReport {
+    /// Diagnostics in the order clippy reported them
+    pub diagnostics: Vec<ClippyDiagnostic>,
+    /// Whether clippy exited successfully
+    pub success: bool,
+}
+
+/// A top-level item of a Rust file
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct RustItem {
+    /// Item keyword such as `struct`, `fn` or `impl`, `macro` for macro invocations
+    pub kind: String,
+    /// Item name, the implemented type for `impl` blocks, empty if there is none
+    pub name: String,
+    /// Line of the item keyword
+    pub line: usize,
+    /// Offset of the first doc comment or attribute of the item
+    pub start: usize,
+    /// Offset after the item and the rest of its last line
+    pub end: usize,
+}
+
+/// A layout issue found in a module directory
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LayoutFinding {
+    /// File the issue was found in
+    pub path: PathBuf,
+    /// Line of the issue, 0 for the whole file
+    pub line: usize,
+    /// The issue
+    pub issue: LayoutIssue,
+    /// Whether `--fix` can resolve the issue
+    pub fixable: bool,
+}
+
+/// Result of a layout lint run
+#[derive(Clone, Debug, Default)]
+pub(crate) struct LayoutReport {
+    /// Issues left after fixing
+    pub findings: Vec<LayoutFinding>,
+    /// Number of issues resolved by `--fix`
+    pub fixed: usize,
+}
+
+/// Parameter and return types of a function signature
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct FnSignature {
+    /// Function name
+    pub name: String,
+    /// Parameter types without the receiver
+    pub params: Vec<String>,
+    /// Return type, None for `()`
+    pub returns: Option<String>,
+    /// Implemented type that `Self` stands for in methods
+    pub self_type: Option<String>,
+}
+
+/// A doc issue found in a function
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct DocFinding {
+    /// File of the function
+    pub path: PathBuf,
+    /// Line of the function
+    pub line: usize,
+    /// Function name
+    pub function: String,
+    /// The issue
+    pub issue: DocIssue,
+}
+
+/// Result of a docs lint run
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DocReport {
+    /// Issues left after fixing
+    pub findings: Vec<DocFinding>,
+    /// Number of functions whose doc sections were rewritten by `--fix`
+    pub fixed: usize,
+}
+
+/// Declarations and re-exports of a mod.rs
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct ModRsLayout {
+    /// Submodules declared outside `#[cfg(test)]`, without `r#`
+    pub modules: Vec<String>,
+    /// Whether a `test` module is declared behind `#[cfg(test)]`
+    pub test: bool,
+    /// Submodules re-exported with a glob, without `r#`
+    pub exports: Vec<String>,
+    /// Visibility of the re-exports such as `pub(crate)`, empty if there are none
+    pub visibility: String,
+    /// Private `use` items kept as written
+    pub imports: Vec<String>,
+}
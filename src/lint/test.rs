@@ -0,0 +1,336 @@
+use crate::*;
+
+#[test]
+fn test_parse_json() {
+    let value: JsonValue =
+        parse_json(r#"{"a": [1, -2.5e1, true, null], "b": {"c": "x\"é\n"}}"#).unwrap();
+    assert_eq!(
+        value.get("a"),
+        Some(&JsonValue::Array(vec![
+            JsonValue::Number(1.0),
+            JsonValue::Number(-25.0),
+            JsonValue::Bool(true),
+            JsonValue::Null,
+        ]))
+    );
+    assert_eq!(
+        value
+            .get("b")
+            .and_then(|b: &JsonValue| b.get("c"))
+            .and_then(JsonValue::as_str),
+        Some("x\"é\n")
+    );
+    assert_eq!(parse_json("[1, 2"), None);
+    assert_eq!(parse_json("{} trailing"), None);
+}
+
+#[test]
+fn test_parse_clippy_diagnostics() {
+    let output: &str = concat!(
+        r#"{"reason":"compiler-artifact","target":{"name":"app"}}"#,
+        "\n",
+        r#"{"reason":"compiler-message","message":{"message":"unneeded `return` statement","level":"warning","code":{"code":"clippy::needless_return","explanation":null},"spans":[{"file_name":"src/lib.rs","line_start":3,"is_primary":true}]}}"#,
+        "\n",
+        r#"{"reason":"compiler-message","message":{"message":"unneeded `return` statement","level":"warning","code":{"code":"clippy::needless_return","explanation":null},"spans":[{"file_name":"src/lib.rs","line_start":3,"is_primary":true}]}}"#,
+        "\n",
+        r#"{"reason":"compiler-message","message":{"message":"unused variable: `x`","level":"error","code":null,"spans":[{"file_name":"src/main.rs","line_start":5,"is_primary":false},{"file_name":"src/main.rs","line_start":7,"is_primary":true}]}}"#,
+        "\n",
+        r#"{"reason":"compiler-message","message":{"message":"1 warning emitted","level":"warning","code":null,"spans":[]}}"#,
+        "\n",
+        r#"{"reason":"build-finished","success":false}"#,
+    );
+    let diagnostics: Vec<ClippyDiagnostic> = parse_clippy_diagnostics(output);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].lint, "clippy::needless_return");
+    assert_eq!(diagnostics[1].lint, "rustc");
+    assert_eq!(diagnostics[1].line, 7);
+    assert_eq!(
+        format_lint_summary(&diagnostics),
+        "clippy::needless_return (1)\n  src/lib.rs:3: warning: unneeded `return` statement\nrustc (1)\n  src/main.rs:7: error: unused variable: `x`\n1 warnings and 1 errors in 2 lints\n"
+    );
+}
+
+#[test]
+fn test_parse_rust_items() {
+    let source: &str = r#"use super::*;
+
+/// A user
+#[derive(Debug)]
+pub(crate) struct User {
+    name: String,
+}
+
+// "}" in a comment
+impl<T: Clone> Default for Wrapper<T> where T: Default {
+    fn default() -> Self {
+        Self(T::default())
+    }
+}
+
+pub(crate) const fn answer() -> [u8; 2] {
+    [4, 2]
+}
+
+pub(crate) const LIMIT: Limit = Limit { max: 1 };
+"#;
+    let items: Vec<RustItem> = parse_rust_items(source);
+    let kinds: Vec<(&str, &str, usize)> = items
+        .iter()
+        .map(|item: &RustItem| (item.kind.as_str(), item.name.as_str(), item.line))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("use", "", 1),
+            ("struct", "User", 5),
+            ("impl", "Default for Wrapper<T>", 10),
+            ("fn", "answer", 16),
+            ("const", "LIMIT", 20),
+        ]
+    );
+    assert!(source[items[1].start..items[1].end].starts_with("/// A user\n#[derive(Debug)]"));
+    assert!(source[items[2].start..items[2].end].starts_with("// \"}\" in a comment\nimpl"));
+    assert!(source[items[4].start..items[4].end].ends_with("{ max: 1 };\n"));
+}
+
+#[test]
+fn test_lint_layout_module() {
+    let dir: PathBuf = PathBuf::from("./tmp/test_lint_layout/src/user");
+    let _ = std::fs::remove_dir_all("./tmp/test_lint_layout");
+    create_dir_all(&dir).unwrap();
+    write(dir.join("mod.rs"), "mod r#fn;\nmod r#enum;\n").unwrap();
+    write(
+        dir.join("fn.rs"),
+        "use super::*;\n\npub fn build() -> User {\n    User\n}\n\n/// A user\npub struct User;\n",
+    )
+    .unwrap();
+    write(dir.join("struct.rs"), "use super::*;\n").unwrap();
+    let findings: Vec<LayoutFinding> = lint_layout_module(&dir).unwrap();
+    let issues: Vec<(String, LayoutIssue)> = findings
+        .iter()
+        .map(|finding: &LayoutFinding| {
+            (
+                finding
+                    .path
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string(),
+                finding.issue.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        issues,
+        vec![
+            (
+                "mod.rs".to_string(),
+                LayoutIssue::MissingSubmodule("enum".to_string())
+            ),
+            (
+                "mod.rs".to_string(),
+                LayoutIssue::UndeclaredSubmodule("struct".to_string())
+            ),
+            (
+                "fn.rs".to_string(),
+                LayoutIssue::MisplacedItem {
+                    kind: "struct".to_string(),
+                    name: "User".to_string(),
+                    expected: "struct".to_string(),
+                }
+            ),
+        ]
+    );
+    assert!(
+        findings
+            .iter()
+            .all(|finding: &LayoutFinding| finding.fixable)
+    );
+    let args: Args = Args {
+        manifest_path: Some("./tmp/test_lint_layout/Cargo.toml".to_string()),
+        fix: true,
+        ..parse_args_from(&["hyperlane-cli".to_string()])
+    };
+    write(
+        "./tmp/test_lint_layout/Cargo.toml",
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    let report: LayoutReport = execute_layout_lint(&args).unwrap();
+    assert!(report.findings.is_empty(), "{:?}", report.findings);
+    assert_eq!(report.fixed, 3);
+    assert_eq!(
+        read_to_string(dir.join("mod.rs")).unwrap(),
+        "mod r#enum;\nmod r#fn;\nmod r#struct;\n\npub(crate) use {r#enum::*, r#fn::*, r#struct::*};\n"
+    );
+    assert_eq!(
+        read_to_string(dir.join("fn.rs")).unwrap(),
+        "use super::*;\n\npub fn build() -> User {\n    User\n}\n"
+    );
+    assert_eq!(
+        read_to_string(dir.join("struct.rs")).unwrap(),
+        "use super::*;\n\n/// A user\npub struct User;\n"
+    );
+    assert_eq!(
+        read_to_string(dir.join("enum.rs")).unwrap(),
+        LAYOUT_FILE_HEADER
+    );
+}
+
+#[test]
+fn test_lint_layout_module_reads_mod_rs_by_meaning() {
+    let dir: PathBuf = PathBuf::from("./tmp/test_lint_layout_mod_rs/src/user");
+    let _ = std::fs::remove_dir_all("./tmp/test_lint_layout_mod_rs");
+    create_dir_all(&dir).unwrap();
+    for module in ["fn", "impl", "struct", "test"] {
+        write(dir.join(format!("{module}.rs")), "use super::*;\n").unwrap();
+    }
+    let correct: &[&str] = &[
+        "mod r#fn;\nmod r#impl;\nmod r#struct;\n\n#[cfg(test)]\nmod test;\n\npub(crate) use {r#fn::*, r#struct::*};\n",
+        "#[cfg(test)]\nmod test;\nmod r#struct;\nmod r#impl;\nmod r#fn;\n\npub use {\n    r#struct::*,\n    r#fn::*,\n};\n\nuse super::*;\n",
+        &render_mod_rs(&["fn", "impl", "struct", "test"]),
+    ];
+    for source in correct {
+        write(dir.join("mod.rs"), source).unwrap();
+        assert!(lint_layout_module(&dir).unwrap().is_empty(), "{source}");
+    }
+    write(
+        dir.join("mod.rs"),
+        "mod r#fn;\nmod r#impl;\nmod r#struct;\n\n#[cfg(test)]\nmod test;\n\npub(crate) use r#fn::*;\n",
+    )
+    .unwrap();
+    let findings: Vec<LayoutFinding> = lint_layout_module(&dir).unwrap();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].issue, LayoutIssue::ModRsMismatch);
+    assert!(findings[0].fixable);
+    fix_layout_module(&dir, &findings).unwrap();
+    assert_eq!(
+        read_to_string(dir.join("mod.rs")).unwrap(),
+        "mod r#fn;\nmod r#impl;\nmod r#struct;\n\n#[cfg(test)]\nmod test;\n\npub(crate) use {r#fn::*, r#struct::*};\n"
+    );
+    let unparsable: &str = "mod r#fn;\nmod r#impl;\nmod r#struct;\n\n#[cfg(test)]\nmod test;\n\npub(crate) use r#fn::build;\n";
+    write(dir.join("mod.rs"), unparsable).unwrap();
+    let findings: Vec<LayoutFinding> = lint_layout_module(&dir).unwrap();
+    assert_eq!(findings.len(), 1);
+    assert!(!findings[0].fixable);
+    fix_layout_module(&dir, &findings).unwrap();
+    assert_eq!(read_to_string(dir.join("mod.rs")).unwrap(), unparsable);
+}
+
+#[test]
+fn test_parse_fn_signature() {
+    assert_eq!(
+        parse_fn_signature(
+            "pub(crate) async fn load<'a, T: Fn() -> u8>(&'a self, path: &'a Path, map: HashMap<String, Vec<T>>) -> Result<Self, FmtError>\nwhere\n    T: Clone,\n{"
+        ),
+        Some(FnSignature {
+            name: "load".to_string(),
+            params: vec![
+                "&'a Path".to_string(),
+                "HashMap<String, Vec<T>>".to_string()
+            ],
+            returns: Some("Result<Self, FmtError>".to_string()),
+            self_type: None,
+        })
+    );
+    let signature: FnSignature = parse_fn_signature("fn run(mut self) {}").unwrap();
+    assert!(signature.params.is_empty());
+    assert_eq!(signature.returns, None);
+}
+
+#[test]
+fn test_check_fn_docs() {
+    let signature: FnSignature = FnSignature {
+        name: "read".to_string(),
+        params: vec!["&std::path::Path".to_string(), "usize".to_string()],
+        returns: Some("Vec<Line<'a>>".to_string()),
+        self_type: None,
+    };
+    let doc = |text: &str| -> Vec<String> { text.lines().map(str::to_string).collect() };
+    assert!(
+        check_fn_docs(
+            &signature,
+            &doc("Read\n\n# Arguments\n\n- `&Path`: File\n- `usize`: Limit\n\n# Returns\n\n- `Vec<Line>`: Lines")
+        )
+        .is_empty()
+    );
+    assert_eq!(
+        check_fn_docs(&signature, &doc("Read")),
+        vec![DocIssue::MissingArguments, DocIssue::MissingReturns]
+    );
+    assert_eq!(
+        check_fn_docs(
+            &signature,
+            &doc("# Arguments\n\n- `&Path`: File\n\n# Returns\n\n- `Vec<String>`: Lines")
+        ),
+        vec![
+            DocIssue::ArgumentCount {
+                documented: 1,
+                expected: 2
+            },
+            DocIssue::ReturnType {
+                documented: "Vec<String>".to_string(),
+                expected: "Vec<Line<'a>>".to_string()
+            }
+        ]
+    );
+    assert_eq!(
+        check_fn_docs(
+            &FnSignature {
+                name: "new".to_string(),
+                params: Vec::new(),
+                returns: None,
+                self_type: Some("Config".to_string()),
+            },
+            &doc("# Arguments\n\n- `u8`: Unused\n\n# Returns\n\n- `Self`: Config")
+        ),
+        vec![DocIssue::UnexpectedArguments, DocIssue::UnexpectedReturns]
+    );
+    assert!(
+        check_fn_docs(
+            &FnSignature {
+                name: "new".to_string(),
+                params: vec!["Self".to_string()],
+                returns: Some("Self".to_string()),
+                self_type: Some("Config".to_string()),
+            },
+            &doc("# Arguments\n\n- `Config`: Base\n\n# Returns\n\n- `Self`: Config")
+        )
+        .is_empty()
+    );
+}
+
+#[test]
+fn test_fix_docs_source() {
+    let source: &str = "use super::*;\n\n/// Join two names\n///\n/// # Arguments\n///\n/// - `&str`: First name\n///\n/// # Examples\n///\n/// Joined with a space\n#[inline]\npub fn join(first: &str, second: &str) -> String {\n    format!(\"{first} {second}\")\n}\n\nimpl User {\n    /// Greet\n    pub fn greet(&self) {}\n\n    pub fn name(&self) -> &str {\n        &self.name\n    }\n}\n\nimpl Display for User {\n    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {\n        Ok(())\n    }\n}\n\n#[test]\nfn test_join() {}\n";
+    let findings: Vec<DocFinding> = lint_docs_source(Path::new("fn.rs"), source);
+    assert_eq!(
+        findings
+            .iter()
+            .map(|finding: &DocFinding| (
+                finding.line,
+                finding.function.as_str(),
+                finding.issue.clone()
+            ))
+            .collect::<Vec<(usize, &str, DocIssue)>>(),
+        vec![
+            (
+                13,
+                "join",
+                DocIssue::ArgumentCount {
+                    documented: 1,
+                    expected: 2
+                }
+            ),
+            (13, "join", DocIssue::MissingReturns),
+            (21, "name", DocIssue::MissingReturns),
+        ]
+    );
+    let (fixed, count): (String, usize) = fix_docs_source(source);
+    assert_eq!(count, 2);
+    assert_eq!(
+        fixed,
+        "use super::*;\n\n/// Join two names\n///\n/// # Arguments\n///\n/// - `&str`: First name\n/// - `&str`:\n///\n/// # Returns\n///\n/// - `String`:\n///\n/// # Examples\n///\n/// Joined with a space\n#[inline]\npub fn join(first: &str, second: &str) -> String {\n    format!(\"{first} {second}\")\n}\n\nimpl User {\n    /// Greet\n    pub fn greet(&self) {}\n\n    /// # Returns\n    ///\n    /// - `&str`:\n    pub fn name(&self) -> &str {\n        &self.name\n    }\n}\n\nimpl Display for User {\n    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {\n        Ok(())\n    }\n}\n\n#[test]\nfn test_join() {}\n"
+    );
+    assert!(lint_docs_source(Path::new("fn.rs"), &fixed).is_empty());
+}
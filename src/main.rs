@@ -5,21 +5,29 @@
 mod bump;
 mod command;
 mod config;
+mod exec;
 mod fmt;
+mod git;
+mod graph;
 mod help;
+mod hook;
+mod info;
+mod lint;
 mod new;
 mod publish;
+mod release;
 mod template;
 mod version;
 mod watch;
+mod workspace;
 
 pub(crate) use {
-    bump::*, command::*, config::*, fmt::*, help::*, new::*, publish::*, template::*, version::*,
-    watch::*,
+    bump::*, command::*, config::*, exec::*, fmt::*, git::*, graph::*, help::*, hook::*, info::*,
+    lint::*, new::*, publish::*, release::*, template::*, version::*, watch::*, workspace::*,
 };
 
 pub(crate) use std::{
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet},
     env::args,
     fs::{create_dir_all, read_to_string, write},
     path::{Path, PathBuf},
@@ -30,7 +38,12 @@ pub(crate) use std::{
 
 pub(crate) use {
     regex::{Captures, Regex},
-    tokio::{process::Command, sync::Mutex},
+    tokio::{
+        process::Command,
+        sync::Semaphore,
+        task::JoinSet,
+        time::{Duration, Instant},
+    },
 };
 
 #[tokio::main]
@@ -38,9 +51,23 @@ async fn main() {
     let args: Args = parse_args();
     match args.command {
         CommandType::Fmt => {
-            if let Err(error) = execute_fmt(&args).await {
-                eprintln!("fmt failed: {error}");
-                exit(1);
+            let manifest_path: &str = args.manifest_path.as_deref().unwrap_or("Cargo.toml");
+            let result: Result<(), FmtError> = run_with_hooks(
+                HookContext::new("fmt"),
+                Path::new(manifest_path),
+                execute_fmt(&args),
+            )
+            .await;
+            match result {
+                Ok(()) => {}
+                Err(error @ FmtError::CheckFailed(_)) => {
+                    eprintln!("fmt failed: {error}");
+                    exit(FMT_CHECK_EXIT_CODE);
+                }
+                Err(error) => {
+                    eprintln!("fmt failed: {error}");
+                    exit(1);
+                }
             }
         }
         CommandType::Watch => {
@@ -54,26 +81,66 @@ async fn main() {
                 .manifest_path
                 .unwrap_or_else(|| "Cargo.toml".to_string());
             let bump_type: BumpVersionType = args.bump_type.unwrap_or(BumpVersionType::Patch);
-            match execute_bump(&manifest_path, &bump_type) {
-                Ok(new_version) => {
-                    println!("Version bumped to {new_version}");
-                }
-                Err(error) => {
-                    eprintln!("bump failed: {error}");
-                    exit(1);
-                }
+            let result: Result<(), Box<dyn std::error::Error>> = if args.workspace {
+                run_with_hooks_outcome(
+                    HookContext::new("bump"),
+                    Path::new(&manifest_path),
+                    async { execute_bump_workspace(&manifest_path, &bump_type) },
+                    |bumped: &Vec<(String, String)>, context: &mut HookContext| {
+                        for (name, new_version) in bumped {
+                            println!("{name} bumped to {new_version}");
+                        }
+                        add_bumped_versions(bumped, context);
+                        true
+                    },
+                )
+                .await
+                .map(|_| ())
+            } else {
+                run_with_hooks_outcome(
+                    HookContext::new("bump"),
+                    Path::new(&manifest_path),
+                    async { execute_bump(&manifest_path, &bump_type) },
+                    |new_version: &String, context: &mut HookContext| {
+                        println!("Version bumped to {new_version}");
+                        context.new_version = Some(new_version.clone());
+                        true
+                    },
+                )
+                .await
+                .map(|_| ())
+            };
+            if let Err(error) = result {
+                eprintln!("bump failed: {error}");
+                exit(1);
             }
         }
         CommandType::Publish => {
             let manifest_path: String = args
                 .manifest_path
+                .clone()
                 .unwrap_or_else(|| "Cargo.toml".to_string());
-            let max_retries: u32 = args.max_retries;
-            match execute_publish(&manifest_path, max_retries).await {
+            let options: PublishOptions = match PublishOptions::try_from(&args) {
+                Ok(options) => options,
+                Err(error) => {
+                    eprintln!("publish failed: {error}");
+                    exit(1);
+                }
+            };
+            let result: Result<Vec<PublishResult>, PublishError> = run_with_hooks_outcome(
+                HookContext::new("publish"),
+                Path::new(&manifest_path),
+                execute_publish(&manifest_path, &options),
+                |results: &Vec<PublishResult>, _: &mut HookContext| {
+                    results.iter().all(PublishResult::in_registry)
+                },
+            )
+            .await;
+            match result {
                 Ok(results) => {
                     let failed_count: usize = results
                         .iter()
-                        .filter(|r: &&PublishResult| !r.success)
+                        .filter(|r: &&PublishResult| !r.in_registry())
                         .count();
                     if failed_count > 0 {
                         eprintln!("Publish completed with {failed_count} failures");
@@ -88,9 +155,121 @@ async fn main() {
                 }
             }
         }
+        CommandType::Release => {
+            let options: ReleaseOptions = match ReleaseOptions::try_from(&args) {
+                Ok(options) => options,
+                Err(error) => {
+                    eprintln!("release failed: {error}");
+                    exit(1);
+                }
+            };
+            match execute_release(&options).await {
+                Ok(released) => {
+                    println!("Released {} package(s)", released.len());
+                }
+                Err(error) => {
+                    eprintln!("release failed: {error}");
+                    exit(1);
+                }
+            }
+        }
+        CommandType::Exec => {
+            let manifest_path: &str = args.manifest_path.as_deref().unwrap_or("Cargo.toml");
+            match execute_exec(manifest_path, &ExecOptions::from(&args)).await {
+                Ok(results) => {
+                    print!("{}", format_exec_summary(&results));
+                    if results
+                        .iter()
+                        .any(|r: &ExecResult| r.status != ExecStatus::Success)
+                    {
+                        exit(1);
+                    }
+                }
+                Err(error) => {
+                    eprintln!("exec failed: {error}");
+                    exit(1);
+                }
+            }
+        }
+        CommandType::Info => {
+            let manifest_path: &str = args.manifest_path.as_deref().unwrap_or("Cargo.toml");
+            match execute_info(manifest_path, args.json).await {
+                Ok(info) => print!("{info}"),
+                Err(error) => {
+                    eprintln!("info failed: {error}");
+                    exit(1);
+                }
+            }
+        }
+        CommandType::Graph => {
+            let manifest_path: &str = args.manifest_path.as_deref().unwrap_or("Cargo.toml");
+            let options: GraphOptions = match GraphOptions::try_from(&args) {
+                Ok(options) => options,
+                Err(error) => {
+                    eprintln!("graph failed: {error}");
+                    exit(1);
+                }
+            };
+            match execute_graph(manifest_path, &options) {
+                Ok(graph) => print!("{graph}"),
+                Err(error) => {
+                    eprintln!("graph failed: {error}");
+                    exit(1);
+                }
+            }
+        }
+        CommandType::Lint => match args.lint_type.unwrap_or_default() {
+            LintType::Clippy => match execute_clippy_lint(&args).await {
+                Ok(report) => {
+                    print!("{}", format_lint_summary(&report.diagnostics));
+                    if !report.success {
+                        exit(1);
+                    }
+                }
+                Err(error) => {
+                    eprintln!("lint failed: {error}");
+                    exit(1);
+                }
+            },
+            LintType::Docs => match execute_docs_lint(&args).await {
+                Ok(report) => {
+                    print!("{}", format_docs_report(&report));
+                    if !report.findings.is_empty() {
+                        exit(1);
+                    }
+                }
+                Err(error) => {
+                    eprintln!("lint failed: {error}");
+                    exit(1);
+                }
+            },
+            LintType::Layout => match execute_layout_lint(&args) {
+                Ok(report) => {
+                    print!("{}", format_layout_report(&report));
+                    if !report.findings.is_empty() {
+                        exit(1);
+                    }
+                }
+                Err(error) => {
+                    eprintln!("lint failed: {error}");
+                    exit(1);
+                }
+            },
+        },
         CommandType::New => {
             if let Some(project_name) = args.project_name {
-                if let Err(error) = execute_new(&project_name).await {
+                let context: HookContext = HookContext {
+                    package: Some(project_name.clone()),
+                    target_dir: Some(PathBuf::from(&project_name)),
+                    ..HookContext::new("new")
+                };
+                let result: Result<(), NewError> = run_with_hooks(
+                    context,
+                    Path::new(args.manifest_path.as_deref().unwrap_or("Cargo.toml")),
+                    execute_new(&project_name),
+                )
+                .await;
+                if let Err(error) = result {
                     eprintln!("new failed: {error}");
                     exit(1);
                 }
@@ -124,9 +303,20 @@ async fn main() {
                 eprintln!("Error: Model type requires subtype (application|request|response)");
                 exit(1);
             }
-            if let Err(error) =
-                execute_template(template_type, &component_name, args.model_sub_type).await
-            {
+            let config: TemplateConfig =
+                TemplateConfig::new(template_type, component_name.clone(), args.model_sub_type);
+            let context: HookContext = HookContext {
+                package: Some(component_name.clone()),
+                target_dir: Some(config.target_dir()),
+                ..HookContext::new("template")
+            };
+            let result: Result<(), TemplateError> = run_with_hooks(
+                context,
+                Path::new(args.manifest_path.as_deref().unwrap_or("Cargo.toml")),
+                execute_template(template_type, &component_name, args.model_sub_type),
+            )
+            .await;
+            if let Err(error) = result {
                 eprintln!("template failed: {error}");
                 exit(1);
             }
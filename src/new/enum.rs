@@ -1,3 +1,5 @@
+use crate::*;
+
 /// Errors that can occur during project creation
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum NewError {
@@ -16,4 +18,7 @@ pub(crate) enum NewError {
     /// Invalid project name
     #[error("Invalid project name: {0}")]
     InvalidName(String),
+    /// Hook configuration could not be loaded or a pre hook failed
+    #[error(transparent)]
+    HookError(#[from] HookError),
 }
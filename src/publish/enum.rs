@@ -1,13 +1,104 @@
+use crate::*;
+
 /// Error types for publish operation
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum PublishError {
     /// Failed to parse Cargo.toml
     #[error("Failed to parse Cargo.toml")]
     ManifestParseError,
-    /// Circular dependency detected
-    #[error("Circular dependency detected")]
-    CircularDependency,
+    /// Circular dependency detected, with the cycle paths
+    #[error("Circular dependency detected: {}", format_cycles(.0))]
+    CircularDependency(Vec<Vec<String>>),
+    /// Selected package does not exist in the workspace
+    #[error("Package '{0}' not found in workspace")]
+    PackageNotFound(String),
+    /// Unknown publish report format
+    #[error("Invalid report format: {0}")]
+    InvalidReportFormat(String),
+    /// Pre-publish metadata lint found problems
+    #[error("{0} metadata problem(s) must be fixed before publishing")]
+    MetadataLint(usize),
+    /// Git command failed
+    #[error(transparent)]
+    GitError(#[from] GitError),
+    /// Hook configuration could not be loaded
+    #[error(transparent)]
+    HookError(#[from] HookError),
+    /// Workspace discovery failed
+    #[error(transparent)]
+    WorkspaceError(#[from] WorkspaceError),
     /// IO error
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 }
+
+/// Classified cause of a failed `cargo publish`
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub(crate) enum PublishFailure {
+    /// The version already exists in the registry
+    #[error("version already uploaded")]
+    AlreadyUploaded,
+    /// Required manifest metadata is missing or invalid
+    #[error("missing or invalid package metadata")]
+    MissingMetadata,
+    /// Verification build of the packaged crate failed
+    #[error("package verification failed")]
+    Verification,
+    /// Registry token is missing, invalid or lacks permission
+    #[error("authentication failed")]
+    Authentication,
+    /// A dependency version is not yet available in the index
+    #[error("dependency not found in registry index")]
+    DependencyNotFound,
+    /// Registry rejected the request with 429, optionally with a wait time
+    #[error("rate limited by registry")]
+    RateLimited(Option<std::time::Duration>),
+    /// Registry answered with a 5xx server error
+    #[error("registry server error")]
+    ServerError,
+    /// Transient network failure
+    #[error("network error")]
+    Network,
+    /// The pre-publish-package hook failed
+    #[error("pre-publish-package hook failed")]
+    HookFailed,
+    /// A local dependency failed to publish, so the package was not attempted
+    #[error("dependency failed to publish")]
+    DependencyFailed,
+    /// Failure that could not be classified
+    #[error("unknown failure")]
+    Unknown,
+}
+
+/// Section kind of a dependency
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub(crate) enum DependencyKind {
+    /// Declared in `[dependencies]`
+    Normal,
+    /// Declared in `[dev-dependencies]`
+    Dev,
+    /// Declared in `[build-dependencies]`
+    Build,
+}
+
+/// Outcome of publishing a package as shown in reports
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum PublishStatus {
+    /// The version was uploaded
+    Published,
+    /// The version already existed in the registry
+    Skipped,
+    /// Publishing failed
+    Failed,
+}
+
+/// File format of the publish report
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ReportFormat {
+    /// Machine-readable JSON document
+    Json,
+    /// JUnit XML with one test case per package
+    Junit,
+    /// Markdown summary for release notes or PR comments
+    Markdown,
+}
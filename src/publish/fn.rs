@@ -2,6 +2,9 @@ use crate::*;
 
 /// Discover all packages in the workspace
 ///
+/// Members are resolved with the shared workspace discovery, so globs and
+/// `workspace.exclude` behave like cargo.
+///
 /// # Arguments
 ///
 /// - `&Path`: Path to workspace root Cargo.toml
@@ -9,93 +12,56 @@ use crate::*;
 /// # Returns
 ///
 /// - `Result<Vec<Package>, PublishError>`: List of packages or error
-fn discover_packages(workspace_root: &Path) -> Result<Vec<Package>, PublishError> {
-    let content: String = read_to_string(workspace_root)?;
-    let doc: toml::Value =
-        toml::from_str(&content).map_err(|_| PublishError::ManifestParseError)?;
+pub(crate) fn discover_packages(workspace_root: &Path) -> Result<Vec<Package>, PublishError> {
+    let root_doc: toml::Value = read_manifest(workspace_root)?;
+    let workspace: Option<&toml::Value> = root_doc.get("workspace");
     let mut packages: Vec<Package> = Vec::new();
-    if let Some(workspace) = doc.get("workspace") {
-        if let Some(members) = workspace.get("members").and_then(|m| m.as_array()) {
-            for member in members {
-                if let Some(pattern) = member.as_str() {
-                    let base_path: &Path = workspace_root.parent().unwrap_or(workspace_root);
-                    expand_pattern(base_path, pattern, &mut packages)?;
-                }
-            }
-        }
-    }
-    if packages.is_empty() {
-        let package: Package = read_single_package(workspace_root)?;
-        packages.push(package);
+    for dir in discover_member_dirs(workspace_root)? {
+        packages.push(read_package_manifest(&dir.join("Cargo.toml"), workspace)?);
     }
     Ok(packages)
 }
 
-/// Expand glob pattern to find package directories
+/// Discover the names of the workspace `default-members`
 ///
 /// # Arguments
 ///
-/// - `&Path`: Base path for expansion
-/// - `&str`: Glob pattern
-/// - `&mut Vec<Package>`: Output vector for found packages
+/// - `&Path`: Path to workspace root Cargo.toml
 ///
 /// # Returns
 ///
-/// - `Result<(), PublishError>`: Success or error
-fn expand_pattern(
-    base_path: &Path,
-    pattern: &str,
-    packages: &mut Vec<Package>,
-) -> Result<(), PublishError> {
-    if pattern.contains('*') {
-        let parent: &Path = Path::new(pattern).parent().unwrap_or(Path::new("."));
-        let full_parent: PathBuf = base_path.join(parent);
-        if full_parent.is_dir() {
-            for entry in std::fs::read_dir(&full_parent)? {
-                let entry: std::fs::DirEntry = entry?;
-                let path: PathBuf = entry.path();
-                if path.is_dir() {
-                    let cargo_toml: PathBuf = path.join("Cargo.toml");
-                    if cargo_toml.exists() {
-                        let package: Package = read_package_manifest(&cargo_toml)?;
-                        packages.push(package);
-                    }
-                }
-            }
-        }
-    } else {
-        let cargo_toml: PathBuf = base_path.join(pattern).join("Cargo.toml");
-        if cargo_toml.exists() {
-            let package: Package = read_package_manifest(&cargo_toml)?;
-            packages.push(package);
-        }
+/// - `Result<Option<Vec<String>>, PublishError>`: Package names if `default-members` is set
+pub(crate) fn discover_default_members(
+    workspace_root: &Path,
+) -> Result<Option<Vec<String>>, PublishError> {
+    let Some(dirs) = discover_default_member_dirs(workspace_root)? else {
+        return Ok(None);
+    };
+    let root_doc: toml::Value = read_manifest(workspace_root)?;
+    let mut names: Vec<String> = Vec::new();
+    for dir in dirs {
+        names.push(read_package_manifest(&dir.join("Cargo.toml"), root_doc.get("workspace"))?.name);
     }
-    Ok(())
-}
-
-/// Read a single package (non-workspace mode)
-///
-/// # Arguments
-///
-/// - `&Path`: Path to Cargo.toml
-///
-/// # Returns
-///
-/// - `Result<Package, PublishError>`: Package info or error
-fn read_single_package(manifest_path: &Path) -> Result<Package, PublishError> {
-    read_package_manifest(manifest_path)
+    Ok(Some(names))
 }
 
 /// Read package manifest and extract information
 ///
+/// Fields inherited with `workspace = true` are resolved against the root
+/// `[workspace]` table.
+///
 /// # Arguments
 ///
 /// - `&Path`: Path to package Cargo.toml
+/// - `Option<&toml::Value>`: Root `[workspace]` table if any
 ///
 /// # Returns
 ///
 /// - `Result<Package, PublishError>`: Package info or error
-fn read_package_manifest(manifest_path: &Path) -> Result<Package, PublishError> {
+fn read_package_manifest(
+    manifest_path: &Path,
+    workspace: Option<&toml::Value>,
+) -> Result<Package, PublishError> {
     let content: String = read_to_string(manifest_path)?;
     let doc: toml::Value =
         toml::from_str(&content).map_err(|_| PublishError::ManifestParseError)?;
@@ -105,71 +71,160 @@ fn read_package_manifest(manifest_path: &Path) -> Result<Package, PublishError>
         .and_then(|n: &toml::Value| n.as_str())
         .ok_or(PublishError::ManifestParseError)?
         .to_string();
-    let version: String = package_table
-        .get("version")
-        .and_then(|v: &toml::Value| v.as_str())
-        .ok_or(PublishError::ManifestParseError)?
-        .to_string();
-    let path: PathBuf = manifest_path
-        .parent()
-        .filter(|p: &&Path| !p.as_os_str().is_empty())
-        .map_or_else(|| PathBuf::from("."), |p: &Path| p.to_path_buf());
-    let local_dependencies: Vec<String> = extract_local_dependencies(&doc, manifest_path)?;
+    let version: String = match package_table.get("version") {
+        Some(toml::Value::Table(_)) => workspace
+            .and_then(|w: &toml::Value| w.get("package"))
+            .and_then(|p: &toml::Value| p.get("version")),
+        version => version,
+    }
+    .and_then(|v: &toml::Value| v.as_str())
+    .ok_or(PublishError::ManifestParseError)?
+    .to_string();
+    let path: PathBuf = manifest_dir(manifest_path);
+    let workspace_dependencies: Option<&toml::Table> = workspace
+        .and_then(|w: &toml::Value| w.get("dependencies"))
+        .and_then(|d: &toml::Value| d.as_table());
+    let dependencies: Vec<LocalDependency> =
+        extract_local_dependencies(&doc, workspace_dependencies);
+    let mut local_dependencies: Vec<String> = Vec::new();
+    for dependency in &dependencies {
+        let stripped_on_publish: bool =
+            dependency.kind == DependencyKind::Dev && !dependency.has_version;
+        if !stripped_on_publish && !local_dependencies.contains(&dependency.name) {
+            local_dependencies.push(dependency.name.clone());
+        }
+    }
+    let publish_registries: Option<Vec<String>> = match package_table.get("publish") {
+        Some(toml::Value::Boolean(false)) => Some(Vec::new()),
+        Some(toml::Value::Array(registries)) => Some(
+            registries
+                .iter()
+                .filter_map(|r: &toml::Value| r.as_str().map(|s: &str| s.to_string()))
+                .collect(),
+        ),
+        _ => None,
+    };
     Ok(Package {
         name,
         version,
         path,
         local_dependencies,
+        dependencies,
+        publish_registries,
+    })
+}
+
+/// Resolve a dependency entry to a local dependency
+///
+/// `workspace = true` entries are looked up in `[workspace.dependencies]`;
+/// only entries that end up with a `path` are local. `package` renames are
+/// resolved to the real package name.
+///
+/// # Arguments
+///
+/// - `&str`: Dependency key in the manifest
+/// - `&toml::Value`: Dependency value
+/// - `Option<&toml::Table>`: Root `[workspace.dependencies]` table
+/// - `DependencyKind`: Section kind of the dependency
+/// - `Option<&str>`: Target cfg for target-specific sections
+///
+/// # Returns
+///
+/// - `Option<LocalDependency>`: Local dependency if the entry refers to a path
+fn resolve_local_dependency(
+    key: &str,
+    value: &toml::Value,
+    workspace_dependencies: Option<&toml::Table>,
+    kind: DependencyKind,
+    target: Option<&str>,
+) -> Option<LocalDependency> {
+    let table: &toml::Table = value.as_table()?;
+    let inherits: bool = table
+        .get("workspace")
+        .and_then(|w: &toml::Value| w.as_bool())
+        .unwrap_or(false);
+    let resolved: &toml::Table = if inherits {
+        workspace_dependencies?.get(key)?.as_table()?
+    } else {
+        table
+    };
+    resolved.get("path")?;
+    let name: String = resolved
+        .get("package")
+        .and_then(|p: &toml::Value| p.as_str())
+        .unwrap_or(key)
+        .to_string();
+    Some(LocalDependency {
+        name,
+        kind,
+        has_version: resolved.get("version").is_some(),
+        target: target.map(|t: &str| t.to_string()),
     })
 }
 
 /// Extract local workspace dependencies from manifest
 ///
+/// Covers `[dependencies]`, `[dev-dependencies]`, `[build-dependencies]` and
+/// their `[target.'cfg(..)'.*]` variants.
+///
 /// # Arguments
 ///
 /// - `&toml::Value`: Parsed manifest
-/// - `&Path`: Path to manifest for resolving relative paths
+/// - `Option<&toml::Table>`: Root `[workspace.dependencies]` table
 ///
 /// # Returns
 ///
-/// - `Result<Vec<String>, PublishError>`: List of local dependency names
-fn extract_local_dependencies(
+/// - `Vec<LocalDependency>`: Local dependencies of the package
+pub(crate) fn extract_local_dependencies(
     doc: &toml::Value,
-    _manifest_path: &Path,
-) -> Result<Vec<String>, PublishError> {
-    let mut deps: Vec<String> = Vec::new();
-    let dep_sections: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
-    for section in &dep_sections {
-        if let Some(table) = doc.get(section).and_then(|s| s.as_table()) {
-            for (dep_name, dep_value) in table {
-                let is_local: bool = match dep_value {
-                    toml::Value::Table(t) => {
-                        t.get("path").is_some()
-                            || t.get("workspace")
-                                .and_then(|w| w.as_bool())
-                                .unwrap_or(false)
-                    }
-                    _ => false,
-                };
-                if is_local {
-                    deps.push(dep_name.clone());
+    workspace_dependencies: Option<&toml::Table>,
+) -> Vec<LocalDependency> {
+    let dep_sections: [(&str, DependencyKind); 3] = [
+        ("dependencies", DependencyKind::Normal),
+        ("dev-dependencies", DependencyKind::Dev),
+        ("build-dependencies", DependencyKind::Build),
+    ];
+    let mut scopes: Vec<(Option<&str>, &toml::Value)> = vec![(None, doc)];
+    if let Some(targets) = doc.get("target").and_then(|t: &toml::Value| t.as_table()) {
+        for (target, target_doc) in targets {
+            scopes.push((Some(target.as_str()), target_doc));
+        }
+    }
+    let mut deps: Vec<LocalDependency> = Vec::new();
+    for (target, scope) in scopes {
+        for (section, kind) in &dep_sections {
+            let Some(table) = scope.get(section).and_then(|s: &toml::Value| s.as_table()) else {
+                continue;
+            };
+            for (dep_key, dep_value) in table {
+                if let Some(dependency) = resolve_local_dependency(
+                    dep_key,
+                    dep_value,
+                    workspace_dependencies,
+                    *kind,
+                    target,
+                ) {
+                    deps.push(dependency);
                 }
             }
         }
     }
-    Ok(deps)
+    deps
 }
 
-/// Perform topological sort on packages based on dependencies
+/// Group packages into topological levels using Kahn's algorithm
+///
+/// Packages within a level do not depend on each other, and every package
+/// only depends on packages from earlier levels.
 ///
 /// # Arguments
 ///
-/// - `&[Package]`: List of packages to sort
+/// - `&[Package]`: List of packages to group
 ///
 /// # Returns
 ///
-/// - `Result<Vec<Package>, PublishError>`: Sorted packages or error if circular
-fn topological_sort(packages: &[Package]) -> Result<Vec<Package>, PublishError> {
+/// - `Result<Vec<Vec<Package>>, PublishError>`: Levels of packages or error if circular
+pub(crate) fn topological_levels(packages: &[Package]) -> Result<Vec<Vec<Package>>, PublishError> {
     let mut in_degree: HashMap<String, usize> = HashMap::new();
     let mut graph: HashMap<String, Vec<String>> = HashMap::new();
     let package_map: HashMap<String, Package> = packages
@@ -188,72 +243,499 @@ fn topological_sort(packages: &[Package]) -> Result<Vec<Package>, PublishError>
             }
         }
     }
-    let mut queue: VecDeque<String> = VecDeque::new();
-    for (name, degree) in &in_degree {
-        if *degree == 0 {
-            queue.push_back(name.clone());
-        }
-    }
-    let mut result: Vec<Package> = Vec::new();
-    while let Some(name) = queue.pop_front() {
-        if let Some(package) = package_map.get(&name) {
-            result.push(package.clone());
-        }
-        if let Some(dependents) = graph.get(&name) {
-            for dependent in dependents {
-                if let Some(degree) = in_degree.get_mut(dependent) {
-                    *degree -= 1;
-                    if *degree == 0 {
-                        queue.push_back(dependent.clone());
+    let mut current: Vec<String> = in_degree
+        .iter()
+        .filter(|(_, degree): &(&String, &usize)| **degree == 0)
+        .map(|(name, _): (&String, &usize)| name.clone())
+        .collect();
+    let mut levels: Vec<Vec<Package>> = Vec::new();
+    let mut visited: usize = 0;
+    while !current.is_empty() {
+        current.sort();
+        let mut next: Vec<String> = Vec::new();
+        let mut level: Vec<Package> = Vec::new();
+        for name in &current {
+            if let Some(package) = package_map.get(name) {
+                level.push(package.clone());
+            }
+            if let Some(dependents) = graph.get(name) {
+                for dependent in dependents {
+                    if let Some(degree) = in_degree.get_mut(dependent) {
+                        *degree -= 1;
+                        if *degree == 0 {
+                            next.push(dependent.clone());
+                        }
                     }
                 }
             }
         }
+        visited += level.len();
+        levels.push(level);
+        current = next;
+    }
+    if visited != packages.len() {
+        let remaining: Vec<String> = in_degree
+            .into_iter()
+            .filter(|(_, degree): &(String, usize)| *degree > 0)
+            .map(|(name, _): (String, usize)| name)
+            .collect();
+        return Err(PublishError::CircularDependency(find_dependency_cycles(
+            packages, &remaining,
+        )));
+    }
+    Ok(levels)
+}
+
+/// Sort packages in topological order
+///
+/// # Arguments
+///
+/// - `&[Package]`: List of packages to sort
+///
+/// # Returns
+///
+/// - `Result<Vec<Package>, PublishError>`: Packages with dependencies first or error if circular
+pub(crate) fn topological_sort(packages: &[Package]) -> Result<Vec<Package>, PublishError> {
+    Ok(topological_levels(packages)?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+/// Depth-first search for a dependency path leading back to the start package
+///
+/// # Arguments
+///
+/// - `&str`: Package the cycle starts at
+/// - `&str`: Package currently visited
+/// - `&HashMap<String, Vec<String>>`: Dependencies per package
+/// - `&mut Vec<String>`: Already visited packages
+/// - `&mut Vec<String>`: Current path
+///
+/// # Returns
+///
+/// - `bool`: True if the path closes a cycle
+fn find_cycle_from(
+    start: &str,
+    current: &str,
+    dependencies: &HashMap<String, Vec<String>>,
+    visited: &mut Vec<String>,
+    path: &mut Vec<String>,
+) -> bool {
+    path.push(current.to_string());
+    visited.push(current.to_string());
+    for dep in dependencies.get(current).into_iter().flatten() {
+        if dep == start {
+            path.push(dep.clone());
+            return true;
+        }
+        if !visited.contains(dep) && find_cycle_from(start, dep, dependencies, visited, path) {
+            return true;
+        }
+    }
+    path.pop();
+    false
+}
+
+/// Find the dependency cycles among packages left over by Kahn's algorithm
+///
+/// Reports one cycle per group of packages, each as a closed path such as
+/// `a -> b -> a`.
+///
+/// # Arguments
+///
+/// - `&[Package]`: All packages of the graph
+/// - `&[String]`: Packages that could not be ordered
+///
+/// # Returns
+///
+/// - `Vec<Vec<String>>`: Cycle paths
+pub(crate) fn find_dependency_cycles(
+    packages: &[Package],
+    remaining: &[String],
+) -> Vec<Vec<String>> {
+    let mut dependencies: HashMap<String, Vec<String>> = HashMap::new();
+    for package in packages {
+        if !remaining.contains(&package.name) {
+            continue;
+        }
+        let mut deps: Vec<String> = package
+            .local_dependencies
+            .iter()
+            .filter(|dep: &&String| remaining.contains(dep))
+            .cloned()
+            .collect();
+        deps.sort();
+        dependencies.insert(package.name.clone(), deps);
+    }
+    let mut starts: Vec<String> = remaining.to_vec();
+    starts.sort();
+    let mut covered: Vec<String> = Vec::new();
+    let mut cycles: Vec<Vec<String>> = Vec::new();
+    for start in &starts {
+        if covered.contains(start) {
+            continue;
+        }
+        let mut visited: Vec<String> = Vec::new();
+        let mut path: Vec<String> = Vec::new();
+        if find_cycle_from(start, start, &dependencies, &mut visited, &mut path) {
+            covered.extend(path.iter().cloned());
+            cycles.push(path);
+        }
+    }
+    cycles
+}
+
+/// Format dependency cycles for display
+///
+/// # Arguments
+///
+/// - `&[Vec<String>]`: Cycle paths
+///
+/// # Returns
+///
+/// - `String`: Cycles joined as `a -> b -> a; c -> d -> c`
+pub(crate) fn format_cycles(cycles: &[Vec<String>]) -> String {
+    cycles
+        .iter()
+        .map(|cycle: &Vec<String>| cycle.join(" -> "))
+        .collect::<Vec<String>>()
+        .join("; ")
+}
+
+/// Convert a civil date to days since the unix epoch
+///
+/// # Arguments
+///
+/// - `i64`: Year
+/// - `i64`: Month (1-12)
+/// - `i64`: Day of month
+///
+/// # Returns
+///
+/// - `i64`: Days since 1970-01-01
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year: i64 = if month <= 2 { year - 1 } else { year };
+    let era: i64 = year.div_euclid(400);
+    let year_of_era: i64 = year - era * 400;
+    let month_index: i64 = (month + 9) % 12;
+    let day_of_year: i64 = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era: i64 = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Parse the wait time a registry asks for before retrying
+///
+/// # Arguments
+///
+/// - `&str`: Cargo stderr output
+/// - `u64`: Current unix time in seconds
+///
+/// # Returns
+///
+/// - `Option<Duration>`: Time to wait if the output contains a hint
+pub(crate) fn parse_retry_after(stderr: &str, now: u64) -> Option<Duration> {
+    if let Some(captures) = RETRY_AFTER_SECONDS_REGEX.captures(stderr) {
+        let seconds: u64 = captures.get(1)?.as_str().parse().ok()?;
+        return Some(Duration::from_secs(seconds));
+    }
+    let captures: Captures<'_> = RETRY_AFTER_DATE_REGEX.captures(stderr)?;
+    let number = |index: usize| -> Option<i64> { captures.get(index)?.as_str().parse().ok() };
+    let month_name: String = captures.get(2)?.as_str().to_lowercase();
+    let month: i64 = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ]
+    .iter()
+    .position(|name: &&str| *name == month_name)? as i64
+        + 1;
+    let days: i64 = days_from_civil(number(3)?, month, number(1)?);
+    let timestamp: i64 = days * 86400 + number(4)? * 3600 + number(5)? * 60 + number(6)?;
+    let wait: u64 = u64::try_from(timestamp).ok()?.saturating_sub(now);
+    Some(Duration::from_secs(wait))
+}
+
+/// Classify cargo publish stderr output into a failure category
+///
+/// # Arguments
+///
+/// - `&str`: Cargo stderr output
+///
+/// # Returns
+///
+/// - `PublishFailure`: Classified failure
+pub(crate) fn classify_publish_failure(stderr: &str) -> PublishFailure {
+    let error_lines: Vec<&str> = stderr
+        .lines()
+        .skip_while(|line: &&str| !line.trim_start().starts_with("error"))
+        .collect();
+    let text: String = if error_lines.is_empty() {
+        stderr.to_lowercase()
+    } else {
+        error_lines.join("\n").to_lowercase()
+    };
+    let contains_any =
+        |patterns: &[&str]| -> bool { patterns.iter().any(|p: &&str| text.contains(p)) };
+    if contains_any(&["already uploaded", "already exists"]) {
+        return PublishFailure::AlreadyUploaded;
+    }
+    if contains_any(&["status 429", "too many requests", "published too many"]) {
+        let now: u64 = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d: Duration| d.as_secs());
+        return PublishFailure::RateLimited(parse_retry_after(stderr, now));
+    }
+    if contains_any(&[
+        "status 401",
+        "status 403",
+        "unauthorized",
+        "forbidden",
+        "no token found",
+        "cargo login",
+        "invalid token",
+        "authentication",
+    ]) {
+        return PublishFailure::Authentication;
+    }
+    if contains_any(&[
+        "missing or empty metadata",
+        "license expression",
+        "invalid manifest",
+        "invalid keyword",
+        "invalid category",
+    ]) {
+        return PublishFailure::MissingMetadata;
+    }
+    if contains_any(&["no matching package named", "failed to select a version"]) {
+        return PublishFailure::DependencyNotFound;
+    }
+    if contains_any(&["failed to verify package tarball", "could not compile"]) {
+        return PublishFailure::Verification;
     }
-    if result.len() != packages.len() {
-        return Err(PublishError::CircularDependency);
+    if contains_any(&[
+        "status 5",
+        "internal server error",
+        "bad gateway",
+        "service unavailable",
+        "gateway timeout",
+    ]) {
+        return PublishFailure::ServerError;
+    }
+    if contains_any(&[
+        "operation timed out",
+        "connection timed out",
+        "timeout was reached",
+        "failed to connect",
+        "connection refused",
+        "connection reset",
+        "connection was reset",
+        "could not resolve host",
+        "failed to resolve",
+        "recv failure",
+        "send failure",
+        "spurious network error",
+        "ssl_error_syscall",
+    ]) {
+        return PublishFailure::Network;
+    }
+    PublishFailure::Unknown
+}
+
+/// Compute the delay before the next retry attempt
+///
+/// # Arguments
+///
+/// - `u32`: Attempt number starting at 1
+/// - `&PublishFailure`: Failure of the previous attempt
+/// - `&PublishOptions`: Publish options holding backoff limits
+///
+/// # Returns
+///
+/// - `Duration`: Time to wait before retrying
+fn retry_delay(attempt: u32, failure: &PublishFailure, options: &PublishOptions) -> Duration {
+    if let PublishFailure::RateLimited(Some(wait)) = failure {
+        return *wait;
     }
-    Ok(result)
+    let backoff: u64 = 2_u64.saturating_pow(attempt).min(options.retry_max_delay);
+    let jitter: u64 = if options.retry_jitter == 0 {
+        0
+    } else {
+        let nanos: u64 = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d: Duration| u64::from(d.subsec_nanos()));
+        nanos % (options.retry_jitter + 1)
+    };
+    Duration::from_secs(backoff) + Duration::from_millis(jitter)
 }
 
 /// Publish a single package with retry logic
 ///
+/// Only transient failures are retried; fatal failures return immediately.
+///
 /// # Arguments
 ///
 /// - `&Package`: Package to publish
-/// - `u32`: Maximum retry attempts
+/// - `&PublishOptions`: Publish options holding retry settings
+/// - `&PublishSettings`: Cargo publish settings for the package
 ///
 /// # Returns
 ///
 /// - `PublishResult`: Result with success status and retry count
-async fn publish_package_with_retry(package: &Package, max_retries: u32) -> PublishResult {
+async fn publish_package_with_retry(
+    package: &Package,
+    options: &PublishOptions,
+    settings: &PublishSettings,
+) -> PublishResult {
+    let start: Instant = Instant::now();
     let mut attempt: u32 = 0;
-    let mut last_error: Option<String> = None;
-    while attempt <= max_retries {
-        match publish_single_package(package).await {
-            Ok(()) => {
-                return PublishResult {
-                    package_name: package.name.clone(),
-                    success: true,
-                    error: None,
-                    retries: attempt,
-                };
-            }
-            Err(error) => {
-                last_error = Some(error.to_string());
-                attempt += 1;
-                if attempt <= max_retries {
-                    tokio::time::sleep(tokio::time::Duration::from_secs(2_u64.pow(attempt))).await;
+    loop {
+        let error: Box<dyn std::error::Error + Send + Sync> =
+            match publish_single_package(package, settings).await {
+                Ok(()) => {
+                    return PublishResult {
+                        package_name: package.name.clone(),
+                        version: package.version.clone(),
+                        success: true,
+                        error: None,
+                        failure: None,
+                        retries: attempt,
+                        propagation_time: None,
+                        duration: start.elapsed(),
+                    };
                 }
-            }
+                Err(error) => error,
+            };
+        let message: String = error.to_string();
+        let failure: PublishFailure = classify_publish_failure(&message);
+        if !failure.is_retryable() || attempt >= options.max_retries {
+            return PublishResult {
+                package_name: package.name.clone(),
+                version: package.version.clone(),
+                success: false,
+                error: Some(message),
+                failure: Some(failure),
+                retries: attempt,
+                propagation_time: None,
+                duration: start.elapsed(),
+            };
+        }
+        attempt += 1;
+        let delay: Duration = retry_delay(attempt, &failure, options);
+        eprintln!(
+            "Publishing {} failed ({failure}), retrying in {:.1}s...",
+            package.name,
+            delay.as_secs_f64()
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Read the per-package publish settings from the manifest
+///
+/// Settings live in `[package.metadata.hyperlane-cli.publish]` with the keys
+/// `registry`, `index`, `features`, `no-verify`, `target` and `args`.
+///
+/// # Arguments
+///
+/// - `&Package`: Package whose manifest is read
+///
+/// # Returns
+///
+/// - `Result<PublishSettings, PublishError>`: Settings found in the manifest
+pub(crate) fn read_package_publish_settings(
+    package: &Package,
+) -> Result<PublishSettings, PublishError> {
+    let doc: toml::Value = read_manifest(&package.path.join("Cargo.toml"))?;
+    let Some(config) = doc
+        .get("package")
+        .and_then(|p: &toml::Value| p.get("metadata"))
+        .and_then(|m: &toml::Value| m.get(CONFIG_METADATA_KEY))
+        .and_then(|c: &toml::Value| c.get("publish"))
+    else {
+        return Ok(PublishSettings::default());
+    };
+    let string = |key: &str| -> Option<String> {
+        config
+            .get(key)
+            .and_then(|v: &toml::Value| v.as_str())
+            .map(|s: &str| s.to_string())
+    };
+    let list = |key: &str| -> Vec<String> {
+        match config.get(key) {
+            Some(toml::Value::String(value)) => value
+                .split([',', ' '])
+                .filter(|s: &&str| !s.is_empty())
+                .map(|s: &str| s.to_string())
+                .collect(),
+            Some(toml::Value::Array(values)) => values
+                .iter()
+                .filter_map(|v: &toml::Value| v.as_str().map(|s: &str| s.to_string()))
+                .collect(),
+            _ => Vec::new(),
         }
+    };
+    Ok(PublishSettings {
+        registry: string("registry"),
+        index: string("index"),
+        features: list("features"),
+        no_verify: config
+            .get("no-verify")
+            .and_then(|v: &toml::Value| v.as_bool())
+            .unwrap_or(false),
+        target: string("target"),
+        cargo_args: list("args"),
+    })
+}
+
+/// Resolve the effective publish settings of a package
+///
+/// # Arguments
+///
+/// - `&Package`: Package to publish
+/// - `&PublishOptions`: Publish options holding the command settings
+///
+/// # Returns
+///
+/// - `Result<PublishSettings, PublishError>`: Command settings overridden by package settings
+pub(crate) fn resolve_publish_settings(
+    package: &Package,
+    options: &PublishOptions,
+) -> Result<PublishSettings, PublishError> {
+    let package_settings: PublishSettings = read_package_publish_settings(package)?;
+    Ok(options.settings.merge(&package_settings))
+}
+
+/// Build the cargo arguments for publishing a package
+///
+/// # Arguments
+///
+/// - `&PublishSettings`: Cargo publish settings
+///
+/// # Returns
+///
+/// - `Vec<String>`: Arguments passed to `cargo`
+pub(crate) fn publish_command_args(settings: &PublishSettings) -> Vec<String> {
+    let mut cargo_args: Vec<String> = vec!["publish".to_string(), "--allow-dirty".to_string()];
+    if let Some(registry) = &settings.registry {
+        cargo_args.push("--registry".to_string());
+        cargo_args.push(registry.clone());
+    }
+    if let Some(index) = &settings.index {
+        cargo_args.push("--index".to_string());
+        cargo_args.push(index.clone());
+    }
+    if !settings.features.is_empty() {
+        cargo_args.push("--features".to_string());
+        cargo_args.push(settings.features.join(","));
+    }
+    if settings.no_verify {
+        cargo_args.push("--no-verify".to_string());
     }
-    PublishResult {
-        package_name: package.name.clone(),
-        success: false,
-        error: last_error,
-        retries: attempt - 1,
+    if let Some(target) = &settings.target {
+        cargo_args.push("--target".to_string());
+        cargo_args.push(target.clone());
     }
+    cargo_args.extend(settings.cargo_args.iter().cloned());
+    cargo_args
 }
 
 /// Execute cargo publish command for a single package
@@ -261,14 +743,17 @@ async fn publish_package_with_retry(package: &Package, max_retries: u32) -> Publ
 /// # Arguments
 ///
 /// - `&Package`: Package to publish
+/// - `&PublishSettings`: Cargo publish settings for the package
 ///
 /// # Returns
 ///
-/// - `Result<(), Box<dyn std::error::Error>>`: Success or error
-async fn publish_single_package(package: &Package) -> Result<(), Box<dyn std::error::Error>> {
+/// - `Result<(), Box<dyn std::error::Error + Send + Sync>>`: Success or error
+async fn publish_single_package(
+    package: &Package,
+    settings: &PublishSettings,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let output: std::process::Output = Command::new("cargo")
-        .arg("publish")
-        .arg("--allow-dirty")
+        .args(publish_command_args(settings))
         .current_dir(&package.path)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -282,45 +767,1192 @@ async fn publish_single_package(package: &Package) -> Result<(), Box<dyn std::er
     }
 }
 
-/// Execute publish command for all packages in workspace
+/// Look up the index URL of a named registry
+///
+/// Checks `CARGO_REGISTRIES_<NAME>_INDEX` and `[registries.<name>]` in
+/// `.cargo/config.toml` of the current directory.
 ///
 /// # Arguments
 ///
-/// - `&str`: Path to workspace Cargo.toml
-/// - `u32`: Maximum retry attempts per package
+/// - `&str`: Registry name
 ///
 /// # Returns
 ///
-/// - `Result<Vec<PublishResult>, PublishError>`: Results for all packages
-pub(crate) async fn execute_publish(
-    manifest_path: &str,
-    max_retries: u32,
-) -> Result<Vec<PublishResult>, PublishError> {
-    let path: &Path = Path::new(manifest_path);
-    let packages: Vec<Package> = discover_packages(path)?;
-    if packages.is_empty() {
-        return Ok(Vec::new());
+/// - `Option<String>`: Index URL if configured
+fn registry_index_url(registry: &str) -> Option<String> {
+    let env_name: String = format!(
+        "CARGO_REGISTRIES_{}_INDEX",
+        registry.to_uppercase().replace('-', "_")
+    );
+    if let Ok(index) = std::env::var(env_name) {
+        return Some(index);
     }
-    let sorted_packages: Vec<Package> = topological_sort(&packages)?;
-    let mut results: Vec<PublishResult> = Vec::new();
-    for package in sorted_packages {
-        println!("Publishing {} v{}...", package.name, package.version);
-        let result: PublishResult = publish_package_with_retry(&package, max_retries).await;
-        if result.success {
-            if result.retries == 0 {
-                println!("Successfully published {}", result.package_name,);
-            } else {
-                println!(
-                    "Successfully published {} (retried {} times)",
-                    result.package_name, result.retries
-                );
-            }
-        } else if let Some(error) = &result.error {
-            eprintln!("Failed to publish {}: {error}", result.package_name);
+    ["config.toml", "config"].iter().find_map(|file: &&str| {
+        let content: String = read_to_string(Path::new(".cargo").join(file)).ok()?;
+        let doc: toml::Value = toml::from_str(&content).ok()?;
+        doc.get("registries")?
+            .get(registry)?
+            .get("index")?
+            .as_str()
+            .map(|s: &str| s.to_string())
+    })
+}
+
+/// Resolve the index polled for propagation of a package
+///
+/// # Arguments
+///
+/// - `&PublishSettings`: Cargo publish settings of the package
+/// - `&PublishOptions`: Publish options holding the default index URL
+///
+/// # Returns
+///
+/// - `Option<String>`: Index URL, None if a named registry has no known index
+fn resolve_polling_index(settings: &PublishSettings, options: &PublishOptions) -> Option<String> {
+    if let Some(index) = &settings.index {
+        return Some(index.clone());
+    }
+    match &settings.registry {
+        Some(registry) => registry_index_url(registry),
+        None => Some(options.index_url.clone()),
+    }
+}
+
+/// Build the relative path of a crate entry in a registry index
+///
+/// Follows the cargo index layout (`1/a`, `2/ab`, `3/a/abc`, `ab/cd/abcd...`).
+///
+/// # Arguments
+///
+/// - `&str`: Crate name
+///
+/// # Returns
+///
+/// - `String`: Relative path of the index entry
+pub(crate) fn index_entry_path(name: &str) -> String {
+    let name: String = name.to_lowercase();
+    match name.len() {
+        1 => format!("1/{name}"),
+        2 => format!("2/{name}"),
+        3 => format!("3/{}/{name}", &name[..1]),
+        _ => format!("{}/{}/{name}", &name[..2], &name[2..4]),
+    }
+}
+
+/// Fetch the raw index entry of a crate
+///
+/// Supports sparse HTTP indexes (fetched with `curl`) as well as local
+/// directories and `file://` URLs laid out like an index.
+///
+/// # Arguments
+///
+/// - `&str`: Index URL or local directory
+/// - `&str`: Crate name
+///
+/// # Returns
+///
+/// - `Option<String>`: Entry content if it could be fetched
+async fn fetch_index_entry(index_url: &str, name: &str) -> Option<String> {
+    let base: &str = index_url.strip_prefix("sparse+").unwrap_or(index_url);
+    let entry_path: String = index_entry_path(name);
+    if base.starts_with("http://") || base.starts_with("https://") {
+        let url: String = format!("{}/{entry_path}", base.trim_end_matches('/'));
+        let output: std::process::Output = Command::new("curl")
+            .arg("-sfL")
+            .arg("--max-time")
+            .arg("10")
+            .arg(&url)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .output()
+            .await
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        return Some(String::from_utf8_lossy(&output.stdout).to_string());
+    }
+    let dir: &str = base.strip_prefix("file://").unwrap_or(base);
+    tokio::fs::read_to_string(Path::new(dir).join(entry_path))
+        .await
+        .ok()
+}
+
+/// Check whether a crate version is listed in a registry index
+///
+/// # Arguments
+///
+/// - `&str`: Index URL or local directory
+/// - `&str`: Crate name
+/// - `&str`: Version to look for
+///
+/// # Returns
+///
+/// - `bool`: True if the version is present in the index
+pub(crate) async fn index_contains_version(index_url: &str, name: &str, version: &str) -> bool {
+    let Some(content) = fetch_index_entry(index_url, name).await else {
+        return false;
+    };
+    content.lines().any(|line: &str| {
+        INDEX_VERSION_REGEX
+            .captures(line)
+            .and_then(|captures: Captures<'_>| captures.get(1))
+            .is_some_and(|vers: regex::Match<'_>| vers.as_str() == version)
+    })
+}
+
+/// Wait until a published package version is visible in the registry index
+///
+/// # Arguments
+///
+/// - `&Package`: Published package
+/// - `&PublishOptions`: Publish options holding index URL and timeout
+/// - `&PublishSettings`: Cargo publish settings of the package
+///
+/// # Returns
+///
+/// - `Option<Duration>`: Time until the version appeared, None on timeout or when disabled
+async fn wait_for_index_propagation(
+    package: &Package,
+    options: &PublishOptions,
+    settings: &PublishSettings,
+) -> Option<Duration> {
+    if options.index_timeout == 0 {
+        return None;
+    }
+    let Some(index_url) = resolve_polling_index(settings, options) else {
+        println!(
+            "No index known for the registry of {}, not waiting for propagation",
+            package.name
+        );
+        return None;
+    };
+    let start: Instant = Instant::now();
+    let timeout: Duration = Duration::from_secs(options.index_timeout);
+    println!(
+        "Waiting for {} v{} to appear in the index...",
+        package.name, package.version
+    );
+    loop {
+        if index_contains_version(&index_url, &package.name, &package.version).await {
+            return Some(start.elapsed());
+        }
+        if start.elapsed() >= timeout {
+            eprintln!(
+                "Timed out after {}s waiting for {} v{} in the index",
+                options.index_timeout, package.name, package.version
+            );
+            return None;
+        }
+        tokio::time::sleep(Duration::from_millis(INDEX_POLL_INTERVAL_MILLIS)).await;
+    }
+}
+
+/// Print the outcome of a single package publish
+///
+/// # Arguments
+///
+/// - `&PublishResult`: Result to print
+fn print_publish_result(result: &PublishResult) {
+    if result.success {
+        if result.retries == 0 {
+            println!("Successfully published {}", result.package_name);
         } else {
-            eprintln!("Failed to publish {}", result.package_name);
+            println!(
+                "Successfully published {} (retried {} times)",
+                result.package_name, result.retries
+            );
+        }
+        if let Some(propagation_time) = result.propagation_time {
+            println!(
+                "{} is visible in the index after {:.1}s",
+                result.package_name,
+                propagation_time.as_secs_f64()
+            );
+        }
+    } else if let (Some(failure), Some(error)) = (&result.failure, &result.error) {
+        eprintln!(
+            "Failed to publish {} ({failure}): {error}",
+            result.package_name
+        );
+    } else if let Some(error) = &result.error {
+        eprintln!("Failed to publish {}: {error}", result.package_name);
+    } else {
+        eprintln!("Failed to publish {}", result.package_name);
+    }
+}
+
+/// Publish all packages of one topological level concurrently
+///
+/// # Arguments
+///
+/// - `Vec<Package>`: Packages of the level
+/// - `&PublishOptions`: Publish options
+/// - `&HookConfig`: Hooks run before and after each package
+///
+/// # Returns
+///
+/// - `Result<Vec<PublishResult>, PublishError>`: Results in the same order as the level
+async fn publish_level(
+    level: Vec<Package>,
+    options: &PublishOptions,
+    hooks: &HookConfig,
+) -> Result<Vec<PublishResult>, PublishError> {
+    let semaphore: Arc<Semaphore> = Arc::new(Semaphore::new(options.jobs.max(1)));
+    let mut join_set: JoinSet<(usize, PublishResult)> = JoinSet::new();
+    for (index, package) in level.into_iter().enumerate() {
+        let semaphore: Arc<Semaphore> = Arc::clone(&semaphore);
+        let options: PublishOptions = options.clone();
+        let settings: PublishSettings = resolve_publish_settings(&package, &options)?;
+        let hooks: HookConfig = hooks.clone();
+        join_set.spawn(async move {
+            let _permit: Result<tokio::sync::OwnedSemaphorePermit, tokio::sync::AcquireError> =
+                semaphore.acquire_owned().await;
+            let context: HookContext = HookContext {
+                package: Some(package.name.clone()),
+                new_version: Some(package.version.clone()),
+                target_dir: Some(package.path.clone()),
+                ..HookContext::new("publish-package")
+            };
+            if let Err(error) = run_hooks(&hooks, HookStage::Pre, &context).await {
+                let result: PublishResult = PublishResult {
+                    package_name: package.name.clone(),
+                    version: package.version.clone(),
+                    success: false,
+                    error: Some(error.to_string()),
+                    failure: Some(PublishFailure::HookFailed),
+                    retries: 0,
+                    propagation_time: None,
+                    duration: Duration::ZERO,
+                };
+                print_publish_result(&result);
+                return (index, result);
+            }
+            match &settings.registry {
+                Some(registry) => println!(
+                    "Publishing {} v{} to {registry}...",
+                    package.name, package.version
+                ),
+                None => println!("Publishing {} v{}...", package.name, package.version),
+            }
+            let mut result: PublishResult =
+                publish_package_with_retry(&package, &options, &settings).await;
+            if result.success {
+                result.propagation_time =
+                    wait_for_index_propagation(&package, &options, &settings).await;
+            }
+            print_publish_result(&result);
+            run_post_hooks(&hooks, &context, result.success).await;
+            (index, result)
+        });
+    }
+    let mut indexed_results: Vec<(usize, PublishResult)> = join_set.join_all().await;
+    indexed_results.sort_by_key(|(index, _): &(usize, PublishResult)| *index);
+    Ok(indexed_results
+        .into_iter()
+        .map(|(_, result): (usize, PublishResult)| result)
+        .collect())
+}
+
+/// Skip the packages of a level that depend on a failed package
+///
+/// Skipped packages are added to the failed set, so their own dependents
+/// in later levels are skipped as well.
+///
+/// # Arguments
+///
+/// - `Vec<Package>`: Packages of the level
+/// - `&mut HashSet<String>`: Names of failed or skipped packages
+///
+/// # Returns
+///
+/// - `(Vec<Package>, Vec<PublishResult>)`: Packages to publish and results of the skipped ones
+pub(crate) fn skip_failed_dependents(
+    level: Vec<Package>,
+    failed: &mut HashSet<String>,
+) -> (Vec<Package>, Vec<PublishResult>) {
+    let mut publishable: Vec<Package> = Vec::new();
+    let mut skipped: Vec<PublishResult> = Vec::new();
+    for package in level {
+        let Some(dependency) = package
+            .local_dependencies
+            .iter()
+            .find(|dependency: &&String| failed.contains(*dependency))
+        else {
+            publishable.push(package);
+            continue;
+        };
+        skipped.push(PublishResult {
+            package_name: package.name.clone(),
+            version: package.version.clone(),
+            success: false,
+            error: Some(format!("skipped because {dependency} was not published")),
+            failure: Some(PublishFailure::DependencyFailed),
+            retries: 0,
+            propagation_time: None,
+            duration: Duration::ZERO,
+        });
+        failed.insert(package.name);
+    }
+    (publishable, skipped)
+}
+
+/// Resolve a `[package]` field, following `field.workspace = true`
+///
+/// # Arguments
+///
+/// - `&toml::Value`: Package table
+/// - `Option<&toml::Value>`: Root `[workspace]` table
+/// - `&str`: Field name
+///
+/// # Returns
+///
+/// - `Option<toml::Value>`: Field value, inherited from `[workspace.package]` if requested
+fn resolve_package_field(
+    package_table: &toml::Value,
+    workspace: Option<&toml::Value>,
+    field: &str,
+) -> Option<toml::Value> {
+    let value: &toml::Value = package_table.get(field)?;
+    let inherits: bool = value
+        .get("workspace")
+        .and_then(|w: &toml::Value| w.as_bool())
+        .unwrap_or(false);
+    if inherits {
+        return workspace?.get("package")?.get(field).cloned();
+    }
+    Some(value.clone())
+}
+
+/// Check whether a keyword is accepted by crates.io
+///
+/// # Arguments
+///
+/// - `&str`: Keyword
+///
+/// # Returns
+///
+/// - `bool`: True if the keyword is valid
+fn is_valid_keyword(keyword: &str) -> bool {
+    let starts_alphanumeric: bool = keyword
+        .chars()
+        .next()
+        .is_some_and(|c: char| c.is_ascii_alphanumeric());
+    starts_alphanumeric
+        && keyword.len() <= MAX_KEYWORD_LENGTH
+        && keyword
+            .chars()
+            .all(|c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '+')
+}
+
+/// Lint the metadata of a single package manifest
+///
+/// # Arguments
+///
+/// - `&Package`: Package to lint
+/// - `Option<&toml::Value>`: Root `[workspace]` table
+/// - `&Path`: Workspace root directory for inherited paths
+///
+/// # Returns
+///
+/// - `Result<Vec<MetadataIssue>, PublishError>`: Problems found in the manifest
+pub(crate) fn lint_package_metadata(
+    package: &Package,
+    workspace: Option<&toml::Value>,
+    workspace_dir: &Path,
+) -> Result<Vec<MetadataIssue>, PublishError> {
+    let doc: toml::Value = read_manifest(&package.path.join("Cargo.toml"))?;
+    let package_table: &toml::Value = doc.get("package").ok_or(PublishError::ManifestParseError)?;
+    let mut issues: Vec<MetadataIssue> = Vec::new();
+    let mut report = |field: &str, message: String| {
+        issues.push(MetadataIssue {
+            package: package.name.clone(),
+            field: field.to_string(),
+            message,
+        });
+    };
+    let field = |name: &str| -> Option<toml::Value> {
+        resolve_package_field(package_table, workspace, name)
+    };
+    let is_inherited = |name: &str| -> bool {
+        package_table
+            .get(name)
+            .and_then(|v: &toml::Value| v.get("workspace"))
+            .is_some()
+    };
+    let has_text = |value: Option<toml::Value>| -> bool {
+        value
+            .as_ref()
+            .and_then(|v: &toml::Value| v.as_str())
+            .is_some_and(|s: &str| !s.trim().is_empty())
+    };
+    if !has_text(field("description")) {
+        report("description", "missing description".to_string());
+    }
+    if !has_text(field("license")) && !has_text(field("license-file")) {
+        report("license", "missing license or license-file".to_string());
+    }
+    if let Some(license_file) =
+        field("license-file").and_then(|v: toml::Value| v.as_str().map(|s: &str| s.to_string()))
+    {
+        let base: &Path = if is_inherited("license-file") {
+            workspace_dir
+        } else {
+            &package.path
+        };
+        if !base.join(&license_file).is_file() {
+            report(
+                "license-file",
+                format!("file '{license_file}' does not exist"),
+            );
+        }
+    }
+    if !has_text(field("repository")) {
+        report("repository", "missing repository".to_string());
+    }
+    let keywords: Vec<String> = field("keywords")
+        .and_then(|v: toml::Value| v.as_array().cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|k: &toml::Value| k.as_str().map(|s: &str| s.to_string()))
+        .collect();
+    if keywords.len() > MAX_KEYWORDS {
+        report(
+            "keywords",
+            format!(
+                "{} keywords given, at most {MAX_KEYWORDS} allowed",
+                keywords.len()
+            ),
+        );
+    }
+    for keyword in keywords.iter().filter(|k: &&String| !is_valid_keyword(k)) {
+        report("keywords", format!("invalid keyword '{keyword}'"));
+    }
+    let categories: Vec<String> = field("categories")
+        .and_then(|v: toml::Value| v.as_array().cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|c: &toml::Value| c.as_str().map(|s: &str| s.to_string()))
+        .collect();
+    for category in categories
+        .iter()
+        .filter(|c: &&String| !CRATES_IO_CATEGORIES.contains(&c.as_str()))
+    {
+        report("categories", format!("unknown category slug '{category}'"));
+    }
+    if let Some(readme) =
+        field("readme").and_then(|v: toml::Value| v.as_str().map(|s: &str| s.to_string()))
+    {
+        let base: &Path = if is_inherited("readme") {
+            workspace_dir
+        } else {
+            &package.path
+        };
+        if !base.join(&readme).is_file() {
+            report("readme", format!("file '{readme}' does not exist"));
+        }
+    }
+    let workspace_dependencies: Option<&toml::Table> = workspace
+        .and_then(|w: &toml::Value| w.get("dependencies"))
+        .and_then(|d: &toml::Value| d.as_table());
+    for section in ["dependencies", "build-dependencies"] {
+        let Some(table) = doc.get(section).and_then(|s: &toml::Value| s.as_table()) else {
+            continue;
+        };
+        for (dep_key, dep_value) in table {
+            let inherited: Option<&toml::Value> = dep_value
+                .get("workspace")
+                .and_then(|w: &toml::Value| w.as_bool())
+                .filter(|w: &bool| *w)
+                .and_then(|_| workspace_dependencies?.get(dep_key));
+            let resolved: &toml::Value = inherited.unwrap_or(dep_value);
+            let version: Option<&str> = match resolved {
+                toml::Value::String(version) => Some(version.as_str()),
+                _ => resolved
+                    .get("version")
+                    .and_then(|v: &toml::Value| v.as_str()),
+            };
+            let field_name: String = format!("{section}.{dep_key}");
+            match version {
+                Some(version) if version.trim() == "*" => {
+                    report(&field_name, "wildcard version requirement".to_string());
+                }
+                None if resolved.get("path").is_some() || resolved.get("git").is_some() => {
+                    report(
+                        &field_name,
+                        "path or git dependency without version".to_string(),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(issues)
+}
+
+/// Format metadata problems as an aligned table
+///
+/// # Arguments
+///
+/// - `&[MetadataIssue]`: Problems to format
+///
+/// # Returns
+///
+/// - `String`: Table with package, field and problem columns
+pub(crate) fn format_metadata_issues(issues: &[MetadataIssue]) -> String {
+    let package_width: usize = issues
+        .iter()
+        .map(|issue: &MetadataIssue| issue.package.len())
+        .chain(std::iter::once("PACKAGE".len()))
+        .max()
+        .unwrap_or_default();
+    let field_width: usize = issues
+        .iter()
+        .map(|issue: &MetadataIssue| issue.field.len())
+        .chain(std::iter::once("FIELD".len()))
+        .max()
+        .unwrap_or_default();
+    let mut table: String = format!(
+        "{:<package_width$}  {:<field_width$}  PROBLEM\n",
+        "PACKAGE", "FIELD"
+    );
+    for issue in issues {
+        table.push_str(&format!(
+            "{:<package_width$}  {:<field_width$}  {}\n",
+            issue.package, issue.field, issue.message
+        ));
+    }
+    table
+}
+
+/// Lint the metadata of all packages before publishing
+///
+/// # Arguments
+///
+/// - `&Path`: Path to workspace root Cargo.toml
+/// - `&[Package]`: Packages to lint
+///
+/// # Returns
+///
+/// - `Result<Vec<MetadataIssue>, PublishError>`: Problems across all packages
+pub(crate) fn lint_packages_metadata(
+    workspace_root: &Path,
+    packages: &[Package],
+) -> Result<Vec<MetadataIssue>, PublishError> {
+    let root_doc: toml::Value = read_manifest(workspace_root)?;
+    let workspace_dir: PathBuf = manifest_dir(workspace_root);
+    let mut issues: Vec<MetadataIssue> = Vec::new();
+    for package in packages {
+        issues.extend(lint_package_metadata(
+            package,
+            root_doc.get("workspace"),
+            &workspace_dir,
+        )?);
+    }
+    Ok(issues)
+}
+
+/// Select packages by name
+///
+/// Applies `--package` (or `default-members` when no package is given) and
+/// `--exclude`.
+///
+/// # Arguments
+///
+/// - `&[Package]`: All workspace packages
+/// - `Option<Vec<String>>`: Names of the workspace default members
+/// - `&PackageSelection`: Selection given on the command line
+///
+/// # Returns
+///
+/// - `Result<Vec<Package>, PublishError>`: Selected packages or error for unknown names
+pub(crate) fn select_packages_by_name(
+    packages: &[Package],
+    default_members: Option<Vec<String>>,
+    selection: &PackageSelection,
+) -> Result<Vec<Package>, PublishError> {
+    let requested: Vec<&String> = selection
+        .packages
+        .iter()
+        .chain(selection.excludes.iter())
+        .chain(selection.from.iter())
+        .collect();
+    for name in requested {
+        if !packages.iter().any(|p: &Package| &p.name == name) {
+            return Err(PublishError::PackageNotFound(name.clone()));
+        }
+    }
+    let included: Option<Vec<String>> = if selection.packages.is_empty() {
+        default_members
+    } else {
+        Some(selection.packages.clone())
+    };
+    Ok(packages
+        .iter()
+        .filter(|package: &&Package| {
+            included
+                .as_ref()
+                .is_none_or(|names: &Vec<String>| names.contains(&package.name))
+                && !selection.excludes.contains(&package.name)
+        })
+        .cloned()
+        .collect())
+}
+
+/// Select the packages to publish
+///
+/// Applies `--package` (or `default-members` when no package is given),
+/// `--exclude`, `publish = false` / registry restrictions (for the registry
+/// each package is published to) and `--from`.
+///
+/// # Arguments
+///
+/// - `&[Package]`: All workspace packages
+/// - `Option<Vec<String>>`: Names of the workspace default members
+/// - `&PublishOptions`: Publish options holding the selection
+///
+/// # Returns
+///
+/// - `Result<Vec<Package>, PublishError>`: Selected packages or error for unknown names
+pub(crate) fn select_packages(
+    packages: &[Package],
+    default_members: Option<Vec<String>>,
+    options: &PublishOptions,
+) -> Result<Vec<Package>, PublishError> {
+    let mut selected: Vec<Package> = Vec::new();
+    for package in &select_packages_by_name(packages, default_members, &options.selection)? {
+        let settings: PublishSettings = resolve_publish_settings(package, options)?;
+        if !package.is_publishable(settings.registry.as_deref()) {
+            match &settings.registry {
+                Some(registry) => println!(
+                    "Skipping {}: publishing to {registry} is not allowed",
+                    package.name
+                ),
+                None => println!("Skipping {}: publishing is disabled", package.name),
+            }
+            continue;
+        }
+        selected.push(package.clone());
+    }
+    select_from(packages, selected, options.selection.from.as_deref())
+}
+
+/// Keep the selected packages from `--from` onwards in topological order
+///
+/// # Arguments
+///
+/// - `&[Package]`: All workspace packages
+/// - `Vec<Package>`: Selected packages
+/// - `Option<&str>`: Package to resume from, None keeps every package
+///
+/// # Returns
+///
+/// - `Result<Vec<Package>, PublishError>`: Remaining packages or error for a dependency cycle
+pub(crate) fn select_from(
+    packages: &[Package],
+    mut selected: Vec<Package>,
+    from: Option<&str>,
+) -> Result<Vec<Package>, PublishError> {
+    let Some(from) = from else {
+        return Ok(selected);
+    };
+    let order: Vec<String> = topological_levels(packages)?
+        .into_iter()
+        .flatten()
+        .map(|package: Package| package.name)
+        .collect();
+    let start: usize = order
+        .iter()
+        .position(|name: &String| name == from)
+        .unwrap_or(0);
+    let remaining: Vec<String> = order[start..].to_vec();
+    selected.retain(|package: &Package| remaining.contains(&package.name));
+    Ok(selected)
+}
+
+/// Keep only packages changed since their last release
+///
+/// With `--changed-since <ref>` every package is compared against that
+/// reference; with `--changed` each package is compared against its own
+/// last release tag, and packages without a tag count as changed.
+/// Dependents of changed packages are added when `with_dependents` is set.
+///
+/// # Arguments
+///
+/// - `Vec<Package>`: Selected packages
+/// - `&PackageSelection`: Selection holding the change filters
+///
+/// # Returns
+///
+/// - `Result<Vec<Package>, PublishError>`: Changed packages (and dependents)
+pub(crate) async fn filter_changed_packages(
+    selected: Vec<Package>,
+    selection: &PackageSelection,
+) -> Result<Vec<Package>, PublishError> {
+    if !selection.changed && selection.changed_since.is_none() {
+        return Ok(selected);
+    }
+    let mut changed: Vec<String> = Vec::new();
+    for package in &selected {
+        let reference: Option<String> = match &selection.changed_since {
+            Some(reference) => Some(reference.clone()),
+            None => last_release_tag(&package.path, &package.name).await?,
+        };
+        let is_changed: bool = match reference {
+            Some(reference) => has_changes_since(&package.path, &reference).await?,
+            None => true,
+        };
+        if is_changed {
+            changed.push(package.name.clone());
+        } else {
+            println!("Skipping {}: unchanged since last release", package.name);
+        }
+    }
+    if selection.with_dependents {
+        let mut added: bool = true;
+        while added {
+            added = false;
+            for package in &selected {
+                if !changed.contains(&package.name)
+                    && package
+                        .local_dependencies
+                        .iter()
+                        .any(|dep: &String| changed.contains(dep))
+                {
+                    changed.push(package.name.clone());
+                    added = true;
+                }
+            }
+        }
+    }
+    Ok(selected
+        .into_iter()
+        .filter(|package: &Package| changed.contains(&package.name))
+        .collect())
+}
+
+/// Find unselected, unpublished packages that selected packages depend on
+///
+/// Each dependency is looked up in the index of the registry it publishes
+/// to; dependencies on a registry without a known index are not checked.
+///
+/// # Arguments
+///
+/// - `&[Package]`: All workspace packages
+/// - `&[Package]`: Selected packages
+/// - `&PublishOptions`: Publish options holding the default index URL
+///
+/// # Returns
+///
+/// - `Result<Vec<(String, Package)>, PublishError>`: Dependent package names with the missing dependency
+pub(crate) async fn find_unpublished_dependencies(
+    packages: &[Package],
+    selected: &[Package],
+    options: &PublishOptions,
+) -> Result<Vec<(String, Package)>, PublishError> {
+    let mut missing: Vec<(String, Package)> = Vec::new();
+    for package in selected {
+        for dep in &package.local_dependencies {
+            if selected.iter().any(|p: &Package| &p.name == dep) {
+                continue;
+            }
+            let Some(dependency) = packages.iter().find(|p: &&Package| &p.name == dep) else {
+                continue;
+            };
+            let settings: PublishSettings = resolve_publish_settings(dependency, options)?;
+            let Some(index_url) = resolve_polling_index(&settings, options) else {
+                continue;
+            };
+            if !index_contains_version(&index_url, &dependency.name, &dependency.version).await {
+                missing.push((package.name.clone(), dependency.clone()));
+            }
+        }
+    }
+    Ok(missing)
+}
+
+/// Keep the tail of a stderr output for reports
+///
+/// # Arguments
+///
+/// - `&str`: Captured stderr
+///
+/// # Returns
+///
+/// - `String`: Stderr shortened to `REPORT_STDERR_LIMIT` characters
+pub(crate) fn truncate_stderr(stderr: &str) -> String {
+    let stderr: &str = stderr.trim();
+    let length: usize = stderr.chars().count();
+    if length <= REPORT_STDERR_LIMIT {
+        return stderr.to_string();
+    }
+    let tail: String = stderr.chars().skip(length - REPORT_STDERR_LIMIT).collect();
+    format!("...{tail}")
+}
+
+/// Escape a string for a JSON string literal
+///
+/// # Arguments
+///
+/// - `&str`: Raw string
+///
+/// # Returns
+///
+/// - `String`: Escaped string without surrounding quotes
+pub(crate) fn escape_json(value: &str) -> String {
+    let mut escaped: String = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape a string for XML text and attribute values
+///
+/// # Arguments
+///
+/// - `&str`: Raw string
+///
+/// # Returns
+///
+/// - `String`: Escaped string
+fn escape_xml(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c: &char| !c.is_control() || matches!(c, '\n' | '\r' | '\t'))
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Render publish results as a JSON report
+///
+/// # Arguments
+///
+/// - `&[PublishResult]`: Publish results
+/// - `Option<&str>`: Error that aborted publishing
+///
+/// # Returns
+///
+/// - `String`: JSON document
+pub(crate) fn format_report_json(results: &[PublishResult], error: Option<&str>) -> String {
+    let json_string = |value: Option<String>| -> String {
+        match value {
+            Some(value) => format!("\"{}\"", escape_json(&value)),
+            None => "null".to_string(),
+        }
+    };
+    let entries: Vec<String> = results
+        .iter()
+        .map(|result: &PublishResult| {
+            let fields: Vec<String> = vec![
+                format!("\"package\": \"{}\"", escape_json(&result.package_name)),
+                format!("\"version\": \"{}\"", escape_json(&result.version)),
+                format!("\"status\": \"{}\"", result.status().as_str()),
+                format!("\"retries\": {}", result.retries),
+                format!("\"duration_secs\": {:.3}", result.duration.as_secs_f64()),
+                format!(
+                    "\"failure\": {}",
+                    json_string(
+                        result
+                            .failure
+                            .as_ref()
+                            .map(|failure: &PublishFailure| failure.to_string())
+                    )
+                ),
+                format!(
+                    "\"stderr\": {}",
+                    json_string(result.error.as_deref().map(truncate_stderr))
+                ),
+            ];
+            format!("    {{\n      {}\n    }}", fields.join(",\n      "))
+        })
+        .collect();
+    let error: String = json_string(error.map(str::to_string));
+    if entries.is_empty() {
+        return format!("{{\n  \"error\": {error},\n  \"packages\": []\n}}\n");
+    }
+    format!(
+        "{{\n  \"error\": {error},\n  \"packages\": [\n{}\n  ]\n}}\n",
+        entries.join(",\n")
+    )
+}
+
+/// Render publish results as a JUnit XML report
+///
+/// # Arguments
+///
+/// - `&[PublishResult]`: Publish results
+/// - `Option<&str>`: Error that aborted publishing, reported as a failed `publish` test case
+///
+/// # Returns
+///
+/// - `String`: JUnit XML document with one test case per package
+pub(crate) fn format_report_junit(results: &[PublishResult], error: Option<&str>) -> String {
+    let count = |status: PublishStatus| -> usize {
+        results
+            .iter()
+            .filter(|result: &&PublishResult| result.status() == status)
+            .count()
+    };
+    let total_time: f64 = results
+        .iter()
+        .map(|result: &PublishResult| result.duration.as_secs_f64())
+        .sum();
+    let aborted: usize = usize::from(error.is_some());
+    let counts: String = format!(
+        "tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{total_time:.3}\"",
+        results.len() + aborted,
+        count(PublishStatus::Failed) + aborted,
+        count(PublishStatus::Skipped),
+    );
+    let mut report: String = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    report.push_str(&format!("<testsuites name=\"publish\" {counts}>\n"));
+    report.push_str(&format!("  <testsuite name=\"publish\" {counts}>\n"));
+    if let Some(error) = error {
+        report.push_str(&format!(
+            "    <testcase classname=\"publish\" name=\"publish\" time=\"0.000\">\n      <failure message=\"{}\"/>\n    </testcase>\n",
+            escape_xml(error)
+        ));
+    }
+    for result in results {
+        report.push_str(&format!(
+            "    <testcase classname=\"publish\" name=\"{} v{}\" time=\"{:.3}\"",
+            escape_xml(&result.package_name),
+            escape_xml(&result.version),
+            result.duration.as_secs_f64(),
+        ));
+        let message: String = result
+            .failure
+            .as_ref()
+            .map(|failure: &PublishFailure| failure.to_string())
+            .unwrap_or_default();
+        match result.status() {
+            PublishStatus::Published => report.push_str("/>\n"),
+            PublishStatus::Skipped => report.push_str(&format!(
+                ">\n      <skipped message=\"{}\"/>\n    </testcase>\n",
+                escape_xml(&message)
+            )),
+            PublishStatus::Failed => report.push_str(&format!(
+                ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n",
+                escape_xml(&message),
+                escape_xml(&truncate_stderr(
+                    result.error.as_deref().unwrap_or_default()
+                ))
+            )),
+        }
+    }
+    report.push_str("  </testsuite>\n</testsuites>\n");
+    report
+}
+
+/// Escape text for a Markdown table cell or heading
+///
+/// # Arguments
+///
+/// - `&str`: Raw text
+///
+/// # Returns
+///
+/// - `String`: Text with `|` and backslashes escaped and line breaks replaced by spaces
+fn escape_markdown(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+/// Render publish results as a Markdown summary
+///
+/// # Arguments
+///
+/// - `&[PublishResult]`: Publish results
+/// - `Option<&str>`: Error that aborted publishing
+///
+/// # Returns
+///
+/// - `String`: Markdown table followed by the stderr of failed packages
+pub(crate) fn format_report_markdown(results: &[PublishResult], error: Option<&str>) -> String {
+    let mut report: String = String::from("## Publish summary\n\n");
+    if let Some(error) = error {
+        report.push_str(&format!(
+            "**Publishing aborted:** {}\n\n",
+            escape_markdown(error)
+        ));
+    }
+    report.push_str("| Package | Version | Status | Retries | Duration |\n");
+    report.push_str("| --- | --- | --- | --- | --- |\n");
+    for result in results {
+        report.push_str(&format!(
+            "| {} | {} | {} | {} | {:.1}s |\n",
+            escape_markdown(&result.package_name),
+            escape_markdown(&result.version),
+            result.status().as_str(),
+            result.retries,
+            result.duration.as_secs_f64(),
+        ));
+    }
+    let failed: Vec<&PublishResult> = results
+        .iter()
+        .filter(|result: &&PublishResult| result.status() == PublishStatus::Failed)
+        .collect();
+    if !failed.is_empty() {
+        report.push_str("\n### Failures\n");
+        for result in failed {
+            let stderr: String = truncate_stderr(result.error.as_deref().unwrap_or_default());
+            let longest_run: usize = stderr
+                .split(|c: char| c != '`')
+                .map(str::len)
+                .max()
+                .unwrap_or_default();
+            let fence: String = "`".repeat(longest_run.max(3) + 1);
+            report.push_str(&format!(
+                "\n#### {} v{}\n\n{fence}text\n{stderr}\n{fence}\n",
+                escape_markdown(&result.package_name),
+                escape_markdown(&result.version),
+            ));
+        }
+    }
+    report
+}
+
+/// Write the publish report file
+///
+/// # Arguments
+///
+/// - `&Path`: Report file path
+/// - `ReportFormat`: Report format
+/// - `&[PublishResult]`: Publish results
+/// - `Option<&str>`: Error that aborted publishing
+///
+/// # Returns
+///
+/// - `Result<(), PublishError>`: Success or error
+pub(crate) fn write_publish_report(
+    path: &Path,
+    format: ReportFormat,
+    results: &[PublishResult],
+    error: Option<&str>,
+) -> Result<(), PublishError> {
+    let content: String = match format {
+        ReportFormat::Json => format_report_json(results, error),
+        ReportFormat::Junit => format_report_junit(results, error),
+        ReportFormat::Markdown => format_report_markdown(results, error),
+    };
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        create_dir_all(parent)?;
+    }
+    write(path, content)?;
+    Ok(())
+}
+
+/// Publish the selected packages of a workspace
+///
+/// All selected manifests are linted first and nothing is published while
+/// metadata problems remain. Packages are then published level by level:
+/// all packages of a level run concurrently (bounded by `jobs`), and the
+/// next level only starts after the whole level has finished and become
+/// visible in the index. Dependents of a package that failed are skipped.
+///
+/// # Arguments
+///
+/// - `&Path`: Path to workspace Cargo.toml
+/// - `&PublishOptions`: Publish options
+/// - `&mut Vec<PublishResult>`: Results collected so far, kept when an error aborts publishing
+///
+/// # Returns
+///
+/// - `Result<(), PublishError>`: Success or the error that aborted publishing
+async fn publish_workspace(
+    path: &Path,
+    options: &PublishOptions,
+    results: &mut Vec<PublishResult>,
+) -> Result<(), PublishError> {
+    let packages: Vec<Package> = discover_packages(path)?;
+    let default_members: Option<Vec<String>> = discover_default_members(path)?;
+    let selected: Vec<Package> = select_packages(&packages, default_members, options)?;
+    let selected: Vec<Package> = filter_changed_packages(selected, &options.selection).await?;
+    if selected.is_empty() {
+        return Ok(());
+    }
+    let issues: Vec<MetadataIssue> = lint_packages_metadata(path, &selected)?;
+    if !issues.is_empty() {
+        eprint!("{}", format_metadata_issues(&issues));
+        return Err(PublishError::MetadataLint(issues.len()));
+    }
+    for (name, dependency) in find_unpublished_dependencies(&packages, &selected, options).await? {
+        eprintln!(
+            "Warning: {name} depends on {} v{}, which is not selected and not published yet",
+            dependency.name, dependency.version
+        );
+    }
+    let levels: Vec<Vec<Package>> = topological_levels(&selected)?;
+    let hooks: HookConfig = load_hook_config(path)?;
+    let mut failed: HashSet<String> = HashSet::new();
+    for level in levels {
+        let (level, skipped): (Vec<Package>, Vec<PublishResult>) =
+            skip_failed_dependents(level, &mut failed);
+        for result in &skipped {
+            print_publish_result(result);
+        }
+        results.extend(skipped);
+        let level_results: Vec<PublishResult> = publish_level(level, options, &hooks).await?;
+        failed.extend(
+            level_results
+                .iter()
+                .filter(|result: &&PublishResult| result.status() == PublishStatus::Failed)
+                .map(|result: &PublishResult| result.package_name.clone()),
+        );
+        results.extend(level_results);
+    }
+    Ok(())
+}
+
+/// Execute publish command for all packages in workspace
+///
+/// The report file is written even when publishing is aborted, for example
+/// by metadata problems or a dependency cycle, and then records the error.
+///
+/// # Arguments
+///
+/// - `&str`: Path to workspace Cargo.toml
+/// - `&PublishOptions`: Publish options
+///
+/// # Returns
+///
+/// - `Result<Vec<PublishResult>, PublishError>`: Results for all packages
+pub(crate) async fn execute_publish(
+    manifest_path: &str,
+    options: &PublishOptions,
+) -> Result<Vec<PublishResult>, PublishError> {
+    let mut results: Vec<PublishResult> = Vec::new();
+    let outcome: Result<(), PublishError> =
+        publish_workspace(Path::new(manifest_path), options, &mut results).await;
+    if let Some(report) = &options.report {
+        let format: ReportFormat = options
+            .report_format
+            .unwrap_or_else(|| ReportFormat::from_path(report));
+        let error: Option<String> = outcome.as_ref().err().map(PublishError::to_string);
+        match write_publish_report(report, format, &results, error.as_deref()) {
+            Ok(()) => println!("Publish report written to {}", report.display()),
+            Err(write_error) if outcome.is_err() => {
+                eprintln!("Failed to write publish report: {write_error}");
+            }
+            Err(write_error) => return Err(write_error),
         }
-        results.push(result);
     }
-    Ok(results)
+    outcome.map(|()| results)
 }
@@ -0,0 +1,204 @@
+use crate::*;
+
+impl From<&Args> for PackageSelection {
+    fn from(args: &Args) -> Self {
+        Self {
+            packages: args.packages.clone(),
+            excludes: args.excludes.clone(),
+            from: args.from.clone(),
+            changed: args.changed,
+            changed_since: args.changed_since.clone(),
+            with_dependents: args.with_dependents,
+        }
+    }
+}
+
+impl TryFrom<&Args> for PublishOptions {
+    type Error = PublishError;
+
+    fn try_from(args: &Args) -> Result<Self, Self::Error> {
+        Ok(Self {
+            max_retries: args.max_retries,
+            jobs: args.jobs,
+            index_url: args
+                .index_url
+                .clone()
+                .unwrap_or_else(|| CRATES_IO_INDEX_URL.to_string()),
+            index_timeout: args.index_timeout,
+            retry_max_delay: args.retry_max_delay,
+            retry_jitter: args.retry_jitter,
+            selection: PackageSelection::from(args),
+            settings: PublishSettings {
+                registry: args.registry.clone(),
+                index: args.index.clone(),
+                features: args.features.clone(),
+                no_verify: args.no_verify,
+                target: args.target.clone(),
+                cargo_args: args.extra_args.clone(),
+            },
+            report: args.report.as_ref().map(PathBuf::from),
+            report_format: args
+                .report_format
+                .as_deref()
+                .map(ReportFormat::from_str)
+                .transpose()?,
+        })
+    }
+}
+
+impl PublishFailure {
+    /// Check whether the failure is transient and worth retrying
+    ///
+    /// # Returns
+    ///
+    /// - `bool`: True for network, 5xx, 429 and index lag failures
+    pub(crate) fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::RateLimited(_) | Self::ServerError | Self::Network | Self::DependencyNotFound
+        )
+    }
+}
+
+impl Package {
+    /// Check whether the package may be published to a registry
+    ///
+    /// # Arguments
+    ///
+    /// - `Option<&str>`: Registry name, None for crates.io
+    ///
+    /// # Returns
+    ///
+    /// - `bool`: True if the `publish` field allows the registry
+    pub(crate) fn is_publishable(&self, registry: Option<&str>) -> bool {
+        match &self.publish_registries {
+            None => true,
+            Some(registries) => {
+                let registry: &str = registry.unwrap_or("crates-io");
+                registries.iter().any(|r: &String| r == registry)
+            }
+        }
+    }
+}
+
+impl PublishSettings {
+    /// Merge package settings over these command settings
+    ///
+    /// Values set by the package win, features and extra arguments are combined.
+    ///
+    /// # Arguments
+    ///
+    /// - `&PublishSettings`: Package settings
+    ///
+    /// # Returns
+    ///
+    /// - `Self`: Merged settings
+    pub(crate) fn merge(&self, package: &PublishSettings) -> Self {
+        let mut features: Vec<String> = self.features.clone();
+        for feature in &package.features {
+            if !features.contains(feature) {
+                features.push(feature.clone());
+            }
+        }
+        let mut cargo_args: Vec<String> = self.cargo_args.clone();
+        cargo_args.extend(package.cargo_args.iter().cloned());
+        Self {
+            registry: package.registry.clone().or_else(|| self.registry.clone()),
+            index: package.index.clone().or_else(|| self.index.clone()),
+            features,
+            no_verify: self.no_verify || package.no_verify,
+            target: package.target.clone().or_else(|| self.target.clone()),
+            cargo_args,
+        }
+    }
+}
+
+impl PublishResult {
+    /// Get the report status of the result
+    ///
+    /// # Returns
+    ///
+    /// - `PublishStatus`: Skipped if the version was already uploaded or a dependency failed
+    pub(crate) fn status(&self) -> PublishStatus {
+        if self.success {
+            PublishStatus::Published
+        } else if matches!(
+            self.failure,
+            Some(PublishFailure::AlreadyUploaded | PublishFailure::DependencyFailed)
+        ) {
+            PublishStatus::Skipped
+        } else {
+            PublishStatus::Failed
+        }
+    }
+
+    /// Check whether the version is in the registry after publishing
+    ///
+    /// # Returns
+    ///
+    /// - `bool`: True if the package was published or the version was already uploaded
+    pub(crate) fn in_registry(&self) -> bool {
+        self.success || self.failure == Some(PublishFailure::AlreadyUploaded)
+    }
+}
+
+impl PublishStatus {
+    /// Get the lowercase name used in reports
+    ///
+    /// # Returns
+    ///
+    /// - `&'static str`: Status name
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Published => "published",
+            Self::Skipped => "skipped",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = PublishError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "junit" | "xml" => Ok(Self::Junit),
+            "markdown" | "md" => Ok(Self::Markdown),
+            _ => Err(PublishError::InvalidReportFormat(s.to_string())),
+        }
+    }
+}
+
+impl ReportFormat {
+    /// Infer the report format from a file extension
+    ///
+    /// # Arguments
+    ///
+    /// - `&Path`: Report file path
+    ///
+    /// # Returns
+    ///
+    /// - `Self`: Format matching the extension, JSON if unknown
+    pub(crate) fn from_path(path: &Path) -> Self {
+        path.extension()
+            .and_then(|extension: &std::ffi::OsStr| extension.to_str())
+            .and_then(|extension: &str| extension.parse::<Self>().ok())
+            .unwrap_or(Self::Json)
+    }
+}
+
+impl DependencyKind {
+    /// Get the lowercase name of the dependency kind
+    ///
+    /// # Returns
+    ///
+    /// - `&'static str`: `normal`, `dev` or `build`
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Dev => "dev",
+            Self::Build => "build",
+        }
+    }
+}
@@ -1,8 +1,11 @@
+mod r#const;
 mod r#enum;
 mod r#fn;
+mod r#impl;
+mod r#static;
 mod r#struct;
 
 #[cfg(test)]
 mod test;
 
-pub(crate) use {r#enum::*, r#fn::*, r#struct::*};
+pub(crate) use {r#const::*, r#enum::*, r#fn::*, r#static::*, r#struct::*};
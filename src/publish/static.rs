@@ -0,0 +1,25 @@
+use crate::*;
+
+/// Regex pattern to match the version of an index entry line
+///
+/// This pattern matches the `"vers":"..."` field of a registry index JSON line.
+pub(crate) static INDEX_VERSION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    regex::Regex::new(r#""vers"\s*:\s*"([^"]+)""#).expect("Invalid regex pattern")
+});
+
+/// Regex pattern to match the `try again after` date reported by crates.io
+///
+/// This pattern matches HTTP dates such as `Tue, 19 Oct 2026 12:34:56 GMT`.
+pub(crate) static RETRY_AFTER_DATE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    regex::Regex::new(
+        r"(?i)try again after \w{3}, (\d{1,2}) (\w{3}) (\d{4}) (\d{2}):(\d{2}):(\d{2}) GMT",
+    )
+    .expect("Invalid regex pattern")
+});
+
+/// Regex pattern to match a `Retry-After` header given in seconds
+///
+/// This pattern matches `Retry-After: 30` style hints.
+pub(crate) static RETRY_AFTER_SECONDS_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"(?i)retry-after:\s*(\d+)").expect("Invalid regex pattern")
+});
@@ -1,3 +1,5 @@
+use crate::*;
+
 /// Package information in monorepo
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) struct Package {
@@ -7,8 +9,39 @@ pub(crate) struct Package {
     pub version: String,
     /// Path to package directory
     pub path: std::path::PathBuf,
-    /// Dependencies within the monorepo
+    /// Dependencies within the monorepo that constrain publish order
+    ///
+    /// Dev-dependencies without a `version` are stripped by cargo on publish
+    /// and therefore not listed here.
     pub local_dependencies: Vec<String>,
+    /// Resolved local dependency entries with their section and target
+    pub dependencies: Vec<LocalDependency>,
+    /// Registries allowed by the `publish` field (None allows all, empty disables publishing)
+    pub publish_registries: Option<Vec<String>>,
+}
+
+/// Dependency on a path package resolved from a manifest
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LocalDependency {
+    /// Name of the depended-on package after `package` renames
+    pub name: String,
+    /// Section the dependency is declared in
+    pub kind: DependencyKind,
+    /// Whether the dependency has a `version` requirement
+    pub has_version: bool,
+    /// Target cfg for `[target.'cfg(..)'.*]` sections
+    pub target: Option<String>,
+}
+
+/// Metadata problem found by the pre-publish lint
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct MetadataIssue {
+    /// Package name
+    pub package: String,
+    /// Manifest field the problem relates to
+    pub field: String,
+    /// Description of the problem
+    pub message: String,
 }
 
 /// Publish result for a single package
@@ -16,10 +49,77 @@ pub(crate) struct Package {
 pub(crate) struct PublishResult {
     /// Package name
     pub package_name: String,
+    /// Package version
+    pub version: String,
     /// Whether publish succeeded
     pub success: bool,
     /// Error message if failed
     pub error: Option<String>,
+    /// Classified failure cause if failed
+    pub failure: Option<PublishFailure>,
     /// Number of retries performed
     pub retries: u32,
+    /// Time until the published version appeared in the index
+    pub propagation_time: Option<std::time::Duration>,
+    /// Time spent publishing, including retries
+    pub duration: std::time::Duration,
+}
+
+/// Options for the publish command
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct PublishOptions {
+    /// Maximum retry attempts per package
+    pub max_retries: u32,
+    /// Maximum number of packages published concurrently within a level
+    pub jobs: usize,
+    /// Registry index polled after each publish
+    pub index_url: String,
+    /// Seconds to wait for a version to appear in the index (0 disables waiting)
+    pub index_timeout: u64,
+    /// Upper bound in seconds for the exponential retry backoff
+    pub retry_max_delay: u64,
+    /// Maximum random jitter in milliseconds added to each retry delay
+    pub retry_jitter: u64,
+    /// Packages to publish
+    pub selection: PackageSelection,
+    /// Cargo publish settings given on the command line
+    pub settings: PublishSettings,
+    /// Path of the report file to write after publishing
+    pub report: Option<PathBuf>,
+    /// Format of the report file, inferred from its extension if None
+    pub report_format: Option<ReportFormat>,
+}
+
+/// Packages selected on the command line
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct PackageSelection {
+    /// Packages explicitly selected
+    pub packages: Vec<String>,
+    /// Packages excluded from the selection
+    pub excludes: Vec<String>,
+    /// Package to resume from in topological order
+    pub from: Option<String>,
+    /// Keep only packages changed since their last release tag
+    pub changed: bool,
+    /// Keep only packages changed since this git reference
+    pub changed_since: Option<String>,
+    /// Also keep dependents of changed packages
+    pub with_dependents: bool,
+}
+
+/// Settings of the `cargo publish` invocation
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct PublishSettings {
+    /// Registry name passed as `--registry`
+    pub registry: Option<String>,
+    /// Registry index URL passed as `--index`
+    pub index: Option<String>,
+    /// Features to activate
+    pub features: Vec<String>,
+    /// Skip the verification build
+    pub no_verify: bool,
+    /// Target triple to verify for
+    pub target: Option<String>,
+    /// Extra arguments appended to `cargo publish`
+    pub cargo_args: Vec<String>,
 }
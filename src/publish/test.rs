@@ -7,6 +7,8 @@ fn test_package_creation() {
         version: "0.1.0".to_string(),
         path: std::path::PathBuf::from("."),
         local_dependencies: vec![],
+        dependencies: vec![],
+        publish_registries: None,
     };
     assert_eq!(package.name, "test-package");
     assert_eq!(package.version, "0.1.0");
@@ -20,6 +22,8 @@ fn test_package_clone() {
         version: "0.1.0".to_string(),
         path: std::path::PathBuf::from("."),
         local_dependencies: vec!["dep1".to_string()],
+        dependencies: vec![],
+        publish_registries: None,
     };
     let cloned: Package = package.clone();
     assert_eq!(cloned.name, package.name);
@@ -34,12 +38,16 @@ fn test_package_equality() {
         version: "0.1.0".to_string(),
         path: std::path::PathBuf::from("."),
         local_dependencies: vec![],
+        dependencies: vec![],
+        publish_registries: None,
     };
     let package2: Package = Package {
         name: "test".to_string(),
         version: "0.1.0".to_string(),
         path: std::path::PathBuf::from("."),
         local_dependencies: vec![],
+        dependencies: vec![],
+        publish_registries: None,
     };
     assert_eq!(package1, package2);
 }
@@ -48,9 +56,13 @@ fn test_package_equality() {
 fn test_publish_result_success() {
     let result: PublishResult = PublishResult {
         package_name: "test".to_string(),
+        version: "0.1.0".to_string(),
         success: true,
         error: None,
+        failure: None,
         retries: 0,
+        propagation_time: None,
+        duration: Duration::ZERO,
     };
     assert_eq!(result.package_name, "test");
     assert!(result.success);
@@ -62,9 +74,13 @@ fn test_publish_result_success() {
 fn test_publish_result_failure() {
     let result: PublishResult = PublishResult {
         package_name: "test".to_string(),
+        version: "0.1.0".to_string(),
         success: false,
         error: Some("network error".to_string()),
+        failure: Some(PublishFailure::Network),
         retries: 3,
+        propagation_time: None,
+        duration: Duration::ZERO,
     };
     assert!(!result.success);
     assert_eq!(result.error, Some("network error".to_string()));
@@ -75,9 +91,13 @@ fn test_publish_result_failure() {
 fn test_publish_result_clone() {
     let result: PublishResult = PublishResult {
         package_name: "test".to_string(),
+        version: "0.1.0".to_string(),
         success: true,
         error: None,
+        failure: None,
         retries: 0,
+        propagation_time: None,
+        duration: Duration::ZERO,
     };
     let cloned: PublishResult = result.clone();
     assert_eq!(cloned.package_name, result.package_name);
@@ -91,8 +111,13 @@ fn test_publish_error_display() {
     let error1: PublishError = PublishError::ManifestParseError;
     assert!(error1.to_string().contains("Failed to parse"));
 
-    let error2: PublishError = PublishError::CircularDependency;
+    let error2: PublishError = PublishError::CircularDependency(vec![vec![
+        "a".to_string(),
+        "b".to_string(),
+        "a".to_string(),
+    ]]);
     assert!(error2.to_string().contains("Circular dependency"));
+    assert!(error2.to_string().contains("a -> b -> a"));
 }
 
 #[test]
@@ -101,3 +126,839 @@ fn test_publish_error_from_io() {
     let publish_error: PublishError = PublishError::from(io_error);
     assert!(publish_error.to_string().contains("IO error"));
 }
+
+#[test]
+fn test_topological_levels_groups_independent_packages() {
+    let make_package = |name: &str, deps: &[&str]| -> Package {
+        Package {
+            name: name.to_string(),
+            version: "0.1.0".to_string(),
+            path: std::path::PathBuf::from("."),
+            local_dependencies: deps.iter().map(|d: &&str| d.to_string()).collect(),
+            dependencies: vec![],
+            publish_registries: None,
+        }
+    };
+    let packages: Vec<Package> = vec![
+        make_package("app", &["core", "utils"]),
+        make_package("utils", &["core"]),
+        make_package("macros", &[]),
+        make_package("core", &[]),
+        make_package("cli", &["core"]),
+    ];
+    let levels: Vec<Vec<Package>> = topological_levels(&packages).unwrap();
+    let names: Vec<Vec<String>> = levels
+        .iter()
+        .map(|level: &Vec<Package>| level.iter().map(|p: &Package| p.name.clone()).collect())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            vec!["core".to_string(), "macros".to_string()],
+            vec!["cli".to_string(), "utils".to_string()],
+            vec!["app".to_string()],
+        ]
+    );
+}
+
+#[test]
+fn test_topological_levels_circular() {
+    let packages: Vec<Package> = vec![
+        Package {
+            name: "a".to_string(),
+            version: "0.1.0".to_string(),
+            path: std::path::PathBuf::from("."),
+            local_dependencies: vec!["b".to_string()],
+            dependencies: vec![],
+            publish_registries: None,
+        },
+        Package {
+            name: "b".to_string(),
+            version: "0.1.0".to_string(),
+            path: std::path::PathBuf::from("."),
+            local_dependencies: vec!["a".to_string()],
+            dependencies: vec![],
+            publish_registries: None,
+        },
+    ];
+    assert!(matches!(
+        topological_levels(&packages),
+        Err(PublishError::CircularDependency(cycles)) if cycles == vec![vec![
+            "a".to_string(),
+            "b".to_string(),
+            "a".to_string(),
+        ]]
+    ));
+}
+
+#[test]
+fn test_index_entry_path() {
+    assert_eq!(index_entry_path("a"), "1/a");
+    assert_eq!(index_entry_path("ab"), "2/ab");
+    assert_eq!(index_entry_path("abc"), "3/a/abc");
+    assert_eq!(index_entry_path("Hyperlane"), "hy/pe/hyperlane");
+}
+
+#[test]
+fn test_index_contains_version_local_index() {
+    use std::path::PathBuf;
+    let index_dir: PathBuf = PathBuf::from("./tmp/test_publish_index");
+    let entry: PathBuf = index_dir.join(index_entry_path("hyperlane-demo"));
+    std::fs::create_dir_all(entry.parent().unwrap()).unwrap();
+    std::fs::write(
+        &entry,
+        "{\"name\":\"hyperlane-demo\",\"vers\":\"0.1.0\",\"deps\":[]}\n{\"name\":\"hyperlane-demo\",\"vers\":\"0.1.1\",\"deps\":[]}\n",
+    )
+    .unwrap();
+    let index_url: String = format!("file://{}", index_dir.display());
+    let rt: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    assert!(rt.block_on(index_contains_version(
+        &index_url,
+        "hyperlane-demo",
+        "0.1.1"
+    )));
+    assert!(!rt.block_on(index_contains_version(
+        &index_url,
+        "hyperlane-demo",
+        "0.1.2"
+    )));
+    assert!(!rt.block_on(index_contains_version(&index_url, "missing-crate", "0.1.0")));
+}
+
+#[test]
+fn test_classify_publish_failure() {
+    assert_eq!(
+        classify_publish_failure("error: crate version `0.1.0` is already uploaded"),
+        PublishFailure::AlreadyUploaded
+    );
+    assert_eq!(
+        classify_publish_failure(
+            "warning: manifest has no description, license\nerror: failed to publish\n\nCaused by:\n  the remote server responded with an error (status 503 Service Unavailable)"
+        ),
+        PublishFailure::ServerError
+    );
+    assert_eq!(
+        classify_publish_failure("error: no token found, please run `cargo login`"),
+        PublishFailure::Authentication
+    );
+    assert_eq!(
+        classify_publish_failure(
+            "error: failed to publish\n\nCaused by:\n  missing or empty metadata fields: description, license"
+        ),
+        PublishFailure::MissingMetadata
+    );
+    assert_eq!(
+        classify_publish_failure(
+            "error: failed to verify package tarball\n\nCaused by:\n  could not compile `demo`"
+        ),
+        PublishFailure::Verification
+    );
+    assert_eq!(
+        classify_publish_failure("error: [28] Timeout was reached (Connection timed out)"),
+        PublishFailure::Network
+    );
+    assert_eq!(
+        classify_publish_failure(
+            "error: failed to publish\n\nCaused by:\n  [7] Couldn't connect to server (Failed to connect to crates.io port 443)"
+        ),
+        PublishFailure::Network
+    );
+    assert_eq!(
+        classify_publish_failure("error: something unexpected"),
+        PublishFailure::Unknown
+    );
+}
+
+#[test]
+fn test_classify_permanent_failures_as_not_network() {
+    assert_eq!(
+        classify_publish_failure(
+            "error: failed to publish\n\nCaused by:\n  [60] SSL peer certificate or SSH remote key was not OK (SSL certificate problem: unable to get local issuer certificate)"
+        ),
+        PublishFailure::Unknown
+    );
+    assert_eq!(
+        classify_publish_failure("error: invalid ssl config: `http.sslVersion` must be a string"),
+        PublishFailure::Unknown
+    );
+    assert_eq!(
+        classify_publish_failure(
+            "error: failed to publish\n\nCaused by:\n  the remote server responded with an error (status 403 Forbidden): connection from this network is not allowed"
+        ),
+        PublishFailure::Authentication
+    );
+    assert_eq!(
+        classify_publish_failure(
+            "error: failed to parse manifest\n\nCaused by:\n  unknown field `network-timeout`"
+        ),
+        PublishFailure::Unknown
+    );
+}
+
+#[test]
+fn test_classify_rate_limited_with_retry_after() {
+    let stderr: &str = "error: failed to publish\n\nCaused by:\n  the remote server responded with an error (status 429 Too Many Requests): You have published too many new crates in a short period of time. Please try again after Thu, 01 Jan 1970 00:10:00 GMT or email help@crates.io to have your limit increased.";
+    assert!(matches!(
+        classify_publish_failure(stderr),
+        PublishFailure::RateLimited(_)
+    ));
+    assert_eq!(
+        parse_retry_after(stderr, 60),
+        Some(std::time::Duration::from_secs(540))
+    );
+    assert_eq!(
+        parse_retry_after("Retry-After: 30", 0),
+        Some(std::time::Duration::from_secs(30))
+    );
+    assert_eq!(parse_retry_after("no hint", 0), None);
+}
+
+#[test]
+fn test_publish_failure_is_retryable() {
+    assert!(PublishFailure::Network.is_retryable());
+    assert!(PublishFailure::ServerError.is_retryable());
+    assert!(PublishFailure::RateLimited(None).is_retryable());
+    assert!(!PublishFailure::AlreadyUploaded.is_retryable());
+    assert!(!PublishFailure::Authentication.is_retryable());
+    assert!(!PublishFailure::Verification.is_retryable());
+    assert!(!PublishFailure::MissingMetadata.is_retryable());
+    assert!(!PublishFailure::Unknown.is_retryable());
+}
+
+#[test]
+fn test_discover_and_select_packages() {
+    use std::path::PathBuf;
+    let root: PathBuf = PathBuf::from("./tmp/test_publish_select");
+    let _ = std::fs::remove_dir_all(&root);
+    let write_crate = |dir: &str, manifest: &str| {
+        let crate_dir: PathBuf = root.join(dir);
+        std::fs::create_dir_all(&crate_dir).unwrap();
+        std::fs::write(crate_dir.join("Cargo.toml"), manifest).unwrap();
+    };
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/scratch\"]\n",
+    )
+    .unwrap();
+    write_crate(
+        "crates/core",
+        "[package]\nname = \"core\"\nversion = \"0.1.0\"\n",
+    );
+    write_crate(
+        "crates/app",
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\ncore = { path = \"../core\", version = \"0.1.0\" }\n",
+    );
+    write_crate(
+        "crates/internal",
+        "[package]\nname = \"internal\"\nversion = \"0.1.0\"\npublish = false\n",
+    );
+    write_crate(
+        "crates/private",
+        "[package]\nname = \"private\"\nversion = \"0.1.0\"\npublish = [\"company\"]\n",
+    );
+    write_crate(
+        "crates/scratch",
+        "[package]\nname = \"scratch\"\nversion = \"0.1.0\"\n",
+    );
+    let packages: Vec<Package> = discover_packages(&root.join("Cargo.toml")).unwrap();
+    let mut names: Vec<String> = packages.iter().map(|p: &Package| p.name.clone()).collect();
+    names.sort();
+    assert_eq!(names, vec!["app", "core", "internal", "private"]);
+    let mut options: PublishOptions = PublishOptions {
+        max_retries: 3,
+        jobs: 1,
+        index_url: CRATES_IO_INDEX_URL.to_string(),
+        index_timeout: 0,
+        retry_max_delay: 60,
+        retry_jitter: 0,
+        selection: PackageSelection::default(),
+        settings: PublishSettings::default(),
+        report: None,
+        report_format: None,
+    };
+    let selected_names = |selected: Vec<Package>| -> Vec<String> {
+        let mut names: Vec<String> = selected.into_iter().map(|p: Package| p.name).collect();
+        names.sort();
+        names
+    };
+    let selected: Vec<Package> = select_packages(&packages, None, &options).unwrap();
+    assert_eq!(selected_names(selected), vec!["app", "core"]);
+    let selected: Vec<Package> =
+        select_packages(&packages, Some(vec!["core".to_string()]), &options).unwrap();
+    assert_eq!(selected_names(selected), vec!["core"]);
+    options.selection.excludes = vec!["core".to_string()];
+    let selected: Vec<Package> = select_packages(&packages, None, &options).unwrap();
+    assert_eq!(selected_names(selected), vec!["app"]);
+    options.selection.excludes = Vec::new();
+    options.selection.from = Some("app".to_string());
+    let selected: Vec<Package> = select_packages(&packages, None, &options).unwrap();
+    assert_eq!(selected_names(selected), vec!["app"]);
+    options.selection.from = None;
+    options.selection.packages = vec!["missing".to_string()];
+    assert!(matches!(
+        select_packages(&packages, None, &options),
+        Err(PublishError::PackageNotFound(_))
+    ));
+}
+
+#[test]
+fn test_package_is_publishable() {
+    let mut package: Package = Package {
+        name: "test".to_string(),
+        version: "0.1.0".to_string(),
+        path: std::path::PathBuf::from("."),
+        local_dependencies: vec![],
+        dependencies: vec![],
+        publish_registries: None,
+    };
+    assert!(package.is_publishable(None));
+    package.publish_registries = Some(Vec::new());
+    assert!(!package.is_publishable(None));
+    package.publish_registries = Some(vec!["company".to_string()]);
+    assert!(!package.is_publishable(None));
+    assert!(package.is_publishable(Some("company")));
+}
+
+#[test]
+fn test_extract_local_dependencies_resolves_workspace_entries() {
+    let root: toml::Value = toml::from_str(
+        r#"
+[workspace.dependencies]
+tokio = { version = "1", features = ["full"] }
+regex = "1"
+core = { path = "crates/core", version = "0.1.0" }
+"#,
+    )
+    .unwrap();
+    let member: toml::Value = toml::from_str(
+        r#"
+[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+tokio.workspace = true
+regex = { workspace = true }
+core = { workspace = true }
+my-utils = { path = "../utils", package = "utils", version = "0.1.0" }
+
+[dev-dependencies]
+testing = { path = "../testing" }
+
+[target.'cfg(windows)'.build-dependencies]
+winres = { path = "../winres", version = "0.1.0" }
+"#,
+    )
+    .unwrap();
+    let workspace_dependencies: Option<&toml::Table> = root
+        .get("workspace")
+        .and_then(|w: &toml::Value| w.get("dependencies"))
+        .and_then(|d: &toml::Value| d.as_table());
+    let deps: Vec<LocalDependency> = extract_local_dependencies(&member, workspace_dependencies);
+    let mut names: Vec<String> = deps
+        .iter()
+        .map(|d: &LocalDependency| d.name.clone())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["core", "testing", "utils", "winres"]);
+    let testing: &LocalDependency = deps
+        .iter()
+        .find(|d: &&LocalDependency| d.name == "testing")
+        .unwrap();
+    assert_eq!(testing.kind, DependencyKind::Dev);
+    assert!(!testing.has_version);
+    let winres: &LocalDependency = deps
+        .iter()
+        .find(|d: &&LocalDependency| d.name == "winres")
+        .unwrap();
+    assert_eq!(winres.kind, DependencyKind::Build);
+    assert_eq!(winres.target, Some("cfg(windows)".to_string()));
+}
+
+#[test]
+fn test_find_dependency_cycles_reports_paths() {
+    let make_package = |name: &str, deps: &[&str]| -> Package {
+        Package {
+            name: name.to_string(),
+            version: "0.1.0".to_string(),
+            path: std::path::PathBuf::from("."),
+            local_dependencies: deps.iter().map(|d: &&str| d.to_string()).collect(),
+            dependencies: vec![],
+            publish_registries: None,
+        }
+    };
+    let packages: Vec<Package> = vec![
+        make_package("a", &["b"]),
+        make_package("b", &["c"]),
+        make_package("c", &["a"]),
+        make_package("d", &["a"]),
+        make_package("x", &["y"]),
+        make_package("y", &["x"]),
+    ];
+    let error: PublishError = topological_levels(&packages).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Circular dependency detected: a -> b -> c -> a; x -> y -> x"
+    );
+}
+
+#[test]
+fn test_dev_dependency_cycle_is_allowed() {
+    use std::path::PathBuf;
+    let root: PathBuf = PathBuf::from("./tmp/test_publish_dev_cycle");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(root.join("core")).unwrap();
+    std::fs::create_dir_all(root.join("testkit")).unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"core\", \"testkit\"]\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("core/Cargo.toml"),
+        "[package]\nname = \"core\"\nversion = \"0.1.0\"\n\n[dev-dependencies]\ntestkit = { path = \"../testkit\" }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("testkit/Cargo.toml"),
+        "[package]\nname = \"testkit\"\nversion = \"0.1.0\"\n\n[dependencies]\ncore = { path = \"../core\", version = \"0.1.0\" }\n",
+    )
+    .unwrap();
+    let packages: Vec<Package> = discover_packages(&root.join("Cargo.toml")).unwrap();
+    let levels: Vec<Vec<Package>> = topological_levels(&packages).unwrap();
+    let names: Vec<String> = levels
+        .into_iter()
+        .flatten()
+        .map(|p: Package| p.name)
+        .collect();
+    assert_eq!(names, vec!["core", "testkit"]);
+}
+
+#[test]
+fn test_lint_packages_metadata() {
+    use std::path::PathBuf;
+    let root: PathBuf = PathBuf::from("./tmp/test_publish_lint");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(root.join("good")).unwrap();
+    std::fs::create_dir_all(root.join("bad")).unwrap();
+    std::fs::write(root.join("README.md"), "# readme\n").unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"good\", \"bad\"]\n\n[workspace.package]\nlicense = \"MIT\"\nrepository = \"https://example.com/repo\"\nreadme = \"README.md\"\n\n[workspace.dependencies]\ngood = { path = \"good\" }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("good/Cargo.toml"),
+        "[package]\nname = \"good\"\nversion = \"0.1.0\"\ndescription = \"Good crate\"\nlicense.workspace = true\nrepository.workspace = true\nreadme.workspace = true\nkeywords = [\"http\", \"server\"]\ncategories = [\"web-programming::http-server\"]\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("bad/Cargo.toml"),
+        "[package]\nname = \"bad\"\nversion = \"0.1.0\"\nreadme = \"MISSING.md\"\nkeywords = [\"a\", \"b\", \"c\", \"d\", \"e\", \"-bad\"]\ncategories = [\"not-a-category\"]\n\n[dependencies]\ngood.workspace = true\nregex = \"*\"\n\n[dev-dependencies]\ntestkit = { path = \"../testkit\" }\n",
+    )
+    .unwrap();
+    let manifest: PathBuf = root.join("Cargo.toml");
+    let packages: Vec<Package> = discover_packages(&manifest).unwrap();
+    let issues: Vec<MetadataIssue> = lint_packages_metadata(&manifest, &packages).unwrap();
+    assert!(
+        issues
+            .iter()
+            .all(|issue: &MetadataIssue| issue.package == "bad")
+    );
+    let fields: Vec<&str> = issues
+        .iter()
+        .map(|issue: &MetadataIssue| issue.field.as_str())
+        .collect();
+    assert_eq!(
+        fields,
+        vec![
+            "description",
+            "license",
+            "repository",
+            "keywords",
+            "keywords",
+            "categories",
+            "readme",
+            "dependencies.good",
+            "dependencies.regex",
+        ]
+    );
+    let table: String = format_metadata_issues(&issues);
+    assert!(table.starts_with("PACKAGE"));
+    assert!(table.contains("unknown category slug 'not-a-category'"));
+}
+
+#[test]
+fn test_filter_changed_packages_with_dependents() {
+    use std::path::PathBuf;
+    let repo: PathBuf = PathBuf::from("./tmp/test_publish_changed");
+    let _ = std::fs::remove_dir_all(&repo);
+    for name in ["core", "app", "cli"] {
+        std::fs::create_dir_all(repo.join(name)).unwrap();
+        std::fs::write(repo.join(name).join("lib.rs"), "// v1\n").unwrap();
+    }
+    let make_package = |name: &str, deps: &[&str]| -> Package {
+        Package {
+            name: name.to_string(),
+            version: "0.1.0".to_string(),
+            path: repo.join(name),
+            local_dependencies: deps.iter().map(|d: &&str| d.to_string()).collect(),
+            dependencies: vec![],
+            publish_registries: None,
+        }
+    };
+    let packages: Vec<Package> = vec![
+        make_package("core", &[]),
+        make_package("app", &["core"]),
+        make_package("cli", &[]),
+    ];
+    let mut selection: PackageSelection = PackageSelection {
+        changed: true,
+        ..PackageSelection::default()
+    };
+    let rt: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        run_git(&repo, &["init", "-q"]).await.unwrap();
+        run_git(&repo, &["add", "."]).await.unwrap();
+        run_git(
+            &repo,
+            &[
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-qm",
+                "release",
+                "--no-gpg-sign",
+            ],
+        )
+        .await
+        .unwrap();
+        for name in ["core", "app", "cli"] {
+            run_git(&repo, &["tag", &release_tag_name(name, "0.1.0")])
+                .await
+                .unwrap();
+        }
+        std::fs::write(repo.join("core/lib.rs"), "// v2\n").unwrap();
+        let names = |packages: Vec<Package>| -> Vec<String> {
+            packages.into_iter().map(|p: Package| p.name).collect()
+        };
+        let changed: Vec<Package> = filter_changed_packages(packages.clone(), &selection)
+            .await
+            .unwrap();
+        assert_eq!(names(changed), vec!["core"]);
+        selection.with_dependents = true;
+        let changed: Vec<Package> = filter_changed_packages(packages.clone(), &selection)
+            .await
+            .unwrap();
+        assert_eq!(names(changed), vec!["core", "app"]);
+        selection.changed = false;
+        selection.changed_since = Some("HEAD".to_string());
+        selection.with_dependents = false;
+        let changed: Vec<Package> = filter_changed_packages(packages.clone(), &selection)
+            .await
+            .unwrap();
+        assert_eq!(names(changed), vec!["core"]);
+    });
+}
+
+#[test]
+fn test_publish_settings_from_package_metadata() {
+    use std::path::PathBuf;
+    let root: PathBuf = PathBuf::from("./tmp/test_publish_settings");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"internal\"\nversion = \"0.1.0\"\npublish = [\"company\"]\n\n[package.metadata.hyperlane-cli.publish]\nregistry = \"company\"\nfeatures = [\"full\"]\nno-verify = true\nargs = [\"--locked\"]\n",
+    )
+    .unwrap();
+    let package: Package = Package {
+        name: "internal".to_string(),
+        version: "0.1.0".to_string(),
+        path: root.clone(),
+        local_dependencies: vec![],
+        dependencies: vec![],
+        publish_registries: Some(vec!["company".to_string()]),
+    };
+    let command_settings: PublishSettings = PublishSettings {
+        features: vec!["tls".to_string(), "full".to_string()],
+        target: Some("x86_64-unknown-linux-gnu".to_string()),
+        cargo_args: vec!["--quiet".to_string()],
+        ..PublishSettings::default()
+    };
+    let package_settings: PublishSettings = read_package_publish_settings(&package).unwrap();
+    let settings: PublishSettings = command_settings.merge(&package_settings);
+    assert_eq!(settings.registry.as_deref(), Some("company"));
+    assert!(package.is_publishable(settings.registry.as_deref()));
+    assert!(!package.is_publishable(None));
+    assert_eq!(
+        publish_command_args(&settings),
+        vec![
+            "publish",
+            "--allow-dirty",
+            "--registry",
+            "company",
+            "--features",
+            "tls,full",
+            "--no-verify",
+            "--target",
+            "x86_64-unknown-linux-gnu",
+            "--quiet",
+            "--locked",
+        ]
+    );
+    assert_eq!(
+        publish_command_args(&PublishSettings::default()),
+        vec!["publish", "--allow-dirty"]
+    );
+}
+
+#[test]
+fn test_publish_reports() {
+    let results: Vec<PublishResult> = vec![
+        PublishResult {
+            package_name: "core".to_string(),
+            version: "1.0.0".to_string(),
+            success: true,
+            error: None,
+            failure: None,
+            retries: 1,
+            propagation_time: None,
+            duration: Duration::from_millis(1500),
+        },
+        PublishResult {
+            package_name: "old".to_string(),
+            version: "0.9.0".to_string(),
+            success: false,
+            error: Some("error: crate version `0.9.0` is already uploaded".to_string()),
+            failure: Some(PublishFailure::AlreadyUploaded),
+            retries: 0,
+            propagation_time: None,
+            duration: Duration::from_millis(200),
+        },
+        PublishResult {
+            package_name: "web".to_string(),
+            version: "1.0.0".to_string(),
+            success: false,
+            error: Some("error: failed to verify \"web\" <x> & y\n".to_string()),
+            failure: Some(PublishFailure::Verification),
+            retries: 0,
+            propagation_time: None,
+            duration: Duration::from_secs(2),
+        },
+    ];
+    let statuses: Vec<PublishStatus> = results.iter().map(PublishResult::status).collect();
+    assert_eq!(
+        statuses,
+        vec![
+            PublishStatus::Published,
+            PublishStatus::Skipped,
+            PublishStatus::Failed
+        ]
+    );
+    assert_eq!(
+        results
+            .iter()
+            .map(PublishResult::in_registry)
+            .collect::<Vec<bool>>(),
+        vec![true, true, false]
+    );
+    let json: String = format_report_json(&results, None);
+    assert!(json.contains("\"package\": \"core\""));
+    assert!(json.contains("\"status\": \"skipped\""));
+    assert!(json.contains("\"duration_secs\": 1.500"));
+    assert!(json.contains("\"stderr\": \"error: failed to verify \\\"web\\\" <x> & y\""));
+    assert!(json.contains("\"stderr\": null"));
+    let junit: String = format_report_junit(&results, None);
+    assert!(junit.contains("tests=\"3\" failures=\"1\" skipped=\"1\""));
+    assert!(
+        junit.contains("<testcase classname=\"publish\" name=\"core v1.0.0\" time=\"1.500\"/>")
+    );
+    assert!(junit.contains(
+        "<failure message=\"package verification failed\">error: failed to verify &quot;web&quot; &lt;x&gt; &amp; y</failure>"
+    ));
+    let markdown: String = format_report_markdown(&results, None);
+    assert!(markdown.contains("| core | 1.0.0 | published | 1 | 1.5s |"));
+    assert!(markdown.contains("#### web v1.0.0"));
+    assert!(!markdown.contains("#### old"));
+    let escaped: String = format_report_markdown(
+        &[PublishResult {
+            package_name: "a|b".to_string(),
+            error: Some("error: ```` fence\n| not a row".to_string()),
+            ..results[2].clone()
+        }],
+        Some("cycle a | b"),
+    );
+    assert!(escaped.contains("**Publishing aborted:** cycle a \\| b"));
+    assert!(escaped.contains("| a\\|b | 1.0.0 | failed | 0 | 2.0s |"));
+    assert!(
+        escaped.contains("#### a\\|b v1.0.0\n\n`````text\nerror: ```` fence\n| not a row\n`````")
+    );
+    let aborted_json: String = format_report_json(&[], Some("2 metadata problem(s)"));
+    assert!(aborted_json.contains("\"error\": \"2 metadata problem(s)\""));
+    assert!(json.contains("\"error\": null"));
+    let aborted_junit: String = format_report_junit(&[], Some("cycle"));
+    assert!(aborted_junit.contains("tests=\"1\" failures=\"1\""));
+    assert!(
+        aborted_junit
+            .contains("name=\"publish\" time=\"0.000\">\n      <failure message=\"cycle\"/>")
+    );
+    let long_stderr: String = "x".repeat(REPORT_STDERR_LIMIT + 10);
+    assert_eq!(truncate_stderr(&long_stderr).len(), REPORT_STDERR_LIMIT + 3);
+    assert_eq!(
+        ReportFormat::from_path(Path::new("report.xml")),
+        ReportFormat::Junit
+    );
+    assert_eq!(
+        "md".parse::<ReportFormat>().unwrap(),
+        ReportFormat::Markdown
+    );
+    assert!("yaml".parse::<ReportFormat>().is_err());
+}
+
+#[test]
+fn test_skip_failed_dependents() {
+    let package = |name: &str, dependencies: &[&str]| -> Package {
+        Package {
+            name: name.to_string(),
+            version: "0.1.0".to_string(),
+            path: PathBuf::from("."),
+            local_dependencies: dependencies
+                .iter()
+                .map(|dep: &&str| dep.to_string())
+                .collect(),
+            dependencies: vec![],
+            publish_registries: None,
+        }
+    };
+    let mut failed: HashSet<String> = HashSet::from(["core".to_string()]);
+    let (publishable, skipped): (Vec<Package>, Vec<PublishResult>) = skip_failed_dependents(
+        vec![package("http", &["core"]), package("utils", &[])],
+        &mut failed,
+    );
+    assert_eq!(publishable, vec![package("utils", &[])]);
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].package_name, "http");
+    assert_eq!(skipped[0].status(), PublishStatus::Skipped);
+    assert_eq!(skipped[0].failure, Some(PublishFailure::DependencyFailed));
+    let (publishable, skipped): (Vec<Package>, Vec<PublishResult>) =
+        skip_failed_dependents(vec![package("server", &["http", "utils"])], &mut failed);
+    assert!(publishable.is_empty());
+    assert_eq!(
+        skipped[0].error.as_deref(),
+        Some("skipped because http was not published")
+    );
+}
+
+#[test]
+fn test_find_unpublished_dependencies_uses_package_index() {
+    use std::path::PathBuf;
+    let root: PathBuf = PathBuf::from("./tmp/test_publish_unpublished_dependencies");
+    let _ = std::fs::remove_dir_all(&root);
+    let index_dir: PathBuf = root.join("index");
+    let entry: PathBuf = index_dir.join(index_entry_path("internal-core"));
+    std::fs::create_dir_all(entry.parent().unwrap()).unwrap();
+    std::fs::write(
+        &entry,
+        "{\"name\":\"internal-core\",\"vers\":\"0.2.0\",\"deps\":[]}\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(root.join("core")).unwrap();
+    std::fs::write(
+        root.join("core/Cargo.toml"),
+        format!(
+            "[package]\nname = \"internal-core\"\nversion = \"0.2.0\"\n\n[package.metadata.hyperlane-cli.publish]\nindex = \"file://{}\"\n",
+            index_dir.display()
+        ),
+    )
+    .unwrap();
+    let package = |name: &str, version: &str, path: PathBuf, deps: &[&str]| -> Package {
+        Package {
+            name: name.to_string(),
+            version: version.to_string(),
+            path,
+            local_dependencies: deps.iter().map(|dep: &&str| dep.to_string()).collect(),
+            dependencies: vec![],
+            publish_registries: None,
+        }
+    };
+    let core: Package = package("internal-core", "0.2.0", root.join("core"), &[]);
+    let app: Package = package("internal-app", "0.1.0", root.clone(), &["internal-core"]);
+    let options: PublishOptions = PublishOptions {
+        max_retries: 3,
+        jobs: 1,
+        index_url: format!("file://{}", root.join("missing-index").display()),
+        index_timeout: 0,
+        retry_max_delay: 60,
+        retry_jitter: 0,
+        selection: PackageSelection::default(),
+        settings: PublishSettings::default(),
+        report: None,
+        report_format: None,
+    };
+    let rt: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    let packages: Vec<Package> = vec![core.clone(), app.clone()];
+    let missing: Vec<(String, Package)> = rt
+        .block_on(find_unpublished_dependencies(
+            &packages,
+            std::slice::from_ref(&app),
+            &options,
+        ))
+        .unwrap();
+    assert!(missing.is_empty());
+    let newer_core: Package = package("internal-core", "0.3.0", root.join("core"), &[]);
+    let missing: Vec<(String, Package)> = rt
+        .block_on(find_unpublished_dependencies(
+            &[newer_core.clone(), app.clone()],
+            std::slice::from_ref(&app),
+            &options,
+        ))
+        .unwrap();
+    assert_eq!(missing, vec![("internal-app".to_string(), newer_core)]);
+}
+
+#[test]
+fn test_invalid_report_format_is_rejected() {
+    let raw_args: Vec<String> = ["hyperlane-cli", "publish", "--report-format", "junt"]
+        .iter()
+        .map(|arg: &&str| arg.to_string())
+        .collect();
+    let args: Args = parse_args_from(&raw_args);
+    assert!(matches!(
+        PublishOptions::try_from(&args),
+        Err(PublishError::InvalidReportFormat(format)) if format == "junt"
+    ));
+}
+
+#[test]
+fn test_execute_publish_writes_report_on_error() {
+    let root: PathBuf = PathBuf::from("./tmp/test_publish_report_on_error");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"undocumented\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    let report: PathBuf = root.join("report.json");
+    let raw_args: Vec<String> = ["hyperlane-cli", "publish", "--report", "unused"]
+        .iter()
+        .map(|arg: &&str| arg.to_string())
+        .collect();
+    let options: PublishOptions = PublishOptions {
+        report: Some(report.clone()),
+        ..PublishOptions::try_from(&parse_args_from(&raw_args)).unwrap()
+    };
+    let rt: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    let result: Result<Vec<PublishResult>, PublishError> = rt.block_on(execute_publish(
+        root.join("Cargo.toml").to_str().unwrap(),
+        &options,
+    ));
+    assert!(matches!(result, Err(PublishError::MetadataLint(_))));
+    let content: String = std::fs::read_to_string(&report).unwrap();
+    assert!(content.contains("metadata problem(s) must be fixed before publishing"));
+    assert!(content.contains("\"packages\": []"));
+}
@@ -0,0 +1,8 @@
+/// Changelog file updated by the release command
+pub(crate) const CHANGELOG_FILE_NAME: &str = "CHANGELOG.md";
+
+/// Title written to a newly created changelog
+pub(crate) const CHANGELOG_TITLE: &str = "# Changelog";
+
+/// Number of steps of the release command
+pub(crate) const RELEASE_STEP_COUNT: usize = 9;
@@ -0,0 +1,30 @@
+use crate::*;
+
+/// Error types for release operation
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ReleaseError {
+    /// Working tree has uncommitted changes
+    #[error("Working tree is not clean:\n{}", .0.join("\n"))]
+    DirtyWorkingTree(Vec<String>),
+    /// Version bump failed
+    #[error("Version bump failed: {0}")]
+    BumpFailed(String),
+    /// External command exited with an error
+    #[error("{0} failed: {1}")]
+    CommandFailed(String, String),
+    /// Publishing did not complete for every package
+    #[error("{0} package(s) failed to publish")]
+    PublishFailed(usize),
+    /// User declined to continue at a checkpoint
+    #[error("Release aborted before {0}")]
+    Aborted(String),
+    /// Git command failed
+    #[error(transparent)]
+    GitError(#[from] GitError),
+    /// Publish command failed
+    #[error(transparent)]
+    PublishError(#[from] PublishError),
+    /// IO error
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
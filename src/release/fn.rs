@@ -0,0 +1,543 @@
+use crate::*;
+
+/// Convert days since the unix epoch to a civil date
+///
+/// # Arguments
+///
+/// - `i64`: Days since 1970-01-01
+///
+/// # Returns
+///
+/// - `(i64, i64, i64)`: Year, month (1-12) and day of month
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days: i64 = days + 719468;
+    let era: i64 = days.div_euclid(146097);
+    let day_of_era: i64 = days - era * 146097;
+    let year_of_era: i64 =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year: i64 = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index: i64 = (5 * day_of_year + 2) / 153;
+    let day: i64 = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month: i64 = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year: i64 = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Format a unix timestamp as an ISO 8601 date
+///
+/// # Arguments
+///
+/// - `u64`: Seconds since the unix epoch
+///
+/// # Returns
+///
+/// - `String`: Date in the form `YYYY-MM-DD` (UTC)
+pub(crate) fn format_release_date(timestamp: u64) -> String {
+    let (year, month, day): (i64, i64, i64) = civil_from_days((timestamp / 86400) as i64);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Build the changelog section of a release
+///
+/// # Arguments
+///
+/// - `&str`: Release date
+/// - `&[ReleasedPackage]`: Released package versions
+/// - `&[String]`: Commit subjects since the previous release
+///
+/// # Returns
+///
+/// - `String`: Markdown section starting with a `##` heading
+pub(crate) fn format_changelog_section(
+    date: &str,
+    released: &[ReleasedPackage],
+    commits: &[String],
+) -> String {
+    let single_version: Option<&String> = released
+        .first()
+        .map(|package: &ReleasedPackage| &package.version)
+        .filter(|version: &&String| {
+            released
+                .iter()
+                .all(|package: &ReleasedPackage| &package.version == *version)
+        });
+    let mut section: String = match single_version {
+        Some(version) => format!("## v{version} - {date}\n\n"),
+        None => format!("## {date}\n\n"),
+    };
+    for package in released {
+        section.push_str(&format!("- `{}` {}\n", package.name, package.version));
+    }
+    if !commits.is_empty() {
+        section.push_str("\n### Changes\n\n");
+        for commit in commits {
+            section.push_str(&format!("- {commit}\n"));
+        }
+    }
+    section
+}
+
+/// Insert a release section above the previous releases of a changelog
+///
+/// # Arguments
+///
+/// - `&str`: Current changelog content, empty if the file does not exist
+/// - `&str`: Section to insert
+///
+/// # Returns
+///
+/// - `String`: Updated changelog content
+pub(crate) fn insert_changelog_section(changelog: &str, section: &str) -> String {
+    if changelog.trim().is_empty() {
+        return format!("{CHANGELOG_TITLE}\n\n{section}");
+    }
+    let mut offset: usize = 0;
+    for line in changelog.split_inclusive('\n') {
+        if line.starts_with("## ") {
+            return format!(
+                "{}{section}\n{}",
+                &changelog[..offset],
+                &changelog[offset..]
+            );
+        }
+        offset += line.len();
+    }
+    let separator: &str = if changelog.ends_with("\n\n") {
+        ""
+    } else if changelog.ends_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    format!("{changelog}{separator}{section}")
+}
+
+/// Build the message of the release commit
+///
+/// # Arguments
+///
+/// - `&[ReleasedPackage]`: Released package versions
+///
+/// # Returns
+///
+/// - `String`: Commit message
+pub(crate) fn release_commit_message(released: &[ReleasedPackage]) -> String {
+    match released {
+        [package] => format!("Release {} v{}", package.name, package.version),
+        _ => {
+            let lines: Vec<String> = released
+                .iter()
+                .map(|package: &ReleasedPackage| format!("- {} v{}", package.name, package.version))
+                .collect();
+            format!(
+                "Release {} packages\n\n{}",
+                released.len(),
+                lines.join("\n")
+            )
+        }
+    }
+}
+
+/// Print the header of a release step
+///
+/// # Arguments
+///
+/// - `usize`: Step number
+/// - `&str`: Step description
+fn print_release_step(step: usize, description: &str) {
+    println!("[{step}/{RELEASE_STEP_COUNT}] {description}");
+}
+
+/// Ask the user a yes/no question at a release checkpoint
+///
+/// # Arguments
+///
+/// - `&str`: Question to ask
+/// - `bool`: Answer yes without asking
+///
+/// # Returns
+///
+/// - `bool`: True if the user answered yes
+fn confirm(question: &str, assume_yes: bool) -> bool {
+    if assume_yes {
+        println!("{question} [y/N] y");
+        return true;
+    }
+    print!("{question} [y/N] ");
+    let _ = std::io::Write::flush(&mut std::io::stdout());
+    let mut answer: String = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Run a cargo command in the workspace root
+///
+/// # Arguments
+///
+/// - `&Path`: Workspace root directory
+/// - `&[String]`: Cargo arguments
+///
+/// # Returns
+///
+/// - `Result<(), ReleaseError>`: Success or the captured stderr
+async fn run_cargo(root: &Path, cargo_args: &[String]) -> Result<(), ReleaseError> {
+    let output: std::process::Output = Command::new("cargo")
+        .args(cargo_args)
+        .current_dir(root)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await?;
+    if !output.status.success() {
+        return Err(ReleaseError::CommandFailed(
+            format!("cargo {}", cargo_args.join(" ")),
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Read the files a release rewrites before anything is written
+///
+/// # Arguments
+///
+/// - `&Path`: Path to the workspace root Cargo.toml
+/// - `&Path`: Workspace root directory
+///
+/// # Returns
+///
+/// - `Result<Vec<(PathBuf, Option<String>)>, ReleaseError>`: Manifests, Cargo.lock and changelog with their content, None if missing
+pub(crate) fn snapshot_release_files(
+    manifest_path: &Path,
+    root: &Path,
+) -> Result<Vec<(PathBuf, Option<String>)>, ReleaseError> {
+    let mut paths: Vec<PathBuf> = vec![
+        manifest_path.to_path_buf(),
+        root.join("Cargo.lock"),
+        root.join(CHANGELOG_FILE_NAME),
+    ];
+    for package in discover_packages(manifest_path)? {
+        let member_manifest: PathBuf = package.path.join("Cargo.toml");
+        if !paths.contains(&member_manifest) {
+            paths.push(member_manifest);
+        }
+    }
+    let mut snapshot: Vec<(PathBuf, Option<String>)> = Vec::new();
+    for path in paths {
+        let content: Option<String> = if path.exists() {
+            Some(read_to_string(&path)?)
+        } else {
+            None
+        };
+        snapshot.push((path, content));
+    }
+    Ok(snapshot)
+}
+
+/// Restore files read by `snapshot_release_files`
+///
+/// Files that did not exist before the release are removed.
+///
+/// # Arguments
+///
+/// - `&[(PathBuf, Option<String>)]`: Snapshot to restore
+///
+/// # Returns
+///
+/// - `Result<(), std::io::Error>`: Success or error
+pub(crate) fn restore_release_files(
+    snapshot: &[(PathBuf, Option<String>)],
+) -> Result<(), std::io::Error> {
+    for (path, content) in snapshot {
+        match content {
+            Some(content) => write(path, content)?,
+            None if path.exists() => std::fs::remove_file(path)?,
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+/// Offer to undo the release commit and tags
+///
+/// The commit is only reset if no package reached the registry. Only tags
+/// created by the release are deleted, tags of published packages are kept.
+///
+/// # Arguments
+///
+/// - `&Path`: Workspace root directory
+/// - `&[ReleasedPackage]`: Released package versions
+/// - `&ReleaseProgress`: Tags created and packages published so far
+/// - `bool`: Answer yes without asking
+///
+/// # Returns
+///
+/// - `Result<(), ReleaseError>`: Success or error
+async fn offer_release_rollback(
+    root: &Path,
+    released: &[ReleasedPackage],
+    progress: &ReleaseProgress,
+    assume_yes: bool,
+) -> Result<(), ReleaseError> {
+    let unpublished: Vec<&ReleasedPackage> = released
+        .iter()
+        .filter(|package: &&ReleasedPackage| !progress.published.contains(&package.name))
+        .collect();
+    if unpublished.is_empty() {
+        return Ok(());
+    }
+    let reset_commit: bool = progress.published.is_empty();
+    let question: &str = if reset_commit {
+        "Roll back the release commit and tags?"
+    } else {
+        "Some packages are already published. Delete the tags of the unpublished packages?"
+    };
+    if !confirm(question, assume_yes) {
+        println!("Keeping the release commit and tags");
+        return Ok(());
+    }
+    for package in unpublished {
+        let tag: String = package.tag();
+        if progress.tags.contains(&tag) {
+            run_git(root, &["tag", "-d", &tag]).await?;
+            println!("Deleted tag {tag}");
+        }
+    }
+    if reset_commit {
+        run_git(root, &["reset", "--hard", "-q", "HEAD~1"]).await?;
+        println!("Reset the release commit");
+    }
+    Ok(())
+}
+
+/// Bump versions, refresh Cargo.lock, update the changelog and commit
+///
+/// # Arguments
+///
+/// - `&ReleaseOptions`: Release options
+/// - `&Path`: Workspace root directory
+/// - `&str`: File name of the workspace manifest
+///
+/// # Returns
+///
+/// - `Result<Vec<ReleasedPackage>, ReleaseError>`: Released package versions
+async fn create_release_commit(
+    options: &ReleaseOptions,
+    root: &Path,
+    manifest_name: &str,
+) -> Result<Vec<ReleasedPackage>, ReleaseError> {
+    print_release_step(2, "Bumping workspace versions");
+    let released: Vec<ReleasedPackage> = run_with_hooks_outcome(
+        HookContext::new("bump"),
+        Path::new(&options.manifest_path),
+        async { execute_bump_workspace(&options.manifest_path, &options.bump_type) },
+        |bumped: &Vec<(String, String)>, context: &mut HookContext| {
+            add_bumped_versions(bumped, context);
+            true
+        },
+    )
+    .await
+    .map_err(|error: Box<dyn std::error::Error>| ReleaseError::BumpFailed(error.to_string()))?
+    .into_iter()
+    .map(|(name, version): (String, String)| ReleasedPackage { name, version })
+    .collect();
+    for package in &released {
+        println!("{} bumped to {}", package.name, package.version);
+    }
+    print_release_step(3, "Refreshing Cargo.lock");
+    run_cargo(
+        root,
+        &[
+            "update".to_string(),
+            "--workspace".to_string(),
+            "--manifest-path".to_string(),
+            manifest_name.to_string(),
+        ],
+    )
+    .await?;
+    print_release_step(4, "Updating the changelog");
+    let previous_tag: Option<String> = nearest_release_tag(root).await;
+    let commits: Vec<String> = commit_subjects_since(root, previous_tag.as_deref())
+        .await
+        .unwrap_or_default();
+    let now: u64 = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed: std::time::Duration| elapsed.as_secs())
+        .unwrap_or_default();
+    let section: String = format_changelog_section(&format_release_date(now), &released, &commits);
+    let changelog_path: PathBuf = root.join(CHANGELOG_FILE_NAME);
+    let changelog: String = read_to_string(&changelog_path).unwrap_or_default();
+    write(
+        &changelog_path,
+        insert_changelog_section(&changelog, &section),
+    )?;
+    print_release_step(5, "Creating the release commit");
+    run_git(root, &["add", "-A"]).await?;
+    run_git(
+        root,
+        &["commit", "-q", "-m", &release_commit_message(&released)],
+    )
+    .await?;
+    Ok(released)
+}
+
+/// Tag the release commit, run a publish dry-run and publish
+///
+/// Only released packages selected for publishing are tagged. The dry-run
+/// runs once per distinct set of resolved publish settings, so packages
+/// with their own registry or arguments are checked the way they publish.
+///
+/// # Arguments
+///
+/// - `&ReleaseOptions`: Release options
+/// - `&Path`: Workspace root directory
+/// - `&str`: File name of the workspace manifest
+/// - `&[ReleasedPackage]`: Released package versions
+/// - `&mut ReleaseProgress`: Records created tags and the packages in the registry
+///
+/// # Returns
+///
+/// - `Result<(), ReleaseError>`: Success or error
+async fn tag_and_publish_release(
+    options: &ReleaseOptions,
+    root: &Path,
+    manifest_name: &str,
+    released: &[ReleasedPackage],
+    progress: &mut ReleaseProgress,
+) -> Result<(), ReleaseError> {
+    let manifest_path: &Path = Path::new(&options.manifest_path);
+    let packages: Vec<Package> = discover_packages(manifest_path)?;
+    let default_members: Option<Vec<String>> = discover_default_members(manifest_path)?;
+    let selected: Vec<Package> = select_packages(&packages, default_members, &options.publish)?;
+    print_release_step(6, "Tagging the release");
+    for package in released.iter().filter(|package: &&ReleasedPackage| {
+        selected
+            .iter()
+            .any(|selected: &Package| selected.name == package.name)
+    }) {
+        let tag: String = package.tag();
+        let message: String = format!("{} v{}", package.name, package.version);
+        run_git(root, &["tag", "-a", &tag, "-m", &message]).await?;
+        println!("Created tag {tag}");
+        progress.tags.push(tag);
+    }
+    print_release_step(7, "Running a publish dry-run");
+    let mut dry_runs: Vec<(PublishSettings, Vec<String>)> = Vec::new();
+    for package in &selected {
+        let settings: PublishSettings = resolve_publish_settings(package, &options.publish)?;
+        match dry_runs
+            .iter_mut()
+            .find(|(group, _): &&mut (PublishSettings, Vec<String>)| *group == settings)
+        {
+            Some((_, names)) => names.push(package.name.clone()),
+            None => dry_runs.push((settings, vec![package.name.clone()])),
+        }
+    }
+    for (settings, names) in &dry_runs {
+        let mut dry_run_args: Vec<String> = publish_command_args(settings);
+        dry_run_args.push("--dry-run".to_string());
+        dry_run_args.push("--manifest-path".to_string());
+        dry_run_args.push(manifest_name.to_string());
+        for name in names {
+            dry_run_args.push("--package".to_string());
+            dry_run_args.push(name.clone());
+        }
+        run_cargo(root, &dry_run_args).await?;
+    }
+    print_release_step(8, "Publishing");
+    if !confirm(
+        &format!("Publish {} package(s)?", selected.len()),
+        options.assume_yes,
+    ) {
+        return Err(ReleaseError::Aborted("publishing".to_string()));
+    }
+    let results: Vec<PublishResult> = run_with_hooks_outcome(
+        HookContext::new("publish"),
+        manifest_path,
+        execute_publish(&options.manifest_path, &options.publish),
+        |results: &Vec<PublishResult>, _: &mut HookContext| {
+            results.iter().all(PublishResult::in_registry)
+        },
+    )
+    .await?;
+    let failed_count: usize = results
+        .iter()
+        .filter(|result: &&PublishResult| !result.in_registry())
+        .count();
+    if failed_count > 0 {
+        progress.published.extend(
+            results
+                .iter()
+                .filter(|result: &&PublishResult| result.in_registry())
+                .map(|result: &PublishResult| result.package_name.clone()),
+        );
+        return Err(ReleaseError::PublishFailed(failed_count));
+    }
+    Ok(())
+}
+
+/// Execute release command
+///
+/// Runs the release steps in order: clean tree check, version bump,
+/// Cargo.lock refresh, changelog update, release commit, tags, publish
+/// dry-run, publish and push. Publishing and pushing wait for confirmation.
+/// If a step before the release commit fails the rewritten files are
+/// restored; after the commit every failure, including a declined publish,
+/// offers to roll back the commit and tags.
+///
+/// # Arguments
+///
+/// - `&ReleaseOptions`: Release options
+///
+/// # Returns
+///
+/// - `Result<Vec<ReleasedPackage>, ReleaseError>`: Released package versions
+pub(crate) async fn execute_release(
+    options: &ReleaseOptions,
+) -> Result<Vec<ReleasedPackage>, ReleaseError> {
+    let manifest_path: &Path = Path::new(&options.manifest_path);
+    let root: PathBuf = manifest_dir(manifest_path);
+    let manifest_name: String = manifest_path
+        .file_name()
+        .map(|name: &std::ffi::OsStr| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Cargo.toml".to_string());
+    print_release_step(1, "Checking for a clean working tree");
+    let changes: Vec<String> = uncommitted_changes(&root).await?;
+    if !changes.is_empty() {
+        return Err(ReleaseError::DirtyWorkingTree(changes));
+    }
+    let snapshot: Vec<(PathBuf, Option<String>)> = snapshot_release_files(manifest_path, &root)?;
+    let released: Vec<ReleasedPackage> =
+        match create_release_commit(options, &root, &manifest_name).await {
+            Ok(released) => released,
+            Err(error) => {
+                restore_release_files(&snapshot)?;
+                run_git(&root, &["reset", "-q"]).await?;
+                println!("Restored the manifests, Cargo.lock and changelog");
+                return Err(error);
+            }
+        };
+    let mut progress: ReleaseProgress = ReleaseProgress::default();
+    if let Err(error) =
+        tag_and_publish_release(options, &root, &manifest_name, &released, &mut progress).await
+    {
+        offer_release_rollback(&root, &released, &progress, options.assume_yes).await?;
+        return Err(error);
+    }
+    print_release_step(9, "Pushing");
+    if confirm(
+        "Push the release commit and tags to the remote?",
+        options.assume_yes,
+    ) {
+        run_git(&root, &["push", "--follow-tags"]).await?;
+    } else {
+        println!("Run `git push --follow-tags` to push the release");
+    }
+    Ok(released)
+}
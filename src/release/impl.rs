@@ -0,0 +1,35 @@
+use crate::*;
+
+impl TryFrom<&Args> for ReleaseOptions {
+    type Error = ReleaseError;
+
+    fn try_from(args: &Args) -> Result<Self, Self::Error> {
+        Ok(Self {
+            manifest_path: args
+                .manifest_path
+                .clone()
+                .unwrap_or_else(|| "Cargo.toml".to_string()),
+            bump_type: args.bump_type.unwrap_or(BumpVersionType::Patch),
+            assume_yes: args.yes,
+            publish: PublishOptions {
+                selection: PackageSelection {
+                    changed: false,
+                    changed_since: None,
+                    ..PackageSelection::from(args)
+                },
+                ..PublishOptions::try_from(args)?
+            },
+        })
+    }
+}
+
+impl ReleasedPackage {
+    /// Get the release tag of the package version
+    ///
+    /// # Returns
+    ///
+    /// - `String`: Tag name in the form `<name>-v<version>`
+    pub(crate) fn tag(&self) -> String {
+        release_tag_name(&self.name, &self.version)
+    }
+}
@@ -0,0 +1,10 @@
+mod r#const;
+mod r#enum;
+mod r#fn;
+mod r#impl;
+mod r#struct;
+
+#[cfg(test)]
+mod test;
+
+pub(crate) use {r#const::*, r#enum::*, r#fn::*, r#struct::*};
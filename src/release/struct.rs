@@ -0,0 +1,32 @@
+use crate::*;
+
+/// Options for the release command
+#[derive(Clone, Debug)]
+pub(crate) struct ReleaseOptions {
+    /// Path to the workspace root Cargo.toml
+    pub manifest_path: String,
+    /// Type of version bump to apply
+    pub bump_type: BumpVersionType,
+    /// Answer yes at every checkpoint
+    pub assume_yes: bool,
+    /// Options of the publish step
+    pub publish: PublishOptions,
+}
+
+/// Progress of a release, used to roll back after a failure
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct ReleaseProgress {
+    /// Tags created by the release
+    pub tags: Vec<String>,
+    /// Packages that are in the registry
+    pub published: Vec<String>,
+}
+
+/// Package version created by a release
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ReleasedPackage {
+    /// Package name
+    pub name: String,
+    /// New package version
+    pub version: String,
+}
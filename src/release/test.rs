@@ -0,0 +1,103 @@
+use crate::*;
+
+#[test]
+fn test_format_release_date() {
+    assert_eq!(format_release_date(0), "1970-01-01");
+    assert_eq!(format_release_date(951782400), "2000-02-29");
+    assert_eq!(format_release_date(1792368000), "2026-10-19");
+}
+
+#[test]
+fn test_changelog_section_and_commit_message() {
+    let released: Vec<ReleasedPackage> = vec![
+        ReleasedPackage {
+            name: "core".to_string(),
+            version: "0.2.0".to_string(),
+        },
+        ReleasedPackage {
+            name: "web".to_string(),
+            version: "0.2.0".to_string(),
+        },
+    ];
+    let commits: Vec<String> = vec!["Add router".to_string(), "Fix parser".to_string()];
+    let section: String = format_changelog_section("2026-10-19", &released, &commits);
+    assert_eq!(
+        section,
+        "## v0.2.0 - 2026-10-19\n\n- `core` 0.2.0\n- `web` 0.2.0\n\n### Changes\n\n- Add router\n- Fix parser\n"
+    );
+    assert_eq!(
+        insert_changelog_section("", "## v1\n"),
+        "# Changelog\n\n## v1\n"
+    );
+    assert_eq!(
+        insert_changelog_section("# Changelog\n\nIntro.\n\n## v0\n\n- old\n", "## v1\n"),
+        "# Changelog\n\nIntro.\n\n## v1\n\n## v0\n\n- old\n"
+    );
+    assert_eq!(
+        insert_changelog_section("# Changelog\n", "## v1\n"),
+        "# Changelog\n\n## v1\n"
+    );
+    assert_eq!(
+        release_commit_message(&released),
+        "Release 2 packages\n\n- core v0.2.0\n- web v0.2.0"
+    );
+    assert_eq!(
+        release_commit_message(&released[..1]),
+        "Release core v0.2.0"
+    );
+    assert_eq!(released[1].tag(), "web-v0.2.0");
+}
+
+#[test]
+fn test_release_restores_files_when_a_step_fails() {
+    let root: PathBuf = PathBuf::from("./tmp/test_release_restore");
+    let _ = std::fs::remove_dir_all(&root);
+    create_dir_all(root.join("core/src")).unwrap();
+    let root_manifest: &str = "[workspace]\nmembers = [\"core\"]\nresolver = \"2\"\n";
+    let core_manifest: &str = "[package]\nname = \"core\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\nmissing = { path = \"../missing\" }\n";
+    write(root.join("Cargo.toml"), root_manifest).unwrap();
+    write(root.join("core/Cargo.toml"), core_manifest).unwrap();
+    write(root.join("core/src/lib.rs"), "").unwrap();
+    let options: ReleaseOptions = ReleaseOptions {
+        manifest_path: root.join("Cargo.toml").to_string_lossy().to_string(),
+        bump_type: BumpVersionType::Minor,
+        assume_yes: true,
+        publish: PublishOptions::try_from(&parse_args_from(&["hyperlane-cli".to_string()]))
+            .unwrap(),
+    };
+    let rt: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        run_git(&root, &["init", "-q"]).await.unwrap();
+        run_git(&root, &["add", "."]).await.unwrap();
+        run_git(
+            &root,
+            &[
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-qm",
+                "Initial",
+                "--no-gpg-sign",
+            ],
+        )
+        .await
+        .unwrap();
+        let snapshot: Vec<(PathBuf, Option<String>)> =
+            snapshot_release_files(&root.join("Cargo.toml"), &root).unwrap();
+        assert!(snapshot.contains(&(
+            root.join("core/Cargo.toml"),
+            Some(core_manifest.to_string())
+        )));
+        assert!(snapshot.contains(&(root.join(CHANGELOG_FILE_NAME), None)));
+        let result: Result<Vec<ReleasedPackage>, ReleaseError> = execute_release(&options).await;
+        assert!(matches!(result, Err(ReleaseError::CommandFailed(_, _))));
+        assert_eq!(
+            std::fs::read_to_string(root.join("core/Cargo.toml")).unwrap(),
+            core_manifest
+        );
+        assert!(!root.join(CHANGELOG_FILE_NAME).exists());
+        assert!(uncommitted_changes(&root).await.unwrap().is_empty());
+    });
+}
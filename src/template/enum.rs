@@ -1,3 +1,5 @@
+use crate::*;
+
 /// Types of template components that can be generated
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub(crate) enum TemplateType {
@@ -47,4 +49,10 @@ pub(crate) enum TemplateError {
     /// Directory already exists
     #[error("Directory '{0}' already exists")]
     DirectoryExists(String),
+    /// Derive ordering configuration could not be loaded
+    #[error(transparent)]
+    FmtError(#[from] FmtError),
+    /// Hook configuration could not be loaded or a pre hook failed
+    #[error(transparent)]
+    HookError(#[from] HookError),
 }
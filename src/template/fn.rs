@@ -9,7 +9,7 @@ use crate::*;
 /// # Returns
 ///
 /// - `String`: Directory name
-fn get_directory_name(template_type: &TemplateType) -> String {
+pub(crate) fn get_directory_name(template_type: &TemplateType) -> String {
     match template_type {
         TemplateType::Controller => "controller".to_string(),
         TemplateType::Domain => "domain".to_string(),
@@ -56,19 +56,23 @@ fn ensure_directory(path: &Path) -> Result<(), TemplateError> {
     Ok(())
 }
 
-/// Write mod.rs content with module declarations
+/// Render mod.rs content with module declarations
+///
+/// A `test` module is declared behind `#[cfg(test)]` and not re-exported.
 ///
 /// # Arguments
 ///
-/// - `&Path`: Path to mod.rs file
 /// - `&[&str]`: List of modules to include
 ///
 /// # Returns
 ///
-/// - `Result<(), TemplateError>`: Success or error
-fn write_mod_rs(path: &Path, modules: &[&str]) -> Result<(), TemplateError> {
+/// - `String`: mod.rs content
+pub(crate) fn render_mod_rs(modules: &[&str]) -> String {
     let mut content: String = String::new();
     for module in modules {
+        if *module == "test" {
+            continue;
+        }
         let mod_name: String = if module.starts_with("r#") {
             module.to_string()
         } else {
@@ -76,6 +80,9 @@ fn write_mod_rs(path: &Path, modules: &[&str]) -> Result<(), TemplateError> {
         };
         content.push_str(&format!("mod {mod_name};\n"));
     }
+    if modules.contains(&"test") {
+        content.push_str("\n#[cfg(test)]\nmod test;\n");
+    }
     content.push('\n');
     let mut pub_use_parts: Vec<String> = Vec::new();
     for module in modules {
@@ -89,12 +96,8 @@ fn write_mod_rs(path: &Path, modules: &[&str]) -> Result<(), TemplateError> {
         } else {
             format!("r#{module}")
         };
-        if raw_name == "const" || raw_name == "static" {
-            pub_use_parts.push(mod_name);
-        } else if raw_name == "enum" || raw_name == "fn" {
+        if matches!(raw_name, "const" | "enum" | "fn" | "static" | "struct") {
             pub_use_parts.push(format!("{mod_name}::*"));
-        } else if raw_name == "struct" {
-            pub_use_parts.push(mod_name);
         }
     }
     if !pub_use_parts.is_empty() {
@@ -104,7 +107,21 @@ fn write_mod_rs(path: &Path, modules: &[&str]) -> Result<(), TemplateError> {
     }
     content.push('\n');
     content.push_str("use super::*;\n");
-    write(path, content)?;
+    content
+}
+
+/// Write mod.rs content with module declarations
+///
+/// # Arguments
+///
+/// - `&Path`: Path to mod.rs file
+/// - `&[&str]`: List of modules to include
+///
+/// # Returns
+///
+/// - `Result<(), TemplateError>`: Success or error
+fn write_mod_rs(path: &Path, modules: &[&str]) -> Result<(), TemplateError> {
+    write(path, render_mod_rs(modules))?;
     Ok(())
 }
 
@@ -338,9 +355,9 @@ fn create_model_template(
 ///
 /// # Arguments
 ///
-/// - `&TemplateType`: Type of template component
+/// - `TemplateType`: Type of template component
 /// - `&str`: Name of the component
-/// - `model_sub_type`: Optional model subtype
+/// - `Option<ModelSubType>`: Optional model subtype
 ///
 /// # Returns
 ///
@@ -355,12 +372,13 @@ pub(crate) async fn execute_template(
     let base_path: PathBuf = PathBuf::from(&config.base_directory);
     let dir_name: String = get_directory_name(&config.template_type);
     let type_dir: PathBuf = base_path.join(&dir_name);
-    let target_dir: PathBuf = type_dir.join(&config.component_name);
+    let target_dir: PathBuf = config.target_dir();
     if target_dir.exists() {
         return Err(TemplateError::DirectoryExists(
             target_dir.to_string_lossy().to_string(),
         ));
     }
+    let order: DeriveOrder = load_derive_order(Path::new("Cargo.toml"))?;
     ensure_directory(&type_dir)?;
     match config.template_type {
         TemplateType::Controller => {
@@ -382,6 +400,7 @@ pub(crate) async fn execute_template(
             create_model_template(&target_dir, &config.component_name, &sub_type)?;
         }
     }
+    sort_derives_in_dir(&target_dir, &order).await?;
     let _: Result<(), std::io::Error> = crate::fmt::format_path(&target_dir).await;
     println!(
         "Created {} '{}' at {}",
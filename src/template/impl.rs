@@ -43,6 +43,17 @@ impl TemplateConfig {
             base_directory: "./application".to_string(),
         }
     }
+
+    /// Get the directory the component is generated in
+    ///
+    /// # Returns
+    ///
+    /// - `PathBuf`: `<base_directory>/<type directory>/<component_name>`
+    pub(crate) fn target_dir(&self) -> PathBuf {
+        PathBuf::from(&self.base_directory)
+            .join(get_directory_name(&self.template_type))
+            .join(&self.component_name)
+    }
 }
 
 impl FromStr for ModelSubType {
@@ -197,3 +197,11 @@ fn test_parse_model_sub_type_invalid() {
     assert_eq!(ModelSubType::from_str("").ok(), None);
     assert_eq!(ModelSubType::from_str("unknown").ok(), None);
 }
+
+#[test]
+fn test_render_mod_rs() {
+    assert_eq!(
+        render_mod_rs(&["const", "enum", "fn", "impl", "struct", "test"]),
+        "mod r#const;\nmod r#enum;\nmod r#fn;\nmod r#impl;\nmod r#struct;\n\n#[cfg(test)]\nmod test;\n\npub use {r#const::*, r#enum::*, r#fn::*, r#struct::*};\n\nuse super::*;\n"
+    );
+}
@@ -0,0 +1,10 @@
+/// Error types for workspace discovery
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum WorkspaceError {
+    /// Failed to parse Cargo.toml
+    #[error("Failed to parse {0}")]
+    ManifestParseError(String),
+    /// IO error
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
@@ -0,0 +1,300 @@
+use crate::*;
+
+/// Read and parse a manifest file
+///
+/// # Arguments
+///
+/// - `&Path`: Path to Cargo.toml
+///
+/// # Returns
+///
+/// - `Result<toml::Value, WorkspaceError>`: Parsed manifest or error
+pub(crate) fn read_manifest(manifest_path: &Path) -> Result<toml::Value, WorkspaceError> {
+    let content: String = read_to_string(manifest_path)?;
+    toml::from_str(&content)
+        .map_err(|_| WorkspaceError::ManifestParseError(manifest_path.display().to_string()))
+}
+
+/// Get the directory containing a manifest
+///
+/// # Arguments
+///
+/// - `&Path`: Path to Cargo.toml
+///
+/// # Returns
+///
+/// - `PathBuf`: Manifest directory, `.` for a bare file name
+pub(crate) fn manifest_dir(manifest_path: &Path) -> PathBuf {
+    manifest_path
+        .parent()
+        .filter(|p: &&Path| !p.as_os_str().is_empty())
+        .map_or_else(|| PathBuf::from("."), |p: &Path| p.to_path_buf())
+}
+
+/// Read the string entries of a workspace array field
+///
+/// # Arguments
+///
+/// - `&toml::Value`: Parsed root manifest
+/// - `&str`: Field name inside `[workspace]`
+///
+/// # Returns
+///
+/// - `Option<Vec<String>>`: Entries if the field exists
+pub(crate) fn workspace_string_list(doc: &toml::Value, field: &str) -> Option<Vec<String>> {
+    let entries: &Vec<toml::Value> = doc.get("workspace")?.get(field)?.as_array()?;
+    Some(
+        entries
+            .iter()
+            .filter_map(|entry: &toml::Value| entry.as_str().map(|s: &str| s.to_string()))
+            .collect(),
+    )
+}
+
+/// Check whether a path segment contains glob syntax
+///
+/// # Arguments
+///
+/// - `&str`: Path segment
+///
+/// # Returns
+///
+/// - `bool`: True if the segment contains `*`, `?` or `[`
+fn is_glob_segment(segment: &str) -> bool {
+    segment.contains(['*', '?', '['])
+}
+
+/// Match a bracket character class against a character
+///
+/// # Arguments
+///
+/// - `&[char]`: Class content between `[` and `]`
+/// - `char`: Character to test
+///
+/// # Returns
+///
+/// - `bool`: True if the character is matched by the class
+fn match_char_class(class: &[char], c: char) -> bool {
+    let (negated, class): (bool, &[char]) = match class.first() {
+        Some('!') | Some('^') => (true, &class[1..]),
+        _ => (false, class),
+    };
+    let mut matched: bool = false;
+    let mut index: usize = 0;
+    while index < class.len() {
+        if index + 2 < class.len() && class[index + 1] == '-' {
+            if class[index] <= c && c <= class[index + 2] {
+                matched = true;
+            }
+            index += 3;
+        } else {
+            if class[index] == c {
+                matched = true;
+            }
+            index += 1;
+        }
+    }
+    matched != negated
+}
+
+/// Match a single path segment against a glob pattern
+///
+/// Supports `*`, `?` and `[...]` classes (with `!`/`^` negation and ranges).
+/// A `[` without a closing `]` matches itself, like in cargo.
+///
+/// # Arguments
+///
+/// - `&str`: Glob pattern for one path segment
+/// - `&str`: Segment to test
+///
+/// # Returns
+///
+/// - `bool`: True if the segment matches
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let mut p: usize = 0;
+    let mut t: usize = 0;
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() {
+            match pattern[p] {
+                '*' => {
+                    star = Some((p, t));
+                    p += 1;
+                    continue;
+                }
+                '?' => {
+                    p += 1;
+                    t += 1;
+                    continue;
+                }
+                '[' => match pattern[p + 1..].iter().position(|c: &char| *c == ']') {
+                    Some(len) if match_char_class(&pattern[p + 1..p + 1 + len], text[t]) => {
+                        p += len + 2;
+                        t += 1;
+                        continue;
+                    }
+                    None if text[t] == '[' => {
+                        p += 1;
+                        t += 1;
+                        continue;
+                    }
+                    _ => {}
+                },
+                c if c == text[t] => {
+                    p += 1;
+                    t += 1;
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        match star {
+            Some((star_p, star_t)) => {
+                p = star_p + 1;
+                t = star_t + 1;
+                star = Some((star_p, star_t + 1));
+            }
+            None => return false,
+        }
+    }
+    pattern[p..].iter().all(|c: &char| *c == '*')
+}
+
+/// Expand a workspace member pattern into directories
+///
+/// Every path segment may be a glob, so nested patterns such as
+/// `crates/*/impl` are supported.
+///
+/// # Arguments
+///
+/// - `&Path`: Workspace root directory
+/// - `&str`: Member pattern
+///
+/// # Returns
+///
+/// - `Result<Vec<PathBuf>, WorkspaceError>`: Matching directories in sorted order
+pub(crate) fn expand_member_pattern(
+    base_path: &Path,
+    pattern: &str,
+) -> Result<Vec<PathBuf>, WorkspaceError> {
+    let mut current: Vec<PathBuf> = vec![base_path.to_path_buf()];
+    for segment in pattern
+        .split('/')
+        .filter(|s: &&str| !s.is_empty() && *s != ".")
+    {
+        let mut next: Vec<PathBuf> = Vec::new();
+        for dir in &current {
+            if !is_glob_segment(segment) {
+                let path: PathBuf = dir.join(segment);
+                if path.is_dir() {
+                    next.push(path);
+                }
+                continue;
+            }
+            if !dir.is_dir() {
+                continue;
+            }
+            let mut matches: Vec<PathBuf> = Vec::new();
+            for entry in std::fs::read_dir(dir)? {
+                let entry: std::fs::DirEntry = entry?;
+                let path: PathBuf = entry.path();
+                if path.is_dir() && glob_match(segment, &entry.file_name().to_string_lossy()) {
+                    matches.push(path);
+                }
+            }
+            matches.sort();
+            next.extend(matches);
+        }
+        current = next;
+    }
+    Ok(current)
+}
+
+/// Expand a list of member patterns, applying excludes
+///
+/// # Arguments
+///
+/// - `&Path`: Workspace root directory
+/// - `&[String]`: Member patterns
+/// - `&[String]`: Excluded paths relative to the root
+///
+/// # Returns
+///
+/// - `Result<Vec<PathBuf>, WorkspaceError>`: Member directories containing a Cargo.toml
+fn expand_member_patterns(
+    base_path: &Path,
+    patterns: &[String],
+    excludes: &[String],
+) -> Result<Vec<PathBuf>, WorkspaceError> {
+    let excluded: Vec<PathBuf> = excludes
+        .iter()
+        .map(|exclude: &String| base_path.join(exclude))
+        .collect();
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for pattern in patterns {
+        for dir in expand_member_pattern(base_path, pattern)? {
+            let is_excluded: bool = excluded
+                .iter()
+                .any(|exclude: &PathBuf| dir.starts_with(exclude));
+            if !is_excluded && dir.join("Cargo.toml").is_file() && !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+    }
+    Ok(dirs)
+}
+
+/// Discover the directories of all workspace members
+///
+/// The root directory is included when the root manifest has a `[package]`
+/// section or no `[workspace]` section. Members matching `workspace.exclude`
+/// are skipped.
+///
+/// # Arguments
+///
+/// - `&Path`: Path to workspace root Cargo.toml
+///
+/// # Returns
+///
+/// - `Result<Vec<PathBuf>, WorkspaceError>`: Member directories
+pub(crate) fn discover_member_dirs(manifest_path: &Path) -> Result<Vec<PathBuf>, WorkspaceError> {
+    let doc: toml::Value = read_manifest(manifest_path)?;
+    let base_path: PathBuf = manifest_dir(manifest_path);
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if doc.get("package").is_some() || doc.get("workspace").is_none() {
+        dirs.push(base_path.clone());
+    }
+    let members: Vec<String> = workspace_string_list(&doc, "members").unwrap_or_default();
+    let excludes: Vec<String> = workspace_string_list(&doc, "exclude").unwrap_or_default();
+    for dir in expand_member_patterns(&base_path, &members, &excludes)? {
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    Ok(dirs)
+}
+
+/// Discover the directories of the workspace `default-members`
+///
+/// # Arguments
+///
+/// - `&Path`: Path to workspace root Cargo.toml
+///
+/// # Returns
+///
+/// - `Result<Option<Vec<PathBuf>>, WorkspaceError>`: Directories if `default-members` is set
+pub(crate) fn discover_default_member_dirs(
+    manifest_path: &Path,
+) -> Result<Option<Vec<PathBuf>>, WorkspaceError> {
+    let doc: toml::Value = read_manifest(manifest_path)?;
+    let Some(patterns) = workspace_string_list(&doc, "default-members") else {
+        return Ok(None);
+    };
+    let base_path: PathBuf = manifest_dir(manifest_path);
+    let excludes: Vec<String> = workspace_string_list(&doc, "exclude").unwrap_or_default();
+    Ok(Some(expand_member_patterns(
+        &base_path, &patterns, &excludes,
+    )?))
+}
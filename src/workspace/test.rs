@@ -0,0 +1,73 @@
+use crate::*;
+
+#[test]
+fn test_glob_match() {
+    assert!(glob_match("*", "core"));
+    assert!(glob_match("hyperlane-*", "hyperlane-utils"));
+    assert!(!glob_match("hyperlane-*", "utils"));
+    assert!(glob_match("crate?", "crate1"));
+    assert!(!glob_match("crate?", "crate12"));
+    assert!(glob_match("[ab]*", "api"));
+    assert!(!glob_match("[ab]*", "core"));
+    assert!(glob_match("v[0-9]", "v7"));
+    assert!(glob_match("[!t]*", "core"));
+    assert!(!glob_match("[!t]*", "tests"));
+    assert!(glob_match("*-*-impl", "a-b-impl"));
+    assert!(glob_match("crate[1", "crate[1"));
+    assert!(glob_match("*[", "legacy["));
+    assert!(!glob_match("crate[1", "crate1"));
+}
+
+#[test]
+fn test_discover_member_dirs_nested_patterns_and_excludes() {
+    let root: PathBuf = PathBuf::from("./tmp/test_workspace_members");
+    let _ = std::fs::remove_dir_all(&root);
+    let write_crate = |dir: &str| {
+        let crate_dir: PathBuf = root.join(dir);
+        create_dir_all(&crate_dir).unwrap();
+        write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"x\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+    };
+    write_crate("crates/a/impl");
+    write_crate("crates/b/impl");
+    write_crate("crates/c/impl");
+    write_crate("tools/gen1");
+    write_crate("tools/gen22");
+    create_dir_all(root.join("crates/d/impl")).unwrap();
+    write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*/impl\", \"tools/gen?\"]\nexclude = [\"crates/c\"]\ndefault-members = [\"tools/*\"]\n",
+    )
+    .unwrap();
+    let manifest: PathBuf = root.join("Cargo.toml");
+    let dirs: Vec<PathBuf> = discover_member_dirs(&manifest).unwrap();
+    assert_eq!(
+        dirs,
+        vec![
+            root.join("crates/a/impl"),
+            root.join("crates/b/impl"),
+            root.join("tools/gen1"),
+        ]
+    );
+    let default_dirs: Option<Vec<PathBuf>> = discover_default_member_dirs(&manifest).unwrap();
+    assert_eq!(
+        default_dirs,
+        Some(vec![root.join("tools/gen1"), root.join("tools/gen22")])
+    );
+}
+
+#[test]
+fn test_discover_member_dirs_single_package() {
+    let root: PathBuf = PathBuf::from("./tmp/test_workspace_single");
+    create_dir_all(&root).unwrap();
+    write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"single\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    let dirs: Vec<PathBuf> = discover_member_dirs(&root.join("Cargo.toml")).unwrap();
+    assert_eq!(dirs, vec![root]);
+}